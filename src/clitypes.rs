@@ -162,6 +162,20 @@ impl From<csvs_convert::DescribeError> for CliError {
     }
 }
 
+#[cfg(all(feature = "from", not(feature = "lite")))]
+impl From<arrow::error::ArrowError> for CliError {
+    fn from(err: arrow::error::ArrowError) -> CliError {
+        CliError::Other(format!("Arrow error: {err:?}"))
+    }
+}
+
+#[cfg(all(feature = "from", not(feature = "lite")))]
+impl From<parquet::errors::ParquetError> for CliError {
+    fn from(err: parquet::errors::ParquetError) -> CliError {
+        CliError::Other(format!("Parquet error: {err:?}"))
+    }
+}
+
 #[cfg(all(feature = "to", not(feature = "lite")))]
 impl From<serde_json::Error> for CliError {
     fn from(err: serde_json::Error) -> CliError {
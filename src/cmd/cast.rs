@@ -0,0 +1,480 @@
+static USAGE: &str = r#"
+Casts the values of a CSV to their canonical representation, as typed by a
+JSON Schema or a Frictionless Table Schema, e.g. after validating the CSV with the
+`validate` command.
+
+The columns are matched with the properties of a JSON Schema, or the fields of a
+Table Schema, by name. Their values are cast according to their type:
+  integer    "1,234" => 1234
+  number     "1,234.50" => 1234.5
+  boolean    "Y", "no" => true, false
+  date       "July 4, 2023", "04/07/2023" => 2023-07-04
+  datetime   "2023-07-04 10:30" => 2023-07-04T10:30:00Z (RFC 3339)
+A JSON Schema date or datetime is a string with a "date" or "date-time" format,
+and a JSON Schema boolean may list its values in "x-trueValues" & "x-falseValues",
+as the `schema` command does.
+Values of the other types and columns without a property are not changed, and
+empty values are kept empty.
+
+Numbers are parsed with the --decimal-separator, and may group their digits by
+thousands with a comma, a period (whichever is not the decimal separator), a space
+or an apostrophe. Dates are parsed with the same date parser as the `stats` command.
+Table Schema fields may also set their own decimalChar, groupChar, trueValues,
+falseValues & date format. Otherwise, booleans are one of (case-insensitive)
+true,t,yes,y,1 or false,f,no,n,0.
+
+A row with a value that cannot be cast is rejected, and written to the --rejects
+file if set, with a "cast_errors" column listing the reasons. With --error-columns,
+rows are never rejected. Instead, a "<column>_error" column is added for each
+cast column, with the reason its value could not be cast, and the value is left
+unchanged.
+
+cast exits with a non-zero exit code if any rows were rejected.
+
+For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_cast.rs.
+
+Usage:
+    qsv cast [options] <schema> [<input>]
+    qsv cast --help
+
+cast options:
+    --decimal-separator <arg>  The decimal separator of numbers, either . or ,
+                               [default: .]
+    --prefer-dmy               Prefer to parse dates in dmy format.
+                               Otherwise, use mdy format.
+    --rejects <file>           Write the rejected rows to <file>.
+    --error-columns            Add an error column for each cast column,
+                               instead of rejecting rows.
+
+Common options:
+    -h, --help                 Display this message
+    -o, --output <file>        Write output to <file> instead of stdout.
+    -d, --delimiter <arg>      The field delimiter for reading CSV data.
+                               Must be a single character. (default: ,)
+"#;
+
+use std::{fs, str::FromStr};
+
+use chrono::{NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use qsv_dateparser::parse_with_preference;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    cmd::{
+        stats::{DEFAULT_FALSY, DEFAULT_TRUTHY},
+        validate,
+    },
+    config::{Config, Delimiter},
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_schema:             String,
+    arg_input:              Option<String>,
+    flag_decimal_separator: String,
+    flag_prefer_dmy:        bool,
+    flag_rejects:           Option<String>,
+    flag_error_columns:     bool,
+    flag_output:            Option<String>,
+    flag_delimiter:         Option<Delimiter>,
+}
+
+/// the decimal separator & the thousands group separators of numbers
+#[derive(Clone)]
+struct NumberFormat {
+    decimal: char,
+    groups:  Vec<char>,
+}
+
+impl NumberFormat {
+    fn new(decimal: char, group: Option<char>) -> Self {
+        let groups = match group {
+            Some(group) => vec![group],
+            None => {
+                let mut groups = vec![' ', '\u{a0}', '\u{202f}', '\''];
+                groups.push(if decimal == ',' { '.' } else { ',' });
+                groups
+            }
+        };
+        NumberFormat { decimal, groups }
+    }
+
+    /// parse a number, with its digits optionally grouped by thousands
+    fn parse(&self, value: &str) -> Option<Decimal> {
+        let is_group = |c: char| self.groups.contains(&c);
+        let (sign, digits) = match value.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", value.strip_prefix('+').unwrap_or(value)),
+        };
+        let (int_part, frac_part) = match digits.split_once(self.decimal) {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (digits, None),
+        };
+
+        let int_digits = if int_part.contains(is_group) {
+            let groups: Vec<&str> = int_part.split(is_group).collect();
+            let is_grouped = groups.iter().enumerate().all(|(i, group)| {
+                group.bytes().all(|b| b.is_ascii_digit())
+                    && if i == 0 {
+                        (1..=3).contains(&group.len())
+                    } else {
+                        group.len() == 3
+                    }
+            });
+            if !is_grouped {
+                return None;
+            }
+            groups.concat()
+        } else {
+            int_part.to_string()
+        };
+        if frac_part.map_or(false, |frac_part| frac_part.contains(is_group)) {
+            return None;
+        }
+
+        let number = match frac_part {
+            Some(frac_part) => format!("{sign}{int_digits}.{frac_part}"),
+            None => format!("{sign}{int_digits}"),
+        };
+        Decimal::from_str(&number)
+            .or_else(|_| Decimal::from_scientific(&number))
+            .ok()
+    }
+}
+
+enum CastType {
+    Integer(NumberFormat),
+    Number(NumberFormat),
+    Boolean {
+        true_values:    Vec<String>,
+        false_values:   Vec<String>,
+        case_sensitive: bool,
+    },
+    /// a date, with its chrono format if it's not parsed with the date parser
+    Date(Option<String>),
+    DateTime(Option<String>),
+}
+
+/// a column cast to a type
+struct CastColumn {
+    name:           String,
+    cast_type:      CastType,
+    missing_values: Vec<String>,
+}
+
+impl CastColumn {
+    /// the cast column of a JSON Schema property, if its type is cast
+    fn from_json_schema(
+        name: &str,
+        property: &Value,
+        number_format: &NumberFormat,
+    ) -> Option<Self> {
+        let json_type = match property.get("type") {
+            Some(Value::String(json_type)) => Some(json_type.as_str()),
+            Some(Value::Array(json_types)) => json_types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|json_type| *json_type != "null"),
+            _ => None,
+        };
+        let cast_type = match (json_type?, property.get("format").and_then(Value::as_str)) {
+            ("integer", _) => CastType::Integer(number_format.clone()),
+            ("number", _) => CastType::Number(number_format.clone()),
+            // the listed values are exact, as validate parses them
+            ("boolean", _) => match listed_values(property, "x-trueValues") {
+                None => default_boolean(),
+                Some(true_values) => CastType::Boolean {
+                    true_values,
+                    false_values: listed_values(property, "x-falseValues").unwrap_or_default(),
+                    case_sensitive: true,
+                },
+            },
+            ("string", Some("date")) => CastType::Date(None),
+            ("string", Some("date-time")) => CastType::DateTime(None),
+            _ => return None,
+        };
+        Some(CastColumn {
+            name: name.to_string(),
+            cast_type,
+            missing_values: vec![String::new()],
+        })
+    }
+
+    /// the cast column of a Table Schema field, if its type is cast
+    fn from_table_schema(
+        field: &Value,
+        missing_values: &[String],
+        number_format: &NumberFormat,
+    ) -> Option<Self> {
+        let name = field.get("name").and_then(Value::as_str)?;
+        let date_format = match field.get("format").and_then(Value::as_str) {
+            None | Some("default" | "any") => None,
+            Some(format) => Some(format.to_string()),
+        };
+        let char_property = |key: &str| {
+            field
+                .get(key)
+                .and_then(Value::as_str)
+                .and_then(|s| s.chars().next())
+        };
+        let field_number_format = || {
+            if field.get("decimalChar").is_some() || field.get("groupChar").is_some() {
+                NumberFormat::new(
+                    char_property("decimalChar").unwrap_or(number_format.decimal),
+                    char_property("groupChar"),
+                )
+            } else {
+                number_format.clone()
+            }
+        };
+        let cast_type = match field.get("type").and_then(Value::as_str)? {
+            "integer" | "year" => CastType::Integer(field_number_format()),
+            "number" => CastType::Number(field_number_format()),
+            "boolean" => match (
+                listed_values(field, "trueValues"),
+                listed_values(field, "falseValues"),
+            ) {
+                (None, None) => default_boolean(),
+                (true_values, false_values) => CastType::Boolean {
+                    true_values:    true_values
+                        .unwrap_or_else(|| string_vec(&["true", "True", "TRUE", "1"])),
+                    false_values:   false_values
+                        .unwrap_or_else(|| string_vec(&["false", "False", "FALSE", "0"])),
+                    case_sensitive: true,
+                },
+            },
+            "date" => CastType::Date(date_format),
+            "datetime" => CastType::DateTime(date_format),
+            _ => return None,
+        };
+        Some(CastColumn {
+            name: name.to_string(),
+            cast_type,
+            missing_values: missing_values.to_vec(),
+        })
+    }
+
+    /// cast a value, or return the reason it cannot be cast
+    fn cast(&self, value: &str, prefer_dmy: bool) -> Result<String, String> {
+        if self.missing_values.iter().any(|missing| missing == value) {
+            return Ok(String::new());
+        }
+        let trimmed = value.trim();
+        let cast = match &self.cast_type {
+            CastType::Integer(number_format) => number_format
+                .parse(trimmed)
+                .filter(|number| number.fract().is_zero())
+                .map(|number| number.trunc().normalize().to_string()),
+            CastType::Number(number_format) => number_format
+                .parse(trimmed)
+                .map(|number| number.normalize().to_string()),
+            CastType::Boolean {
+                true_values,
+                false_values,
+                case_sensitive,
+            } => {
+                let matches = |values: &[String]| {
+                    values.iter().any(|v| {
+                        if *case_sensitive {
+                            v == trimmed
+                        } else {
+                            v.eq_ignore_ascii_case(trimmed)
+                        }
+                    })
+                };
+                if matches(true_values) {
+                    Some("true".to_string())
+                } else if matches(false_values) {
+                    Some("false".to_string())
+                } else {
+                    None
+                }
+            }
+            CastType::Date(Some(format)) => NaiveDate::parse_from_str(trimmed, format)
+                .ok()
+                .map(|date| date.format("%Y-%m-%d").to_string()),
+            CastType::Date(None) => parse_with_preference(trimmed, prefer_dmy)
+                .ok()
+                .map(|datetime| datetime.format("%Y-%m-%d").to_string()),
+            CastType::DateTime(Some(format)) => NaiveDateTime::parse_from_str(trimmed, format)
+                .ok()
+                .map(|datetime| {
+                    Utc.from_utc_datetime(&datetime)
+                        .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                }),
+            CastType::DateTime(None) => parse_with_preference(trimmed, prefer_dmy)
+                .ok()
+                .map(|datetime| datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        };
+        cast.ok_or_else(|| format!("cannot cast \"{value}\" to {}", self.type_name()))
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self.cast_type {
+            CastType::Integer(_) => "integer",
+            CastType::Number(_) => "number",
+            CastType::Boolean { .. } => "boolean",
+            CastType::Date(_) => "date",
+            CastType::DateTime(_) => "datetime",
+        }
+    }
+}
+
+fn default_boolean() -> CastType {
+    CastType::Boolean {
+        true_values:    DEFAULT_TRUTHY.split(',').map(ToString::to_string).collect(),
+        false_values:   DEFAULT_FALSY.split(',').map(ToString::to_string).collect(),
+        case_sensitive: false,
+    }
+}
+
+/// the string values listed in a keyword of a schema property or field
+fn listed_values(def: &Value, key: &str) -> Option<Vec<String>> {
+    let values = def.get(key)?.as_array()?;
+    Some(
+        values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+fn string_vec(values: &[&str]) -> Vec<String> {
+    values.iter().map(ToString::to_string).collect()
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let decimal = match args.flag_decimal_separator.as_str() {
+        "." => '.',
+        "," => ',',
+        _ => return fail_clierror!("--decimal-separator must be either . or ,"),
+    };
+    let number_format = NumberFormat::new(decimal, None);
+    let prefer_dmy = args.flag_prefer_dmy || std::env::var("QSV_PREFER_DMY").is_ok();
+
+    let schema: Value = match serde_json::from_str(&fs::read_to_string(&args.arg_schema)?) {
+        Ok(schema) => schema,
+        Err(e) => return fail_clierror!("Cannot parse schema {}: {e}", args.arg_schema),
+    };
+    let columns: Vec<CastColumn> = if validate::is_table_schema(&schema) {
+        let Some(fields) = schema.get("fields").and_then(Value::as_array) else {
+            return fail_clierror!(
+                "{} is a Data Package. Use the Table Schema of one of its resources instead.",
+                args.arg_schema
+            );
+        };
+        let missing_values: Vec<String> = match schema.get("missingValues") {
+            Some(Value::Array(values)) => values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(ToString::to_string)
+                .collect(),
+            _ => vec![String::new()],
+        };
+        fields
+            .iter()
+            .filter_map(|field| {
+                CastColumn::from_table_schema(field, &missing_values, &number_format)
+            })
+            .collect()
+    } else if let Some(Value::Object(properties)) = schema.get("properties") {
+        properties
+            .iter()
+            .filter_map(|(name, property)| {
+                CastColumn::from_json_schema(name, property, &number_format)
+            })
+            .collect()
+    } else {
+        return fail_clierror!("Schema {} has no properties.", args.arg_schema);
+    };
+
+    let rconfig = Config::new(&args.arg_input).delimiter(args.flag_delimiter);
+    let mut rdr = rconfig.reader()?;
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+
+    // the cast column of each CSV column, if any
+    let headers = rdr.headers()?.clone();
+    let header_casts: Vec<Option<&CastColumn>> = headers
+        .iter()
+        .map(|header| columns.iter().find(|col| col.name == header))
+        .collect();
+
+    let mut output_headers = headers.clone();
+    if args.flag_error_columns {
+        for col in header_casts.iter().flatten() {
+            output_headers.push_field(&format!("{}_error", col.name));
+        }
+    }
+    wtr.write_record(&output_headers)?;
+
+    let mut rejects_wtr = match args.flag_rejects {
+        Some(ref rejects) if !args.flag_error_columns => {
+            let mut rejects_wtr = Config::new(&Some(rejects.clone())).writer()?;
+            let mut rejects_headers = headers.clone();
+            rejects_headers.push_field("cast_errors");
+            rejects_wtr.write_record(&rejects_headers)?;
+            Some(rejects_wtr)
+        }
+        _ => None,
+    };
+
+    let mut record = csv::StringRecord::new();
+    let mut cast_record = csv::StringRecord::with_capacity(256, output_headers.len());
+    let mut error_fields: Vec<String> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+    let mut rejected = 0_u64;
+    while rdr.read_record(&mut record)? {
+        cast_record.clear();
+        error_fields.clear();
+        errors.clear();
+        for (i, value) in record.iter().enumerate() {
+            let Some(col) = header_casts.get(i).copied().flatten() else {
+                cast_record.push_field(value);
+                continue;
+            };
+            match col.cast(value, prefer_dmy) {
+                Ok(cast) => {
+                    cast_record.push_field(&cast);
+                    error_fields.push(String::new());
+                }
+                Err(reason) => {
+                    cast_record.push_field(value);
+                    errors.push(format!("{}: {reason}", col.name));
+                    error_fields.push(reason);
+                }
+            }
+        }
+
+        if args.flag_error_columns {
+            // short rows still get all their error columns
+            error_fields.resize(header_casts.iter().flatten().count(), String::new());
+            for error in &error_fields {
+                cast_record.push_field(error);
+            }
+        } else if !errors.is_empty() {
+            rejected += 1;
+            if let Some(ref mut rejects_wtr) = rejects_wtr {
+                record.push_field(&errors.join("; "));
+                rejects_wtr.write_record(&record)?;
+            }
+            continue;
+        }
+        wtr.write_record(&cast_record)?;
+    }
+    wtr.flush()?;
+    if let Some(mut rejects_wtr) = rejects_wtr {
+        rejects_wtr.flush()?;
+    }
+
+    match rejected {
+        0 => Ok(()),
+        1 => fail_clierror!("1 row rejected."),
+        n => fail_clierror!("{n} rows rejected."),
+    }
+}
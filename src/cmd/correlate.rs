@@ -0,0 +1,442 @@
+static USAGE: &str = r#"
+Compute the pairwise correlation & covariance of the numeric columns of CSV data.
+
+The data types of the columns are inferred with the stats command, and only
+Integer & Float columns are used. Correlations are computed in one pass over the
+data, using only the rows where both columns in a pair are not NULL.
+
+By default, the output is in long format, with one row per pair of columns:
+
+    field1,field2,n,covariance,pearson[,spearman]
+
+where n is the number of rows used for the pair, covariance is the sample covariance,
+pearson is the Pearson correlation coefficient & spearman is the Spearman rank
+correlation coefficient (only with --spearman).
+
+With --matrix, a correlation matrix of the statistic selected with --stat is
+returned instead, with a row and a column for each numeric column.
+
+Computing correlations on a large file can be made much faster if you create an
+index for it first with 'qsv index' to enable multithreading.
+
+For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_correlate.rs.
+
+Usage:
+    qsv correlate [options] [<input>]
+    qsv correlate --help
+
+correlate options:
+    -s, --select <arg>        Select a subset of columns to correlate.
+                              Non-numeric columns are ignored.
+                              See 'qsv select --help' for the format details.
+    --spearman                Also compute the Spearman rank correlation.
+                              This requires loading the numeric columns in memory.
+    --matrix                  Return a correlation matrix instead of the long format.
+    --stat <arg>              The statistic to show in the matrix - pearson,
+                              spearman or covariance.
+                              [default: pearson]
+    --round <decimal_places>  Round statistics to <decimal_places>.
+                              [default: 4]
+    -j, --jobs <arg>          The number of jobs to run in parallel.
+                              This works only when the given CSV has an index.
+                              When not set, the number of jobs is set to the
+                              number of CPUs detected.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will NOT be interpreted
+                           as column names. i.e., They will be included
+                           in the correlations.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::{fs, str};
+
+use serde::Deserialize;
+use stats::{merge_all, Commute};
+use threadpool::ThreadPool;
+
+use crate::{
+    cmd::stats::{DEFAULT_FALSY, DEFAULT_TRUTHY},
+    config::{Config, Delimiter},
+    index::Indexed,
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Clone, Deserialize)]
+struct Args {
+    arg_input:       Option<String>,
+    flag_select:     SelectColumns,
+    flag_spearman:   bool,
+    flag_matrix:     bool,
+    flag_stat:       String,
+    flag_round:      u32,
+    flag_jobs:       Option<usize>,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MatrixStat {
+    Pearson,
+    Spearman,
+    Covariance,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let mut args: Args = util::get_args(USAGE, argv)?;
+
+    let matrix_stat = match args.flag_stat.to_lowercase().as_str() {
+        "pearson" => MatrixStat::Pearson,
+        "spearman" => MatrixStat::Spearman,
+        "covariance" => MatrixStat::Covariance,
+        _ => {
+            return fail_clierror!("Invalid --stat: {}", args.flag_stat);
+        }
+    };
+    if args.flag_matrix && matrix_stat == MatrixStat::Spearman {
+        args.flag_spearman = true;
+    }
+
+    let (names, col_indices) = numeric_columns(&args)?;
+    if names.len() < 2 {
+        return fail_clierror!(
+            "At least two numeric columns are required to compute correlations."
+        );
+    }
+
+    let correlations = match args.rconfig().indexed()? {
+        Some(ref idx) if util::njobs(args.flag_jobs) > 1 && idx.count() > 0 => {
+            args.parallel_correlations(&col_indices, idx)
+        }
+        _ => args.sequential_correlations(&col_indices),
+    }?;
+
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+    let round = |val: f64| {
+        if val.is_finite() {
+            util::round_num(val, args.flag_round)
+        } else {
+            String::new()
+        }
+    };
+
+    if args.flag_matrix {
+        let mut header = vec!["field".to_string()];
+        header.extend(names.iter().cloned());
+        wtr.write_record(&header)?;
+        for (i, name) in names.iter().enumerate() {
+            let mut row = Vec::with_capacity(names.len() + 1);
+            row.push(name.clone());
+            for j in 0..names.len() {
+                let val = match matrix_stat {
+                    MatrixStat::Pearson => correlations.pair(i, j).pearson(),
+                    MatrixStat::Covariance => correlations.pair(i, j).covariance(),
+                    MatrixStat::Spearman => correlations.spearman(i, j),
+                };
+                row.push(round(val));
+            }
+            wtr.write_record(&row)?;
+        }
+    } else {
+        let mut header = vec!["field1", "field2", "n", "covariance", "pearson"];
+        if args.flag_spearman {
+            header.push("spearman");
+        }
+        wtr.write_record(&header)?;
+        for i in 0..names.len() {
+            for j in (i + 1)..names.len() {
+                let pair = correlations.pair(i, j);
+                let mut row = vec![
+                    names[i].clone(),
+                    names[j].clone(),
+                    pair.n.to_string(),
+                    round(pair.covariance()),
+                    round(pair.pearson()),
+                ];
+                if args.flag_spearman {
+                    row.push(round(correlations.spearman(i, j)));
+                }
+                wtr.write_record(&row)?;
+            }
+        }
+    }
+    Ok(wtr.flush()?)
+}
+
+impl Args {
+    fn rconfig(&self) -> Config {
+        Config::new(&self.arg_input)
+            .delimiter(self.flag_delimiter)
+            .no_headers(self.flag_no_headers)
+            .select(self.flag_select.clone())
+    }
+
+    fn sequential_correlations(&self, col_indices: &[usize]) -> CliResult<Correlations> {
+        let mut rdr = self.rconfig().reader()?;
+        Ok(self.compute(col_indices, rdr.byte_records()))
+    }
+
+    fn parallel_correlations(
+        &self,
+        col_indices: &[usize],
+        idx: &Indexed<fs::File, fs::File>,
+    ) -> CliResult<Correlations> {
+        let chunk_size = util::chunk_size(idx.count() as usize, util::njobs(self.flag_jobs));
+        let nchunks = util::num_of_chunks(idx.count() as usize, chunk_size);
+
+        let pool = ThreadPool::new(util::njobs(self.flag_jobs));
+        let (send, recv) = channel::bounded(0);
+        for i in 0..nchunks {
+            let (send, args, col_indices) = (send.clone(), self.clone(), col_indices.to_vec());
+            pool.execute(move || {
+                let mut idx = args.rconfig().indexed().unwrap().unwrap();
+                idx.seek((i * chunk_size) as u64).unwrap();
+                let it = idx.byte_records().take(chunk_size);
+                send.send(args.compute(&col_indices, it)).unwrap();
+            });
+        }
+        drop(send);
+        Ok(merge_all(recv.iter())
+            .unwrap_or_else(|| Correlations::new(col_indices.len(), self.flag_spearman)))
+    }
+
+    fn compute<I>(&self, col_indices: &[usize], it: I) -> Correlations
+    where
+        I: Iterator<Item = csv::Result<csv::ByteRecord>>,
+    {
+        let mut correlations = Correlations::new(col_indices.len(), self.flag_spearman);
+        let mut row_values: Vec<Option<f64>> = Vec::with_capacity(col_indices.len());
+        for row in it.flatten() {
+            row_values.clear();
+            row_values.extend(col_indices.iter().map(|&i| {
+                row.get(i)
+                    .and_then(|field| str::from_utf8(field).ok())
+                    .and_then(|field| field.trim().parse::<f64>().ok())
+            }));
+            correlations.add(&row_values);
+        }
+        correlations
+    }
+}
+
+/// infer the column types with stats, and return the names and record indices
+/// of the Integer & Float columns of the selection
+fn numeric_columns(args: &Args) -> CliResult<(Vec<String>, Vec<usize>)> {
+    let stats_args = crate::cmd::stats::Args {
+        arg_input:            args.arg_input.clone(),
+        flag_select:          args.flag_select.clone(),
+        flag_everything:      false,
+        flag_typesonly:       true,
+        flag_mode:            false,
+        flag_cardinality:     false,
+        flag_median:          false,
+        flag_quartiles:       false,
+        flag_mad:             false,
+        flag_nulls:           false,
+        flag_round:           args.flag_round,
+        flag_infer_dates:     false,
+        flag_dates_whitelist: "none".to_string(),
+        flag_prefer_dmy:      false,
+        flag_infer_boolean:   false,
+        flag_truthy:          DEFAULT_TRUTHY.to_string(),
+        flag_falsy:           DEFAULT_FALSY.to_string(),
+        flag_infer_subtypes:  false,
+        flag_jobs:            Some(util::njobs(args.flag_jobs)),
+        flag_output:          None,
+        flag_no_headers:      args.flag_no_headers,
+        flag_delimiter:       args.flag_delimiter,
+    };
+    let (headers, stats) = match stats_args.rconfig().indexed()? {
+        Some(idx) => stats_args.parallel_stats(&stats_args.flag_dates_whitelist, &idx),
+        None => stats_args.sequential_stats(&stats_args.flag_dates_whitelist),
+    }?;
+    let types = stats_args.stats_to_records(stats);
+
+    let rconfig = args.rconfig();
+    let mut rdr = rconfig.reader()?;
+    let sel = rconfig.selection(rdr.byte_headers()?)?;
+
+    let mut names = Vec::new();
+    let mut col_indices = Vec::new();
+    for (pos, (header, typ)) in headers.iter().zip(types.iter()).enumerate() {
+        if let Some("Integer" | "Float") = typ.get(0) {
+            names.push(if args.flag_no_headers {
+                (pos + 1).to_string()
+            } else {
+                String::from_utf8_lossy(header).to_string()
+            });
+            col_indices.push(sel[pos]);
+        }
+    }
+    Ok((names, col_indices))
+}
+
+/// the co-moments of a pair of columns, which are updated & merged
+/// with Welford's/Chan's numerically stable algorithms
+#[derive(Clone, Copy, Default)]
+struct CoMoments {
+    n:      u64,
+    mean_x: f64,
+    mean_y: f64,
+    m2_x:   f64,
+    m2_y:   f64,
+    c_xy:   f64,
+}
+
+impl CoMoments {
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    fn add(&mut self, x: f64, y: f64) {
+        self.n += 1;
+        let n = self.n as f64;
+        let dx = x - self.mean_x;
+        self.mean_x += dx / n;
+        let dy = y - self.mean_y;
+        self.mean_y += dy / n;
+        self.m2_x += dx * (x - self.mean_x);
+        self.m2_y += dy * (y - self.mean_y);
+        self.c_xy += dx * (y - self.mean_y);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn covariance(&self) -> f64 {
+        if self.n < 2 {
+            return f64::NAN;
+        }
+        self.c_xy / (self.n - 1) as f64
+    }
+
+    fn pearson(&self) -> f64 {
+        self.c_xy / (self.m2_x * self.m2_y).sqrt()
+    }
+}
+
+impl Commute for CoMoments {
+    #[allow(clippy::cast_precision_loss)]
+    fn merge(&mut self, other: CoMoments) {
+        if other.n == 0 {
+            return;
+        }
+        if self.n == 0 {
+            *self = other;
+            return;
+        }
+        let (na, nb) = (self.n as f64, other.n as f64);
+        let n = na + nb;
+        let dx = other.mean_x - self.mean_x;
+        let dy = other.mean_y - self.mean_y;
+        self.mean_x += dx * nb / n;
+        self.mean_y += dy * nb / n;
+        self.m2_x += other.m2_x + dx * dx * na * nb / n;
+        self.m2_y += other.m2_y + dy * dy * na * nb / n;
+        self.c_xy += other.c_xy + dx * dy * na * nb / n;
+        self.n += other.n;
+    }
+}
+
+/// the pairwise co-moments of all the numeric columns and with --spearman,
+/// their values, so they can be ranked
+struct Correlations {
+    ncols:   usize,
+    // ncols x ncols, only the upper triangle (including the diagonal) is used
+    pairs:   Vec<CoMoments>,
+    // the values of each column. All columns have the same length,
+    // so the values of a row are at the same index in each column
+    columns: Option<Vec<Vec<Option<f64>>>>,
+}
+
+impl Correlations {
+    fn new(ncols: usize, keep_values: bool) -> Correlations {
+        Correlations {
+            ncols,
+            pairs: vec![CoMoments::default(); ncols * ncols],
+            columns: if keep_values {
+                Some(vec![Vec::new(); ncols])
+            } else {
+                None
+            },
+        }
+    }
+
+    #[inline]
+    fn add(&mut self, row: &[Option<f64>]) {
+        for (i, x) in row.iter().enumerate() {
+            let Some(x) = x else { continue };
+            for (j, y) in row.iter().enumerate().skip(i) {
+                if let Some(y) = y {
+                    self.pairs[i * self.ncols + j].add(*x, *y);
+                }
+            }
+        }
+        if let Some(columns) = self.columns.as_mut() {
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(*value);
+            }
+        }
+    }
+
+    #[inline]
+    fn pair(&self, i: usize, j: usize) -> &CoMoments {
+        let (i, j) = if i <= j { (i, j) } else { (j, i) };
+        &self.pairs[i * self.ncols + j]
+    }
+
+    /// the Pearson correlation of the ranks of the rows where both columns are not NULL
+    fn spearman(&self, i: usize, j: usize) -> f64 {
+        let Some(columns) = self.columns.as_ref() else {
+            return f64::NAN;
+        };
+        let (xs, ys): (Vec<f64>, Vec<f64>) = columns[i]
+            .iter()
+            .zip(columns[j].iter())
+            .filter_map(|(x, y)| x.zip(*y))
+            .unzip();
+
+        let mut comoments = CoMoments::default();
+        for (x, y) in ranks(&xs).into_iter().zip(ranks(&ys)) {
+            comoments.add(x, y);
+        }
+        comoments.pearson()
+    }
+}
+
+impl Commute for Correlations {
+    fn merge(&mut self, other: Correlations) {
+        for (pair, other_pair) in self.pairs.iter_mut().zip(other.pairs) {
+            pair.merge(other_pair);
+        }
+        if let (Some(columns), Some(other_columns)) = (self.columns.as_mut(), other.columns) {
+            for (column, other_column) in columns.iter_mut().zip(other_columns) {
+                column.extend(other_column);
+            }
+        }
+    }
+}
+
+/// the 1-based ranks of the values, with ties getting the average of their ranks
+#[allow(clippy::cast_precision_loss)]
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_unstable_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        // average of the 1-based ranks start+1..=end+1
+        let rank = (start + end) as f64 / 2.0 + 1.0;
+        for &idx in &order[start..=end] {
+            ranks[idx] = rank;
+        }
+        start = end + 1;
+    }
+    ranks
+}
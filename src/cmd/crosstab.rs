@@ -0,0 +1,513 @@
+static USAGE: &str = r#"
+Compute a contingency table (crosstab) of the values of two sets of columns.
+
+Each distinct combination of values of the <rows> columns is a row of the
+table, each distinct combination of values of the <columns> columns is a
+column of the table, and the cells count the records with those values.
+Both <rows> and <columns> can select several columns, for n-way crosstabs.
+
+By default, the table is returned as a wide, pivoted matrix:
+
+    region,gadget,widget
+    east,1,3
+    west,3,1
+
+With --long, a row is returned for each combination of values instead:
+
+    region,product,count
+    east,gadget,1
+    east,widget,3
+    ...
+
+With --margins, row & column totals are added. With --normalize, the counts
+are replaced by percentages of the row, column or overall totals.
+
+With --chi2, Pearson's chi-square test of independence of the rows & columns
+is computed, and its statistic, degrees of freedom, p-value & Cramer's V
+are written to stderr. To use them in a script, write them as CSV to a file
+with --stats instead:
+
+    statistic,value
+    chi_square,2
+    degrees_of_freedom,1
+    p_value,0.1573
+    cramers_v,0.5
+
+Since this computes an exact crosstab, memory proportional to the number of
+distinct combinations of values is required.
+
+For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_crosstab.rs.
+
+Usage:
+    qsv crosstab [options] <rows> <columns> [<input>]
+    qsv crosstab --help
+
+crosstab arguments:
+    <rows>                    The columns whose values are the rows of the table.
+                              See 'qsv select --help' for the format details.
+    <columns>                 The columns whose values are the columns of the table.
+                              See 'qsv select --help' for the format details.
+
+crosstab options:
+    --long                    Return the crosstab in long format, with a row for
+                              each combination of values, instead of a matrix.
+                              Only the combinations that occur are returned.
+    --margins                 Add row & column totals.
+    --normalize <arg>         Replace the counts with percentages of the totals.
+                              Valid values are:
+                                none   - return the counts
+                                row    - percentages of the row totals
+                                column - percentages of the column totals
+                                all    - percentages of the overall total
+                              [default: none]
+    --chi2                    Write the chi-square test of independence of
+                              the rows & columns to stderr.
+    --stats <file>            Write the chi-square test of independence of
+                              the rows & columns as CSV to <file>.
+    --separator <arg>         The separator of the values in the column names
+                              of the matrix when <columns> selects more than
+                              one column.
+                              [default: |]
+    --round <decimal_places>  Round percentages & statistics to <decimal_places>.
+                              [default: 4]
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will NOT be interpreted
+                           as column names. The columns will be named by
+                           their 1-based indices instead.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::collections::BTreeSet;
+
+use ahash::AHashMap;
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::{SelectColumns, Selection},
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_rows:        SelectColumns,
+    arg_columns:     SelectColumns,
+    arg_input:       Option<String>,
+    flag_long:       bool,
+    flag_margins:    bool,
+    flag_normalize:  String,
+    flag_chi2:       bool,
+    flag_stats:      Option<String>,
+    flag_separator:  String,
+    flag_round:      u32,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+const MARGIN_LABEL: &str = "Total";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Normalize {
+    None,
+    Row,
+    Column,
+    All,
+}
+
+/// the values of the <rows> or <columns> columns of a record
+type Key = Vec<Vec<u8>>;
+
+struct Crosstab {
+    row_keys:   Vec<Key>,
+    col_keys:   Vec<Key>,
+    /// counts[i][j] is the count of the i-th row key & the j-th column key
+    counts:     Vec<Vec<u64>>,
+    row_totals: Vec<u64>,
+    col_totals: Vec<u64>,
+    total:      u64,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let normalize = match args.flag_normalize.to_lowercase().as_str() {
+        "none" => Normalize::None,
+        "row" => Normalize::Row,
+        "column" => Normalize::Column,
+        "all" => Normalize::All,
+        _ => {
+            return fail_clierror!(
+                "Invalid --normalize option: {}. Valid values are none, row, column & all.",
+                args.flag_normalize
+            )
+        }
+    };
+
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+    let mut rdr = rconfig.reader()?;
+    let headers = rdr.byte_headers()?.clone();
+
+    let row_sel = args.arg_rows.selection(&headers, !args.flag_no_headers)?;
+    let col_sel = args
+        .arg_columns
+        .selection(&headers, !args.flag_no_headers)?;
+    if row_sel.is_empty() || col_sel.is_empty() {
+        return fail_clierror!("<rows> & <columns> must each select at least one column.");
+    }
+
+    let mut cells: AHashMap<(Key, Key), u64> = AHashMap::new();
+    let mut record = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut record)? {
+        let key = (select_key(&row_sel, &record), select_key(&col_sel, &record));
+        *cells.entry(key).or_insert(0) += 1;
+    }
+    let crosstab = Crosstab::new(cells);
+
+    if args.flag_chi2 {
+        for (_, label, value) in crosstab.chi2_stats(args.flag_round) {
+            winfo!("{label}: {value}");
+        }
+    }
+    if args.flag_stats.is_some() {
+        let mut stats_wtr = Config::new(&args.flag_stats).writer()?;
+        stats_wtr.write_record(["statistic", "value"])?;
+        for (statistic, _, value) in crosstab.chi2_stats(args.flag_round) {
+            stats_wtr.write_record([statistic, value.as_str()])?;
+        }
+        stats_wtr.flush()?;
+    }
+
+    let column_name = |i: usize| -> Vec<u8> {
+        if args.flag_no_headers {
+            (i + 1).to_string().into_bytes()
+        } else {
+            headers[i].to_vec()
+        }
+    };
+    let row_names: Vec<Vec<u8>> = row_sel.iter().map(|&i| column_name(i)).collect();
+    let col_names: Vec<Vec<u8>> = col_sel.iter().map(|&i| column_name(i)).collect();
+
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+    let fmt = CellFormat {
+        normalize,
+        round: args.flag_round,
+    };
+    if args.flag_long {
+        crosstab.write_long(&mut wtr, &row_names, &col_names, fmt, args.flag_margins)?;
+    } else {
+        crosstab.write_wide(
+            &mut wtr,
+            &row_names,
+            &args.flag_separator,
+            fmt,
+            args.flag_margins,
+        )?;
+    }
+    Ok(wtr.flush()?)
+}
+
+#[inline]
+fn select_key(sel: &Selection, record: &csv::ByteRecord) -> Key {
+    sel.select(record).map(<[u8]>::to_vec).collect()
+}
+
+/// a key of the margins, with the margin label in its first column
+fn margin_key(len: usize) -> Key {
+    let mut key = vec![vec![]; len];
+    key[0] = MARGIN_LABEL.as_bytes().to_vec();
+    key
+}
+
+#[derive(Clone, Copy)]
+struct CellFormat {
+    normalize: Normalize,
+    round:     u32,
+}
+
+impl CellFormat {
+    /// formats a count, or its percentage of the row, column or overall total
+    /// with --normalize
+    #[allow(clippy::cast_precision_loss)]
+    fn format(self, count: u64, row_total: u64, col_total: u64, total: u64) -> Vec<u8> {
+        let denominator = match self.normalize {
+            Normalize::None => return count.to_string().into_bytes(),
+            Normalize::Row => row_total,
+            Normalize::Column => col_total,
+            Normalize::All => total,
+        };
+        let pct = if denominator == 0 {
+            0.0
+        } else {
+            count as f64 / denominator as f64 * 100.0
+        };
+        util::round_num(pct, self.round).into_bytes()
+    }
+}
+
+impl Crosstab {
+    fn new(cells: AHashMap<(Key, Key), u64>) -> Self {
+        let row_keys: Vec<Key> = cells
+            .keys()
+            .map(|(r, _)| r.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let col_keys: Vec<Key> = cells
+            .keys()
+            .map(|(_, c)| c.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let row_pos: AHashMap<&Key, usize> =
+            row_keys.iter().enumerate().map(|(i, k)| (k, i)).collect();
+        let col_pos: AHashMap<&Key, usize> =
+            col_keys.iter().enumerate().map(|(i, k)| (k, i)).collect();
+
+        let mut counts = vec![vec![0_u64; col_keys.len()]; row_keys.len()];
+        let mut row_totals = vec![0_u64; row_keys.len()];
+        let mut col_totals = vec![0_u64; col_keys.len()];
+        let mut total = 0;
+        for ((row_key, col_key), count) in &cells {
+            let (i, j) = (row_pos[row_key], col_pos[col_key]);
+            counts[i][j] = *count;
+            row_totals[i] += count;
+            col_totals[j] += count;
+            total += count;
+        }
+
+        Crosstab {
+            row_keys,
+            col_keys,
+            counts,
+            row_totals,
+            col_totals,
+            total,
+        }
+    }
+
+    fn write_wide<W: std::io::Write>(
+        &self,
+        wtr: &mut csv::Writer<W>,
+        row_names: &[Vec<u8>],
+        separator: &str,
+        fmt: CellFormat,
+        margins: bool,
+    ) -> CliResult<()> {
+        let mut header: Vec<Vec<u8>> = row_names.to_vec();
+        header.extend(
+            self.col_keys
+                .iter()
+                .map(|key| key.join(separator.as_bytes())),
+        );
+        if margins {
+            header.push(MARGIN_LABEL.as_bytes().to_vec());
+        }
+        wtr.write_record(&header)?;
+
+        for (i, row_key) in self.row_keys.iter().enumerate() {
+            let mut record = row_key.clone();
+            for (j, count) in self.counts[i].iter().enumerate() {
+                record.push(fmt.format(*count, self.row_totals[i], self.col_totals[j], self.total));
+            }
+            if margins {
+                record.push(fmt.format(
+                    self.row_totals[i],
+                    self.row_totals[i],
+                    self.total,
+                    self.total,
+                ));
+            }
+            wtr.write_record(&record)?;
+        }
+
+        if margins {
+            let mut record = margin_key(row_names.len());
+            for col_total in &self.col_totals {
+                record.push(fmt.format(*col_total, self.total, *col_total, self.total));
+            }
+            record.push(fmt.format(self.total, self.total, self.total, self.total));
+            wtr.write_record(&record)?;
+        }
+        Ok(())
+    }
+
+    fn write_long<W: std::io::Write>(
+        &self,
+        wtr: &mut csv::Writer<W>,
+        row_names: &[Vec<u8>],
+        col_names: &[Vec<u8>],
+        fmt: CellFormat,
+        margins: bool,
+    ) -> CliResult<()> {
+        let value_name: &[u8] = if fmt.normalize == Normalize::None {
+            b"count"
+        } else {
+            b"percentage"
+        };
+        let mut header: Vec<Vec<u8>> = row_names.to_vec();
+        header.extend(col_names.iter().cloned());
+        header.push(value_name.to_vec());
+        wtr.write_record(&header)?;
+
+        let col_margin = margin_key(col_names.len());
+        for (i, row_key) in self.row_keys.iter().enumerate() {
+            for (j, col_key) in self.col_keys.iter().enumerate() {
+                let count = self.counts[i][j];
+                if count == 0 {
+                    continue;
+                }
+                let value = fmt.format(count, self.row_totals[i], self.col_totals[j], self.total);
+                wtr.write_record(row_key.iter().chain(col_key).chain([&value]))?;
+            }
+            if margins {
+                let value = fmt.format(
+                    self.row_totals[i],
+                    self.row_totals[i],
+                    self.total,
+                    self.total,
+                );
+                wtr.write_record(row_key.iter().chain(&col_margin).chain([&value]))?;
+            }
+        }
+
+        if margins {
+            let row_margin = margin_key(row_names.len());
+            for (j, col_key) in self.col_keys.iter().enumerate() {
+                let col_total = self.col_totals[j];
+                let value = fmt.format(col_total, self.total, col_total, self.total);
+                wtr.write_record(row_margin.iter().chain(col_key).chain([&value]))?;
+            }
+            let value = fmt.format(self.total, self.total, self.total, self.total);
+            wtr.write_record(row_margin.iter().chain(&col_margin).chain([&value]))?;
+        }
+        Ok(())
+    }
+
+    /// Pearson's chi-square test of independence, returning the statistic,
+    /// its degrees of freedom, p-value & Cramer's V
+    #[allow(clippy::cast_precision_loss)]
+    fn chi2(&self) -> (f64, usize, f64, f64) {
+        let total = self.total as f64;
+        let mut chi2 = 0.0;
+        for (i, row) in self.counts.iter().enumerate() {
+            for (j, count) in row.iter().enumerate() {
+                let expected = self.row_totals[i] as f64 * self.col_totals[j] as f64 / total;
+                if expected > 0.0 {
+                    chi2 += (*count as f64 - expected).powi(2) / expected;
+                }
+            }
+        }
+
+        let (nrows, ncols) = (self.row_keys.len(), self.col_keys.len());
+        let dof = nrows.saturating_sub(1) * ncols.saturating_sub(1);
+        if dof == 0 {
+            return (chi2, dof, 1.0, 0.0);
+        }
+        let p_value = gamma_q(dof as f64 / 2.0, chi2 / 2.0);
+        let cramers_v = (chi2 / (total * (nrows.min(ncols) - 1) as f64)).sqrt();
+        (chi2, dof, p_value, cramers_v)
+    }
+
+    /// the names, labels & rounded values of the chi-square test statistics
+    fn chi2_stats(&self, round: u32) -> [(&'static str, &'static str, String); 4] {
+        let (chi2, dof, p_value, cramers_v) = self.chi2();
+        [
+            ("chi_square", "chi-square", util::round_num(chi2, round)),
+            ("degrees_of_freedom", "degrees of freedom", dof.to_string()),
+            ("p_value", "p-value", util::round_num(p_value, round)),
+            ("cramers_v", "cramer's v", util::round_num(cramers_v, round)),
+        ]
+    }
+}
+
+/// the natural log of the gamma function, using the Lanczos approximation
+#[allow(clippy::excessive_precision)]
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_93,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_13,
+        -176.615_029_162_140_59,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_571_6e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // the reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let mut sum = COEFFICIENTS[0];
+    for (i, coefficient) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += coefficient / (x + i as f64);
+    }
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// the regularized upper incomplete gamma function Q(a, x), which is the
+/// p-value of a chi-square statistic of 2x with 2a degrees of freedom.
+/// Computed with its series or continued fraction representation,
+/// whichever converges faster.
+fn gamma_q(a: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 1000;
+    const EPSILON: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let ln_prefix = -x + a * x.ln() - ln_gamma(a);
+
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut ap = a;
+        for _ in 0..MAX_ITERATIONS {
+            ap += 1.0;
+            term *= x / ap;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        return (1.0 - sum * ln_prefix.exp()).clamp(0.0, 1.0);
+    }
+
+    // modified Lentz's method
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..MAX_ITERATIONS {
+        let i = i as f64;
+        let an = -i * (i - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    (h * ln_prefix.exp()).clamp(0.0, 1.0)
+}
@@ -1,7 +1,41 @@
 static USAGE: &str = r#"
 Creates the difference between two CSVs.
 
-Note that diff does not support stdin. A file path is required for both arguments.
+One of the CSVs can be read from stdin, by leaving out its path or with `-`.
+CSVs compressed with gzip are decompressed when their path ends with .gz.
+
+By default, the differences are written as whole rows, tagged in a 'diffresult'
+column with '-' for rows of the left CSV that were removed or modified, and '+'
+for rows of the right CSV that were added or modified. --format can instead write:
+  long     one row for each modified field, with the primary key columns
+           followed by diffresult, column, old_value & new_value columns.
+           Added & removed rows have a row without a column.
+  json     an array of the added & removed rows, and of the modified fields of
+           the modified rows, with their primary keys.
+  summary  the number of added, removed & modified rows, and the number of
+           modified fields of each column, with diffresult, column & count columns.
+
+Rows are matched on their primary key, which is the first column by default. It can
+be set with --key by name, or with --primary-key-idx by index. Columns that should
+not be compared, like timestamps of the export, can be left out with --ignore-columns.
+
+e.g. 'qsv diff --key id --ignore-columns updated_at --format long old.csv new.csv'
+
+Re-exported data often only differs in formatting, like '1.0' & '1', or in
+whitespace or case. Such fields are not reported as modified with --compare,
+which sets how the fields of columns are compared, as a ';'-separated list of
+'<columns>:<rules>', where <rules> is a '+'-separated list of:
+  trim     ignore leading & trailing whitespace
+  casei    compare case insensitively
+  date     compare as dates, e.g. '2023-01-31' & 'Jan 31, 2023' are equal
+  numeric  compare as numbers, e.g. '1.0' & '1' are equal
+  abs=<n>  compare as numbers, equal within an absolute tolerance of <n>
+  rel=<n>  compare as numbers, equal within a relative tolerance of <n>,
+           e.g. 0.01 for 1% of the largest absolute value
+Values that are not dates or numbers are compared as text. The rules do not
+apply to the primary key columns.
+
+e.g. 'qsv diff --compare "price,tax:abs=0.005;name:trim+casei;updated:date" a.csv b.csv'
 
 Usage:
     qsv diff [options] [<input-left>] [<input-right>]
@@ -23,6 +57,20 @@ diff options:
     --primary-key-idx <arg...>  The column indices that uniquely identify a record
                                 as a comma separated list of indices, e.g. 0,1,2.
                                 (default: 0)
+    -k, --key <arg>             The columns that uniquely identify a record, by
+                                name or by index starting at 1.
+                                See 'qsv select --help' for the format details.
+                                Cannot be used with --primary-key-idx.
+    --ignore-columns <arg>      The columns to leave out of the diff, by name or by
+                                index starting at 1. They are not in the output, and
+                                --primary-key-idx is then the index of the remaining
+                                columns.
+    --format <arg>              The output format - csv, long, json or summary.
+                                [default: csv]
+    --compare <arg>             How the fields of columns are compared.
+                                See above for the format details.
+    --prefer-dmy                Parse dates in dmy format for the date rule.
+                                Otherwise, use mdy format.
     -j, --jobs <arg>            The number of jobs to run in parallel.
                                 When not set, the number of jobs is set to the number
                                 of CPUs detected.
@@ -32,14 +80,22 @@ Common options:
     -o, --output <file>         Write output to <file> instead of stdout.
 "#;
 
-use std::io::{self, Read, Write};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+};
 
 use csv_diff::{csv_diff::CsvByteDiffBuilder, diff_row::DiffByteRecord};
+use flate2::read::MultiGzDecoder;
+use qsv_dateparser::parse_with_preference;
 use serde::Deserialize;
+use serde_json::{json, Map, Value};
 
 use crate::{
     clitypes::CliError,
     config::{Config, Delimiter},
+    select::SelectColumns,
     util, CliResult,
 };
 
@@ -54,6 +110,19 @@ struct Args {
     flag_delimiter_left:   Option<Delimiter>,
     flag_delimiter_right:  Option<Delimiter>,
     flag_primary_key_idx:  Option<String>,
+    flag_key:              Option<SelectColumns>,
+    flag_ignore_columns:   Option<SelectColumns>,
+    flag_format:           String,
+    flag_compare:          Option<String>,
+    flag_prefer_dmy:       bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DiffFormat {
+    Csv,
+    Long,
+    Json,
+    Summary,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
@@ -69,40 +138,534 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         .checkutf8(false)
         .no_headers(args.flag_no_headers_right);
 
-    if rconfig_left.is_stdin() || rconfig_right.is_stdin() {
-        return fail_clierror!(
-            "diff does not support stdin. A file path is required for both arguments."
-        );
+    if rconfig_left.is_stdin() && rconfig_right.is_stdin() {
+        return fail_clierror!("diff can only read one of the CSVs from stdin.");
+    }
+
+    let format = match args.flag_format.to_lowercase().as_str() {
+        "csv" => DiffFormat::Csv,
+        "long" => DiffFormat::Long,
+        "json" => DiffFormat::Json,
+        "summary" => DiffFormat::Summary,
+        _ => {
+            return fail_clierror!(
+                "Invalid --format: {}. Valid values are csv, long, json & summary.",
+                args.flag_format
+            )
+        }
+    };
+    if args.flag_key.is_some() && args.flag_primary_key_idx.is_some() {
+        return fail_clierror!("--key cannot be used with --primary-key-idx.");
     }
 
-    let primary_key_cols = match args.flag_primary_key_idx {
-        None => vec![0],
-        Some(s) => s
+    let mut csv_rdr_left = diff_reader(rconfig_left, &args.arg_input_left)?;
+    let mut csv_rdr_right = diff_reader(rconfig_right, &args.arg_input_right)?;
+
+    // the headers of the diff are the headers of the left CSV, or of the right
+    // CSV when the left CSV has no headers
+    let headers_left = csv_rdr_left.byte_headers()?.clone();
+    let headers_right = csv_rdr_right.byte_headers()?.clone();
+    let (mut headers, use_names) = match (csv_rdr_left.has_headers(), csv_rdr_right.has_headers()) {
+        (false, true) => (headers_right, true),
+        (has_headers, _) => (headers_left, has_headers),
+    };
+
+    if let Some(ref ignore_columns) = args.flag_ignore_columns {
+        let ignored = ignore_columns.selection(&headers, use_names)?;
+        let keep: Vec<usize> = (0..headers.len())
+            .filter(|i| !ignored.contains(i))
+            .collect();
+        csv_rdr_left = without_columns(csv_rdr_left, &keep)?;
+        csv_rdr_right = without_columns(csv_rdr_right, &keep)?;
+        headers = keep.iter().map(|&i| &headers[i]).collect();
+    }
+
+    let primary_key_cols = match (&args.flag_key, args.flag_primary_key_idx) {
+        (Some(key), _) => key.selection(&headers, use_names)?.to_vec(),
+        (None, None) => vec![0],
+        (None, Some(s)) => s
             .split(',')
             .map(str::parse::<usize>)
             .collect::<Result<Vec<_>, _>>()
             .map_err(|err| CliError::Other(err.to_string()))?,
     };
 
-    let wtr = Config::new(&args.flag_output).writer()?;
-    let mut csv_rdr_left = rconfig_left.reader()?;
-    let mut csv_rdr_right = rconfig_right.reader()?;
+    let compare_rules = match args.flag_compare {
+        Some(ref compare) => Some(CompareRules::new(
+            compare,
+            &headers,
+            use_names,
+            &primary_key_cols,
+            args.flag_prefer_dmy || std::env::var("QSV_PREFER_DMY").is_ok(),
+        )?),
+        None => None,
+    };
 
     // set RAYON_NUM_THREADS
     util::njobs(args.flag_jobs);
 
-    let mut csv_diff_writer = CsvDiffWriter::new(wtr);
-    csv_diff_writer.write_headers(&mut csv_rdr_left, &mut csv_rdr_right)?;
+    let mut csv_diff_writer = if format == DiffFormat::Csv {
+        let mut csv_diff_writer = CsvDiffWriter::new(Config::new(&args.flag_output).writer()?);
+        csv_diff_writer.write_headers(&mut csv_rdr_left, &mut csv_rdr_right)?;
+        Some(csv_diff_writer)
+    } else {
+        None
+    };
 
     let Ok(csv_diff) = CsvByteDiffBuilder::new()
-        .primary_key_columns(primary_key_cols)
+        .primary_key_columns(primary_key_cols.clone())
         .build() else {
             return fail_clierror!("Cannot instantiate diff")
         };
 
-    let diff_byte_records_iter = csv_diff.diff(csv_rdr_left.into(), csv_rdr_right.into());
+    let diff_byte_records_iter = csv_diff
+        .diff(csv_rdr_left.into(), csv_rdr_right.into())
+        .filter_map(
+            |diff_byte_record| match (diff_byte_record, &compare_rules) {
+                (Ok(diff_byte_record), Some(compare_rules)) => {
+                    compare_rules.filter(diff_byte_record).map(Ok)
+                }
+                (diff_byte_record, _) => Some(diff_byte_record),
+            },
+        );
+
+    if let Some(ref mut csv_diff_writer) = csv_diff_writer {
+        return Ok(csv_diff_writer.write_diff_byte_records(diff_byte_records_iter)?);
+    }
+
+    // the names of the columns, or their indices starting at 1 without headers
+    let names: Vec<String> = if use_names {
+        headers
+            .iter()
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect()
+    } else {
+        (1..=headers.len()).map(|i| i.to_string()).collect()
+    };
+    let report = DiffReport {
+        names,
+        key: primary_key_cols,
+    };
+    match format {
+        DiffFormat::Long => report.write_long(diff_byte_records_iter, &args.flag_output),
+        DiffFormat::Json => report.write_json(diff_byte_records_iter, &args.flag_output),
+        _ => report.write_summary(diff_byte_records_iter, &args.flag_output),
+    }
+}
+
+/// Creates the reader of a CSV, decompressing it when its path ends with .gz.
+fn diff_reader(
+    rconfig: Config,
+    input: &Option<String>,
+) -> CliResult<csv::Reader<Box<dyn Read + Send>>> {
+    let Some(path) = input.as_deref().map(Path::new).filter(|path| {
+        path.extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("gz"))
+    }) else {
+        return Ok(rconfig.reader()?);
+    };
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) => return fail_clierror!("failed to open {}: {err}", path.display()),
+    };
+    Ok(rconfig.from_reader(Box::new(MultiGzDecoder::new(io::BufReader::new(file)))))
+}
+
+/// Wraps the reader of a CSV to leave out the columns not in `keep`.
+fn without_columns(
+    mut rdr: csv::Reader<Box<dyn Read + Send>>,
+    keep: &[usize],
+) -> CliResult<csv::Reader<Box<dyn Read + Send>>> {
+    let has_headers = rdr.has_headers();
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    if has_headers {
+        let headers = rdr.byte_headers()?.clone();
+        wtr.write_record(keep.iter().map(|&i| &headers[i]))?;
+        wtr.flush()?;
+    }
+    let columns_reader = ColumnsReader {
+        rdr,
+        keep: keep.to_vec(),
+        wtr,
+        pos: 0,
+        row: csv::ByteRecord::new(),
+    };
+    Ok(csv::ReaderBuilder::new()
+        .has_headers(has_headers)
+        .from_reader(Box::new(columns_reader) as Box<dyn Read + Send>))
+}
+
+/// Re-serializes the rows of a CSV with only the `keep` columns, one row at a time,
+/// so that --ignore-columns does not hold the CSVs in memory.
+struct ColumnsReader {
+    rdr:  csv::Reader<Box<dyn Read + Send>>,
+    keep: Vec<usize>,
+    /// the serialized current row, read from `pos`
+    wtr:  csv::Writer<Vec<u8>>,
+    pos:  usize,
+    row:  csv::ByteRecord,
+}
+
+impl Read for ColumnsReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.wtr.get_ref().len() {
+            self.wtr.get_mut().clear();
+            self.pos = 0;
+            if !self.rdr.read_byte_record(&mut self.row)? {
+                return Ok(0);
+            }
+            self.wtr
+                .write_record(self.keep.iter().map(|&i| &self.row[i]))?;
+            self.wtr.flush()?;
+        }
+        let pending = &self.wtr.get_ref()[self.pos..];
+        let len = pending.len().min(buf.len());
+        buf[..len].copy_from_slice(&pending[..len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// How the fields of a column are compared with --compare.
+#[derive(Clone, Default)]
+struct CompareRule {
+    trim:    bool,
+    casei:   bool,
+    date:    bool,
+    numeric: bool,
+    abs:     f64,
+    rel:     f64,
+}
+
+impl CompareRule {
+    fn parse(rules: &str) -> CliResult<CompareRule> {
+        let mut compare_rule = CompareRule::default();
+        for rule in rules.split('+') {
+            let rule = rule.trim().to_lowercase();
+            match rule.split_once('=') {
+                None if rule == "trim" => compare_rule.trim = true,
+                None if rule == "casei" => compare_rule.casei = true,
+                None if rule == "date" => compare_rule.date = true,
+                None if rule == "numeric" => compare_rule.numeric = true,
+                Some((name @ ("abs" | "rel"), tolerance)) => {
+                    let Ok(tolerance) = tolerance.parse::<f64>() else {
+                        return fail_clierror!("Invalid tolerance in --compare: {rule}");
+                    };
+                    if tolerance < 0.0 {
+                        return fail_clierror!("Tolerances cannot be negative: {rule}");
+                    }
+                    compare_rule.numeric = true;
+                    if name == "abs" {
+                        compare_rule.abs = tolerance;
+                    } else {
+                        compare_rule.rel = tolerance;
+                    }
+                }
+                _ => return fail_clierror!("Invalid rule in --compare: {rule}"),
+            }
+        }
+        Ok(compare_rule)
+    }
+
+    /// whether the fields are equal by this rule
+    fn equal(&self, old: &[u8], new: &[u8], prefer_dmy: bool) -> bool {
+        let (Ok(old), Ok(new)) = (std::str::from_utf8(old), std::str::from_utf8(new)) else {
+            return old == new;
+        };
+        let (old, new) = if self.trim || self.date || self.numeric {
+            (old.trim(), new.trim())
+        } else {
+            (old, new)
+        };
+
+        if self.numeric {
+            if let (Ok(old), Ok(new)) = (old.parse::<f64>(), new.parse::<f64>()) {
+                let tolerance = self.abs.max(self.rel * old.abs().max(new.abs()));
+                return (old - new).abs() <= tolerance;
+            }
+        }
+        if self.date {
+            if let (Ok(old), Ok(new)) = (
+                parse_with_preference(old, prefer_dmy),
+                parse_with_preference(new, prefer_dmy),
+            ) {
+                return old == new;
+            }
+        }
+        if self.casei {
+            old.to_lowercase() == new.to_lowercase()
+        } else {
+            old == new
+        }
+    }
+}
+
+/// The --compare rules of the columns, to filter out the modified fields that
+/// are equal by their column's rule.
+struct CompareRules {
+    rules:      Vec<Option<CompareRule>>,
+    prefer_dmy: bool,
+}
+
+impl CompareRules {
+    fn new(
+        compare: &str,
+        headers: &csv::ByteRecord,
+        use_names: bool,
+        primary_key_cols: &[usize],
+        prefer_dmy: bool,
+    ) -> CliResult<CompareRules> {
+        let mut rules = vec![None; headers.len()];
+        for column_rules in compare.split(';').filter(|s| !s.trim().is_empty()) {
+            let Some((columns, column_rules)) = column_rules.rsplit_once(':') else {
+                return fail_clierror!(
+                    "Invalid --compare: {column_rules}. Use '<columns>:<rules>'."
+                );
+            };
+            let compare_rule = CompareRule::parse(column_rules)?;
+            for i in SelectColumns::parse(columns.trim())?
+                .selection(headers, use_names)?
+                .iter()
+            {
+                if !primary_key_cols.contains(i) {
+                    rules[*i] = Some(compare_rule.clone());
+                }
+            }
+        }
+        Ok(CompareRules { rules, prefer_dmy })
+    }
+
+    /// Filters out the modified fields that are equal by their rule, and the
+    /// modified rows without any other modified field.
+    fn filter(&self, diff_byte_record: DiffByteRecord) -> Option<DiffByteRecord> {
+        match diff_byte_record {
+            DiffByteRecord::Modify {
+                delete,
+                add,
+                field_indices,
+            } => {
+                let (old, new) = (delete.byte_record(), add.byte_record());
+                let field_indices: Vec<usize> = field_indices
+                    .into_iter()
+                    .filter(|&i| match self.rules.get(i) {
+                        Some(Some(rule)) => !rule.equal(
+                            old.get(i).unwrap_or_default(),
+                            new.get(i).unwrap_or_default(),
+                            self.prefer_dmy,
+                        ),
+                        _ => true,
+                    })
+                    .collect();
+                if field_indices.is_empty() {
+                    return None;
+                }
+                Some(DiffByteRecord::Modify {
+                    delete,
+                    add,
+                    field_indices,
+                })
+            }
+            diff_byte_record => Some(diff_byte_record),
+        }
+    }
+}
+
+/// Writes the long, JSON & summary formats of a diff.
+struct DiffReport {
+    names: Vec<String>,
+    /// the indices of the primary key columns
+    key:   Vec<usize>,
+}
+
+impl DiffReport {
+    fn key_values<'a>(&'a self, row: &'a csv::ByteRecord) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.key
+            .iter()
+            .map(move |&i| row.get(i).unwrap_or_default())
+    }
+
+    fn key_json(&self, row: &csv::ByteRecord) -> Value {
+        let key: Map<String, Value> = self
+            .key
+            .iter()
+            .zip(self.key_values(row))
+            .map(|(&i, value)| (self.name(i), json!(String::from_utf8_lossy(value))))
+            .collect();
+        Value::Object(key)
+    }
+
+    fn name(&self, i: usize) -> String {
+        self.names
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| (i + 1).to_string())
+    }
+
+    fn write_long(
+        &self,
+        diff_byte_records: impl IntoIterator<Item = csv::Result<DiffByteRecord>>,
+        output: &Option<String>,
+    ) -> CliResult<()> {
+        let mut wtr = Config::new(output).writer()?;
+        let mut headers: Vec<String> = self.key.iter().map(|&i| self.name(i)).collect();
+        headers.extend(
+            ["diffresult", "column", "old_value", "new_value"]
+                .iter()
+                .map(ToString::to_string),
+        );
+        wtr.write_record(&headers)?;
+
+        for diff_byte_record in diff_byte_records {
+            match diff_byte_record? {
+                DiffByteRecord::Add(add) => {
+                    let row = add.byte_record();
+                    let mut record: Vec<&[u8]> = self.key_values(row).collect();
+                    record.extend([&b"added"[..], b"", b"", b""]);
+                    wtr.write_record(record)?;
+                }
+                DiffByteRecord::Delete(del) => {
+                    let row = del.byte_record();
+                    let mut record: Vec<&[u8]> = self.key_values(row).collect();
+                    record.extend([&b"removed"[..], b"", b"", b""]);
+                    wtr.write_record(record)?;
+                }
+                DiffByteRecord::Modify {
+                    delete,
+                    add,
+                    field_indices,
+                } => {
+                    let (old, new) = (delete.byte_record(), add.byte_record());
+                    for i in field_indices {
+                        let name = self.name(i);
+                        let mut record: Vec<&[u8]> = self.key_values(new).collect();
+                        record.extend([
+                            &b"modified"[..],
+                            name.as_bytes(),
+                            old.get(i).unwrap_or_default(),
+                            new.get(i).unwrap_or_default(),
+                        ]);
+                        wtr.write_record(record)?;
+                    }
+                }
+            }
+        }
+        Ok(wtr.flush()?)
+    }
+
+    fn write_json(
+        &self,
+        diff_byte_records: impl IntoIterator<Item = csv::Result<DiffByteRecord>>,
+        output: &Option<String>,
+    ) -> CliResult<()> {
+        let row_json = |row: &csv::ByteRecord| -> Value {
+            let row: Map<String, Value> = row
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (self.name(i), json!(String::from_utf8_lossy(value))))
+                .collect();
+            Value::Object(row)
+        };
+
+        let json_record = |diff_byte_record: DiffByteRecord| -> Value {
+            match diff_byte_record {
+                DiffByteRecord::Add(add) => json!({
+                    "diffresult": "added",
+                    "key": self.key_json(add.byte_record()),
+                    "row": row_json(add.byte_record()),
+                }),
+                DiffByteRecord::Delete(del) => json!({
+                    "diffresult": "removed",
+                    "key": self.key_json(del.byte_record()),
+                    "row": row_json(del.byte_record()),
+                }),
+                DiffByteRecord::Modify {
+                    delete,
+                    add,
+                    field_indices,
+                } => {
+                    let (old, new) = (delete.byte_record(), add.byte_record());
+                    let changes: Map<String, Value> = field_indices
+                        .iter()
+                        .map(|&i| {
+                            (
+                                self.name(i),
+                                json!({
+                                    "old": String::from_utf8_lossy(old.get(i).unwrap_or_default()),
+                                    "new": String::from_utf8_lossy(new.get(i).unwrap_or_default()),
+                                }),
+                            )
+                        })
+                        .collect();
+                    json!({
+                        "diffresult": "modified",
+                        "key": self.key_json(new),
+                        "changes": changes,
+                    })
+                }
+            }
+        };
+
+        // write the array one record at a time, laid out like serde_json's pretty printer
+        let mut wtr = Config::new(output).io_writer()?;
+        let mut empty = true;
+        for diff_byte_record in diff_byte_records {
+            let pretty = serde_json::to_string_pretty(&json_record(diff_byte_record?))?;
+            wtr.write_all(if empty { b"[\n" } else { b",\n" })?;
+            for (i, line) in pretty.lines().enumerate() {
+                if i > 0 {
+                    wtr.write_all(b"\n")?;
+                }
+                write!(wtr, "  {line}")?;
+            }
+            empty = false;
+        }
+        writeln!(wtr, "{}", if empty { "[]" } else { "\n]" })?;
+        Ok(wtr.flush()?)
+    }
 
-    Ok(csv_diff_writer.write_diff_byte_records(diff_byte_records_iter)?)
+    fn write_summary(
+        &self,
+        diff_byte_records: impl IntoIterator<Item = csv::Result<DiffByteRecord>>,
+        output: &Option<String>,
+    ) -> CliResult<()> {
+        let (mut added, mut removed, mut modified) = (0_u64, 0_u64, 0_u64);
+        let mut column_changes = vec![0_u64; self.names.len()];
+        for diff_byte_record in diff_byte_records {
+            match diff_byte_record? {
+                DiffByteRecord::Add(_) => added += 1,
+                DiffByteRecord::Delete(_) => removed += 1,
+                DiffByteRecord::Modify { field_indices, .. } => {
+                    modified += 1;
+                    for i in field_indices {
+                        if i >= column_changes.len() {
+                            column_changes.resize(i + 1, 0);
+                        }
+                        column_changes[i] += 1;
+                    }
+                }
+            }
+        }
+
+        let mut wtr = Config::new(output).writer()?;
+        wtr.write_record(["diffresult", "column", "count"])?;
+        for (diffresult, count) in [
+            ("added", added),
+            ("removed", removed),
+            ("modified", modified),
+        ] {
+            wtr.write_record([diffresult, "", count.to_string().as_str()])?;
+        }
+        for (i, count) in column_changes.into_iter().enumerate() {
+            if count > 0 {
+                wtr.write_record([
+                    "modified",
+                    self.name(i).as_str(),
+                    count.to_string().as_str(),
+                ])?;
+            }
+        }
+        Ok(wtr.flush()?)
+    }
 }
 
 struct CsvDiffWriter<W: Write> {
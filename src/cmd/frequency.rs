@@ -7,10 +7,25 @@ The frequency table is formatted as CSV data:
 
 By default, there is a row for the N most frequent values for each field in the
 data. The order and number of values can be tweaked with --asc and --limit,
-respectively.
+respectively. Use --other to add a row that sums up the values beyond --limit,
+so the counts of each field add up to the total.
+
+With --weight, the count of a value is the sum of the weight column for the
+rows with that value (e.g. the revenue per region), instead of a row count.
+
+The --rank and --pct options add a rank column, and percentage and
+cumulative percentage columns respectively:
+
+    field,value,count,rank,percentage,cumulative_percentage
 
 Since this computes an exact frequency table, memory proportional to the
-cardinality of each column is required.
+cardinality of each column is required. For columns with too many distinct
+values, --approx computes the most frequent values approximately in bounded
+memory instead, with the Space-Saving algorithm. Only --approx-capacity counters
+are kept for each column, and an error column is added - the true count of a
+value is between count - error and count. Every value whose true count is more
+than the number of values of the column divided by the capacity is guaranteed
+to be in the frequency table.
 
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_frequency.rs.
 
@@ -30,6 +45,32 @@ frequency options:
     -a, --asc              Sort the frequency tables in ascending order by
                            count. The default is descending order.
     --no-nulls             Don't include NULLs in the frequency table.
+    -w, --weight <column>  Sum up the values of this numeric column for each
+                           value, instead of counting rows. Empty and
+                           non-numeric weights are counted as 0.
+                           The weight column is excluded from the
+                           frequency tables.
+    --rank                 Add a rank column. Values with the same count
+                           share the same rank.
+    --pct                  Add percentage and cumulative percentage columns.
+                           Percentages are relative to the total count of
+                           the field, and are rounded to 4 decimal places.
+    --other                Add an "Other" row for each field that sums up the
+                           counts of the values beyond --limit.
+    --other-text <arg>     The value of the "Other" row.
+                           [default: Other]
+    --json                 Output the frequency tables as JSON instead of CSV.
+                           Each field is an object with its total count and
+                           its values, with their count, rank, percentage
+                           and cumulative percentage.
+    --approx               Compute the most frequent values approximately,
+                           in memory bounded by --approx-capacity.
+                           Cannot be used with --asc, --weight or a
+                           --limit of 0.
+    --approx-capacity <n>  The number of counters kept for each column
+                           with --approx. More counters are more accurate.
+                           Set to '0' to use 10 times --limit.
+                           [default: 0]
     -j, --jobs <arg>       The number of jobs to run in parallel.
                            This works better when the given CSV data has
                            an index already created. Note that a file handle
@@ -48,9 +89,15 @@ Common options:
                            Must be a single character. (default: ,)
 "#;
 
-use std::{fs, io};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    io::Write,
+};
 
+use ahash::AHashMap;
 use serde::Deserialize;
+use serde_json::{json, Value};
 use stats::{merge_all, Frequencies};
 use threadpool::ThreadPool;
 
@@ -63,48 +110,117 @@ use crate::{
 
 #[derive(Clone, Deserialize)]
 pub struct Args {
-    pub arg_input:       Option<String>,
-    pub flag_select:     SelectColumns,
-    pub flag_limit:      usize,
-    pub flag_asc:        bool,
-    pub flag_no_nulls:   bool,
-    pub flag_jobs:       Option<usize>,
-    pub flag_output:     Option<String>,
-    pub flag_no_headers: bool,
-    pub flag_delimiter:  Option<Delimiter>,
+    pub arg_input:            Option<String>,
+    pub flag_select:          SelectColumns,
+    pub flag_limit:           usize,
+    pub flag_asc:             bool,
+    pub flag_no_nulls:        bool,
+    pub flag_weight:          Option<String>,
+    pub flag_rank:            bool,
+    pub flag_pct:             bool,
+    pub flag_other:           bool,
+    pub flag_other_text:      String,
+    pub flag_json:            bool,
+    pub flag_approx:          bool,
+    pub flag_approx_capacity: usize,
+    pub flag_jobs:            Option<usize>,
+    pub flag_output:          Option<String>,
+    pub flag_no_headers:      bool,
+    pub flag_delimiter:       Option<Delimiter>,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
     let rconfig = args.rconfig();
 
-    let mut wtr = Config::new(&args.flag_output).writer()?;
-    let (headers, tables) = match args.rconfig().indexed()? {
-        Some(ref mut idx) if util::njobs(args.flag_jobs) > 1 => args.parallel_ftables(idx),
-        _ => args.sequential_ftables(),
-    }?;
+    if args.flag_approx {
+        if args.flag_asc || args.flag_weight.is_some() {
+            return fail_clierror!("--approx cannot be used with --asc or --weight.");
+        }
+        if args.flag_limit == 0 {
+            return fail_clierror!("--approx requires a --limit greater than 0.");
+        }
+    }
 
-    wtr.write_record(vec!["field", "value", "count"])?;
+    let (headers, tables) = if args.flag_approx {
+        let (headers, sstables) = match args.rconfig().indexed()? {
+            Some(ref mut idx) if util::njobs(args.flag_jobs) > 1 => {
+                args.parallel_approx_ftables(idx)
+            }
+            _ => args.sequential_approx_ftables(),
+        }?;
+        let tables: Vec<_> = sstables.into_iter().map(|sstab| sstab.counts()).collect();
+        (headers, tables)
+    } else if args.flag_weight.is_some() {
+        let (headers, wtables) = match args.rconfig().indexed()? {
+            Some(ref mut idx) if util::njobs(args.flag_jobs) > 1 => {
+                args.parallel_weighted_ftables(idx)
+            }
+            _ => args.sequential_weighted_ftables(),
+        }?;
+        let tables: Vec<_> = wtables
+            .into_iter()
+            .map(|wtab| (args.weighted_counts(wtab), 0.0))
+            .collect();
+        (headers, tables)
+    } else {
+        let (headers, ftables) = match args.rconfig().indexed()? {
+            Some(ref mut idx) if util::njobs(args.flag_jobs) > 1 => args.parallel_ftables(idx),
+            _ => args.sequential_ftables(),
+        }?;
+        let tables: Vec<_> = ftables
+            .iter()
+            .map(|ftab| (args.counts(ftab), 0.0))
+            .collect();
+        (headers, tables)
+    };
+
+    let mut fields = Vec::with_capacity(tables.len());
     let head_ftables = headers.into_iter().zip(tables.into_iter());
-    for (i, (header, ftab)) in head_ftables.enumerate() {
+    for (i, (header, (counts, untracked))) in head_ftables.enumerate() {
         let header = if rconfig.no_headers {
             (i + 1).to_string().into_bytes()
         } else {
             header.to_vec()
         };
-        for (value, count) in args.counts(&ftab) {
-            let count = count.to_string();
-            let row = vec![&*header, &*value, count.as_bytes()];
-            wtr.write_record(row)?;
-        }
+        fields.push(args.frequency_field(header, counts, untracked));
+    }
+
+    if args.flag_json {
+        args.write_json(&fields)
+    } else {
+        args.write_csv(&fields)
     }
-    Ok(())
 }
 
 type ByteString = Vec<u8>;
 type Headers = csv::ByteRecord;
 type FTable = Frequencies<Vec<u8>>;
 type FTables = Vec<Frequencies<Vec<u8>>>;
+type WTable = AHashMap<Vec<u8>, f64>;
+type WTables = Vec<AHashMap<Vec<u8>, f64>>;
+type SSTables = Vec<SpaceSaving>;
+/// the values of a field with their count, and the error bound of the count
+type Counts = Vec<(ByteString, f64, f64)>;
+
+/// the frequency table of a field, as it is output
+struct FrequencyField {
+    field:  ByteString,
+    total:  f64,
+    values: Vec<FrequencyRow>,
+}
+
+struct FrequencyRow {
+    value:                 ByteString,
+    count:                 f64,
+    /// the upper bound of the overestimation of the count with --approx.
+    /// The "Other" row has no error bound, as it is an underestimate
+    error:                 Option<f64>,
+    /// the "Other" row has no rank
+    rank:                  Option<usize>,
+    percentage:            f64,
+    cumulative_percentage: f64,
+}
 
 impl Args {
     pub fn rconfig(&self) -> Config {
@@ -115,25 +231,192 @@ impl Args {
             .select(self.flag_select.clone())
     }
 
-    fn counts(&self, ftab: &FTable) -> Vec<(ByteString, u64)> {
-        let mut counts = if self.flag_asc {
+    /// the counts of a frequency table, sorted by --asc.
+    /// --limit is applied by `frequency_field`
+    #[allow(clippy::cast_precision_loss)]
+    fn counts(&self, ftab: &FTable) -> Counts {
+        let counts = if self.flag_asc {
             ftab.least_frequent()
         } else {
             ftab.most_frequent()
         };
-        if self.flag_limit > 0 {
-            counts = counts.into_iter().take(self.flag_limit).collect();
+        counts
+            .into_iter()
+            .map(|(bs, c)| (null_label(bs), c as f64, 0.0))
+            .collect()
+    }
+
+    /// the summed weights of a weighted frequency table, sorted by --asc.
+    /// Ties are sorted by value, so the output is stable.
+    fn weighted_counts(&self, wtab: WTable) -> Counts {
+        let mut counts: Vec<(ByteString, f64)> = wtab.into_iter().collect();
+        if self.flag_asc {
+            counts.sort_unstable_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        } else {
+            counts.sort_unstable_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
         }
         counts
             .into_iter()
-            .map(|(bs, c)| {
-                if b"" == &**bs {
-                    (b"(NULL)"[..].to_vec(), c)
-                } else {
-                    (bs.clone(), c)
+            .map(|(bs, c)| (null_label(&bs), c, 0.0))
+            .collect()
+    }
+
+    /// applies --limit to the sorted counts of a field, adding the
+    /// rank, percentages and the "Other" row. `untracked` is the count of the
+    /// values that are not in `counts`, which were evicted with --approx
+    #[allow(clippy::float_cmp)]
+    fn frequency_field(
+        &self,
+        header: ByteString,
+        counts: Counts,
+        untracked: f64,
+    ) -> FrequencyField {
+        let total: f64 = counts.iter().map(|(_, c, _)| c).sum::<f64>() + untracked;
+        let pct = |count: f64| {
+            if total == 0.0 {
+                0.0
+            } else {
+                count / total * 100.0
+            }
+        };
+
+        let limit = if self.flag_limit > 0 {
+            self.flag_limit
+        } else {
+            counts.len()
+        };
+        let mut values = Vec::with_capacity(limit.min(counts.len()) + 1);
+        let mut cumulative = 0.0;
+        let mut rank = 0;
+        let mut prev_count = None;
+        let mut counts = counts.into_iter();
+        for (pos, (value, count, error)) in counts.by_ref().take(limit).enumerate() {
+            if prev_count != Some(count) {
+                rank = pos + 1;
+                prev_count = Some(count);
+            }
+            cumulative += count;
+            values.push(FrequencyRow {
+                value,
+                count,
+                error: Some(error),
+                rank: Some(rank),
+                percentage: pct(count),
+                cumulative_percentage: pct(cumulative),
+            });
+        }
+
+        let mut others = counts.peekable();
+        if self.flag_other && (others.peek().is_some() || untracked > 0.0) {
+            let count = others.map(|(_, c, _)| c).sum::<f64>() + untracked;
+            cumulative += count;
+            values.push(FrequencyRow {
+                value: self.flag_other_text.as_bytes().to_vec(),
+                count,
+                error: None,
+                rank: None,
+                percentage: pct(count),
+                cumulative_percentage: pct(cumulative),
+            });
+        }
+
+        FrequencyField {
+            field: header,
+            total,
+            values,
+        }
+    }
+
+    fn format_count(&self, count: f64) -> String {
+        if self.flag_weight.is_some() {
+            util::round_num(count, 4)
+        } else {
+            // unweighted counts are always whole numbers
+            (count as u64).to_string()
+        }
+    }
+
+    fn write_csv(&self, fields: &[FrequencyField]) -> CliResult<()> {
+        let mut wtr = Config::new(&self.flag_output).writer()?;
+
+        let mut header = vec!["field", "value", "count"];
+        if self.flag_approx {
+            header.push("error");
+        }
+        if self.flag_rank {
+            header.push("rank");
+        }
+        if self.flag_pct {
+            header.extend(["percentage", "cumulative_percentage"]);
+        }
+        wtr.write_record(header)?;
+
+        for field in fields {
+            for row in &field.values {
+                let mut record = vec![
+                    field.field.clone(),
+                    row.value.clone(),
+                    self.format_count(row.count).into_bytes(),
+                ];
+                if self.flag_approx {
+                    let error = row.error.map_or_else(String::new, |e| self.format_count(e));
+                    record.push(error.into_bytes());
+                }
+                if self.flag_rank {
+                    let rank = row.rank.map_or_else(String::new, |r| r.to_string());
+                    record.push(rank.into_bytes());
                 }
+                if self.flag_pct {
+                    record.push(util::round_num(row.percentage, 4).into_bytes());
+                    record.push(util::round_num(row.cumulative_percentage, 4).into_bytes());
+                }
+                wtr.write_record(record)?;
+            }
+        }
+        Ok(wtr.flush()?)
+    }
+
+    /// a count as a JSON number, which is an integer unless --weight is used
+    fn json_count(&self, count: f64) -> Value {
+        if self.flag_weight.is_some() {
+            json_number(count)
+        } else {
+            json!(count as u64)
+        }
+    }
+
+    fn write_json(&self, fields: &[FrequencyField]) -> CliResult<()> {
+        let json_fields: Vec<Value> = fields
+            .iter()
+            .map(|field| {
+                let values: Vec<Value> = field
+                    .values
+                    .iter()
+                    .map(|row| {
+                        let mut value = json!({
+                            "value": String::from_utf8_lossy(&row.value),
+                            "count": self.json_count(row.count),
+                            "rank": row.rank,
+                            "percentage": json_number(row.percentage),
+                            "cumulative_percentage": json_number(row.cumulative_percentage),
+                        });
+                        if self.flag_approx {
+                            value["error"] = row.error.map_or(Value::Null, |e| self.json_count(e));
+                        }
+                        value
+                    })
+                    .collect();
+                json!({
+                    "field": String::from_utf8_lossy(&field.field),
+                    "total": self.json_count(field.total),
+                    "values": values,
+                })
             })
-            .collect()
+            .collect();
+
+        let mut wtr = Config::new(&self.flag_output).io_writer()?;
+        writeln!(wtr, "{}", serde_json::to_string_pretty(&json_fields)?)?;
+        Ok(wtr.flush()?)
     }
 
     pub fn sequential_ftables(&self) -> CliResult<(Headers, FTables)> {
@@ -192,6 +475,176 @@ impl Args {
         Ok(tabs)
     }
 
+    fn approx_capacity(&self) -> usize {
+        if self.flag_approx_capacity > 0 {
+            self.flag_approx_capacity
+        } else {
+            self.flag_limit.saturating_mul(10)
+        }
+    }
+
+    pub fn sequential_approx_ftables(&self) -> CliResult<(Headers, SSTables)> {
+        let mut rdr = self.rconfig().reader()?;
+        let (headers, sel) = self.sel_headers(&mut rdr)?;
+        Ok((headers, self.approx_ftables(&sel, rdr.byte_records())?))
+    }
+
+    pub fn parallel_approx_ftables(
+        &self,
+        idx: &mut Indexed<fs::File, fs::File>,
+    ) -> CliResult<(Headers, SSTables)> {
+        let mut rdr = self.rconfig().reader()?;
+        let (headers, sel) = self.sel_headers(&mut rdr)?;
+
+        if idx.count() == 0 {
+            return Ok((headers, vec![]));
+        }
+
+        let chunk_size = util::chunk_size(idx.count() as usize, util::njobs(self.flag_jobs));
+        let nchunks = util::num_of_chunks(idx.count() as usize, chunk_size);
+
+        let pool = ThreadPool::new(util::njobs(self.flag_jobs));
+        let (send, recv) = channel::bounded(0);
+        for i in 0..nchunks {
+            let (send, args, sel) = (send.clone(), self.clone(), sel.clone());
+            pool.execute(move || {
+                let mut idx = args.rconfig().indexed().unwrap().unwrap();
+                idx.seek((i * chunk_size) as u64).unwrap();
+                let it = idx.byte_records().take(chunk_size);
+                send.send(args.approx_ftables(&sel, it).unwrap()).unwrap();
+            });
+        }
+        drop(send);
+
+        let mut merged: Option<SSTables> = None;
+        for sstabs in recv.iter() {
+            merged = Some(match merged {
+                None => sstabs,
+                Some(mut merged) => {
+                    for (merged_tab, sstab) in merged.iter_mut().zip(sstabs) {
+                        merged_tab.merge(sstab);
+                    }
+                    merged
+                }
+            });
+        }
+        Ok((headers, merged.unwrap_or_default()))
+    }
+
+    fn approx_ftables<I>(&self, sel: &Selection, it: I) -> CliResult<SSTables>
+    where
+        I: Iterator<Item = csv::Result<csv::ByteRecord>>,
+    {
+        let nsel = sel.normal();
+        let capacity = self.approx_capacity();
+        let mut tabs: SSTables = (0..nsel.len())
+            .map(|_| SpaceSaving::new(capacity))
+            .collect();
+        for row in it {
+            let row = row?;
+            for (i, field) in nsel.select(row.into_iter()).enumerate() {
+                let field = trim(field.to_vec());
+                if !field.is_empty() || !self.flag_no_nulls {
+                    tabs[i].add(field);
+                }
+            }
+        }
+        Ok(tabs)
+    }
+
+    pub fn sequential_weighted_ftables(&self) -> CliResult<(Headers, WTables)> {
+        let mut rdr = self.rconfig().reader()?;
+        let (headers, cols, weight_col) = self.weighted_sel_headers(&mut rdr)?;
+        Ok((
+            headers,
+            self.weighted_ftables(&cols, weight_col, rdr.byte_records())?,
+        ))
+    }
+
+    pub fn parallel_weighted_ftables(
+        &self,
+        idx: &mut Indexed<fs::File, fs::File>,
+    ) -> CliResult<(Headers, WTables)> {
+        let mut rdr = self.rconfig().reader()?;
+        let (headers, cols, weight_col) = self.weighted_sel_headers(&mut rdr)?;
+
+        if idx.count() == 0 {
+            return Ok((headers, vec![]));
+        }
+
+        let chunk_size = util::chunk_size(idx.count() as usize, util::njobs(self.flag_jobs));
+        let nchunks = util::num_of_chunks(idx.count() as usize, chunk_size);
+
+        let pool = ThreadPool::new(util::njobs(self.flag_jobs));
+        let (send, recv) = channel::bounded(0);
+        for i in 0..nchunks {
+            let (send, args, cols) = (send.clone(), self.clone(), cols.clone());
+            pool.execute(move || {
+                let mut idx = args.rconfig().indexed().unwrap().unwrap();
+                idx.seek((i * chunk_size) as u64).unwrap();
+                let it = idx.byte_records().take(chunk_size);
+                send.send(args.weighted_ftables(&cols, weight_col, it).unwrap())
+                    .unwrap();
+            });
+        }
+        drop(send);
+
+        let mut merged: WTables = (0..cols.len()).map(|_| AHashMap::new()).collect();
+        for wtabs in recv.iter() {
+            for (merged_tab, wtab) in merged.iter_mut().zip(wtabs) {
+                for (value, weight) in wtab {
+                    *merged_tab.entry(value).or_insert(0.0) += weight;
+                }
+            }
+        }
+        Ok((headers, merged))
+    }
+
+    fn weighted_ftables<I>(&self, cols: &[usize], weight_col: usize, it: I) -> CliResult<WTables>
+    where
+        I: Iterator<Item = csv::Result<csv::ByteRecord>>,
+    {
+        let mut tabs: WTables = (0..cols.len()).map(|_| AHashMap::new()).collect();
+        for row in it {
+            let row = row?;
+            let weight = std::str::from_utf8(&row[weight_col])
+                .ok()
+                .and_then(|w| w.trim().parse::<f64>().ok())
+                .filter(|w| w.is_finite())
+                .unwrap_or(0.0);
+            for (i, &col) in cols.iter().enumerate() {
+                let field = trim(row[col].to_vec());
+                if field.is_empty() && self.flag_no_nulls {
+                    continue;
+                }
+                *tabs[i].entry(field).or_insert(0.0) += weight;
+            }
+        }
+        Ok(tabs)
+    }
+
+    /// the selected headers and their column indices, less the weight column,
+    /// and the index of the weight column
+    fn weighted_sel_headers<R: io::Read>(
+        &self,
+        rdr: &mut csv::Reader<R>,
+    ) -> CliResult<(csv::ByteRecord, Vec<usize>, usize)> {
+        let headers = rdr.byte_headers()?.clone();
+        let sel = self.rconfig().selection(&headers)?;
+
+        let weight_arg = self.flag_weight.as_deref().unwrap_or_default();
+        let weight_sel =
+            SelectColumns::parse(weight_arg)?.selection(&headers, !self.flag_no_headers)?;
+        if weight_sel.len() != 1 {
+            return fail_clierror!("--weight must select exactly one column: {weight_arg}");
+        }
+        let weight_col = weight_sel[0];
+
+        let cols: Vec<usize> = sel.iter().copied().filter(|&i| i != weight_col).collect();
+        let sel_headers = cols.iter().map(|&i| &headers[i]).collect();
+        Ok((sel_headers, cols, weight_col))
+    }
+
     fn sel_headers<R: io::Read>(
         &self,
         rdr: &mut csv::Reader<R>,
@@ -202,6 +655,146 @@ impl Args {
     }
 }
 
+/// a float rounded to 4 decimal places, as a JSON number
+fn json_number(num: f64) -> Value {
+    let rounded = util::round_num(num, 4).parse::<f64>().unwrap_or(num);
+    json!(rounded)
+}
+
+/// A Space-Saving summary (Metwally, Agrawal & El Abbadi, 2005) of the most
+/// frequent values of a column, which keeps at most `capacity` counters.
+/// The count of a value overestimates its true count by at most its error.
+/// Summaries are merged as described by Agarwal et al. in "Mergeable Summaries".
+#[derive(Clone)]
+pub struct SpaceSaving {
+    capacity: usize,
+    /// the number of values added to the summary
+    total:    u64,
+    counters: AHashMap<ByteString, Counter>,
+    /// the values ordered by count, to find the counter with the minimum count
+    by_count: BTreeMap<(u64, u64), ByteString>,
+    next_id:  u64,
+}
+
+#[derive(Clone, Copy)]
+struct Counter {
+    count: u64,
+    error: u64,
+    /// breaks ties of counts in `by_count`
+    id:    u64,
+}
+
+impl SpaceSaving {
+    fn new(capacity: usize) -> Self {
+        SpaceSaving {
+            capacity,
+            total: 0,
+            counters: AHashMap::with_capacity(capacity),
+            by_count: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    fn add(&mut self, value: ByteString) {
+        self.total += 1;
+        if let Some(counter) = self.counters.get_mut(&value) {
+            let value = self.by_count.remove(&(counter.count, counter.id)).unwrap();
+            counter.count += 1;
+            self.by_count.insert((counter.count, counter.id), value);
+            return;
+        }
+
+        // a new value replaces the value with the minimum count when the summary is full,
+        // inheriting its count as its error
+        let min_count = if self.counters.len() < self.capacity {
+            0
+        } else {
+            match self.by_count.pop_first() {
+                Some(((min_count, _), evicted)) => {
+                    self.counters.remove(&evicted);
+                    min_count
+                }
+                // a capacity of 0 keeps no counters
+                None => return,
+            }
+        };
+        self.insert(value, min_count + 1, min_count);
+    }
+
+    fn insert(&mut self, value: ByteString, count: u64, error: u64) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.by_count.insert((count, id), value.clone());
+        self.counters.insert(value, Counter { count, error, id });
+    }
+
+    /// the count a value not in the summary may have
+    fn min_count(&self) -> u64 {
+        if self.counters.len() < self.capacity {
+            0
+        } else {
+            self.by_count.keys().next().map_or(0, |(count, _)| *count)
+        }
+    }
+
+    fn merge(&mut self, other: SpaceSaving) {
+        let (self_min, other_min) = (self.min_count(), other.min_count());
+
+        let mut merged: Vec<(ByteString, u64, u64)> =
+            Vec::with_capacity(self.counters.len() + other.counters.len());
+        let mut seen = BTreeSet::new();
+        for (value, counter) in &self.counters {
+            let (count, error) = other
+                .counters
+                .get(value)
+                .map_or((other_min, other_min), |c| (c.count, c.error));
+            merged.push((value.clone(), counter.count + count, counter.error + error));
+            seen.insert(value);
+        }
+        for (value, counter) in &other.counters {
+            if !seen.contains(value) {
+                merged.push((
+                    value.clone(),
+                    counter.count + self_min,
+                    counter.error + self_min,
+                ));
+            }
+        }
+        merged.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        merged.truncate(self.capacity);
+
+        let total = self.total + other.total;
+        *self = SpaceSaving::new(self.capacity);
+        self.total = total;
+        for (value, count, error) in merged {
+            self.insert(value, count, error);
+        }
+    }
+
+    /// the counts of the summary in descending order, and the count of the values
+    /// that are not tracked by the summary
+    #[allow(clippy::cast_precision_loss)]
+    fn counts(&self) -> (Counts, f64) {
+        let mut counts: Vec<(&ByteString, &Counter)> = self.counters.iter().collect();
+        counts.sort_unstable_by(|a, b| b.1.count.cmp(&a.1.count).then_with(|| a.0.cmp(b.0)));
+        let tracked: u64 = counts.iter().map(|(_, c)| c.count).sum();
+        let counts = counts
+            .into_iter()
+            .map(|(value, c)| (null_label(value), c.count as f64, c.error as f64))
+            .collect();
+        (counts, self.total.saturating_sub(tracked) as f64)
+    }
+}
+
+#[inline]
+fn null_label(bs: &[u8]) -> ByteString {
+    if bs.is_empty() {
+        b"(NULL)".to_vec()
+    } else {
+        bs.to_vec()
+    }
+}
+
 #[inline]
 fn trim(bs: ByteString) -> ByteString {
     match String::from_utf8(bs) {
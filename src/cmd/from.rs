@@ -0,0 +1,421 @@
+static USAGE: &str = r#"
+Convert Parquet and Arrow IPC files to CSV, so they can be processed by the other
+qsv commands.
+
+PARQUET
+Stream the row groups of a Parquet file to CSV, reading only the selected columns.
+
+With --filter, the row groups are skipped when the min/max statistics of their
+columns show that none of their rows can match the filter. This is a coarse
+filter, as all the rows of the other row groups are converted, whether they match
+or not. It saves reading & decoding the skipped row groups, as with the partitions
+of a lakehouse.
+
+A filter is a list of conditions separated by semicolons, that must all match.
+Each condition compares a column with a value using one of =, <, <=, > or >=.
+Boolean, integer, float, string, binary, date & timestamp (milli, micro & nano
+second) columns can be filtered. Strings are compared byte by byte, and dates &
+timestamps are parsed with the same date parser as the `stats` command.
+
+Examples:
+
+Convert `data.parquet` to CSV.
+
+  $ qsv from parquet data.parquet > data.csv
+
+Convert the id & amount columns of the row groups that may have 2023 rows.
+
+  $ qsv from parquet --select id,amount --filter 'year>=2023' data.parquet
+
+ARROW
+Convert an Arrow IPC file (also known as Feather V2), or an Arrow IPC stream,
+to CSV, reading only the selected columns.
+
+  $ qsv from arrow data.arrow > data.csv
+
+Usage:
+    qsv from parquet [options] <input>
+    qsv from arrow [options] <input>
+    qsv from --help
+
+from options:
+    -s, --select <arg>     Select the columns to convert, by name or by index
+                           starting at 1. See 'qsv select --help' for the format
+                           details.
+    --filter <expr>        Skip the Parquet row groups that cannot match the filter,
+                           based on their min/max statistics.
+    --batch-size <arg>     The number of rows to read at a time.
+                           [default: 8192]
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+"#;
+
+use std::{cmp::Ordering, fs::File, io::Read};
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Schema, TimeUnit},
+    ipc::reader::{FileReader, StreamReader},
+    record_batch::{RecordBatch, RecordBatchReader},
+    util::display::array_value_to_string,
+};
+use parquet::{
+    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ProjectionMask},
+    file::{metadata::RowGroupMetaData, statistics::Statistics},
+};
+use qsv_dateparser::parse_with_preference;
+use serde::Deserialize;
+
+use crate::{config::Config, select::SelectColumns, util, CliResult};
+
+/// the magic bytes an Arrow IPC file starts with
+const ARROW_MAGIC: &[u8; 6] = b"ARROW1";
+
+#[derive(Deserialize)]
+struct Args {
+    cmd_parquet:     bool,
+    cmd_arrow:       bool,
+    arg_input:       String,
+    flag_select:     Option<SelectColumns>,
+    flag_filter:     Option<String>,
+    flag_batch_size: usize,
+    flag_output:     Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// a value of a filter condition, or of the min/max statistics of a column
+#[derive(PartialEq)]
+enum FilterValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Bytes(Vec<u8>),
+}
+
+impl PartialOrd for FilterValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (FilterValue::Bool(a), FilterValue::Bool(b)) => a.partial_cmp(b),
+            (FilterValue::Int(a), FilterValue::Int(b)) => a.partial_cmp(b),
+            (FilterValue::Float(a), FilterValue::Float(b)) => a.partial_cmp(b),
+            (FilterValue::Bytes(a), FilterValue::Bytes(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+/// a filter condition, with the index of its Parquet leaf column
+struct Condition {
+    column:   usize,
+    /// unsigned integers are stored as signed INT32/INT64 Parquet values
+    unsigned: bool,
+    op:       Op,
+    value:    FilterValue,
+}
+
+impl Condition {
+    /// whether some rows of the row group may match the condition
+    fn may_match(&self, row_group: &RowGroupMetaData) -> bool {
+        let Some((min, max)) = row_group
+            .column(self.column)
+            .statistics()
+            .and_then(|statistics| min_max(statistics, self.unsigned))
+        else {
+            // without statistics, all the rows may match
+            return true;
+        };
+        let value = &self.value;
+        if min.partial_cmp(value).is_none() || max.partial_cmp(value).is_none() {
+            // statistics of another physical type, or NaN
+            return true;
+        }
+        match self.op {
+            Op::Eq => min <= *value && *value <= max,
+            Op::Lt => min < *value,
+            Op::Le => min <= *value,
+            Op::Gt => max > *value,
+            Op::Ge => max >= *value,
+        }
+    }
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    if args.flag_batch_size == 0 {
+        return fail_clierror!("--batch-size must be greater than 0.");
+    }
+
+    if args.cmd_parquet {
+        from_parquet(&args)
+    } else if args.cmd_arrow {
+        if args.flag_filter.is_some() {
+            return fail_clierror!("--filter is only supported for Parquet files.");
+        }
+        from_arrow(&args)
+    } else {
+        unreachable!();
+    }
+}
+
+fn from_parquet(args: &Args) -> CliResult<()> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&args.arg_input)?)?;
+    let (roots, order) = projection(builder.schema(), args.flag_select.as_ref())?;
+
+    let conditions = match args.flag_filter {
+        Some(ref filter) => parse_filter(filter, &builder)?,
+        None => Vec::new(),
+    };
+    let row_groups: Vec<usize> = builder
+        .metadata()
+        .row_groups()
+        .iter()
+        .enumerate()
+        .filter(|(_, row_group)| conditions.iter().all(|c| c.may_match(row_group)))
+        .map(|(i, _)| i)
+        .collect();
+    log::info!(
+        "reading {} of {} row groups",
+        row_groups.len(),
+        builder.metadata().num_row_groups()
+    );
+
+    let mask = ProjectionMask::roots(builder.parquet_schema(), roots);
+    let reader = builder
+        .with_projection(mask)
+        .with_row_groups(row_groups)
+        .with_batch_size(args.flag_batch_size)
+        .build()?;
+
+    let mut writer = BatchWriter::new(args, header_names(&reader.schema(), &order))?;
+    for batch in reader {
+        writer.write(&batch?, &order)?;
+    }
+    writer.finish()
+}
+
+fn from_arrow(args: &Args) -> CliResult<()> {
+    // an IPC file starts with the ARROW1 magic, while an IPC stream doesn't
+    let mut magic = [0_u8; 6];
+    let is_file =
+        File::open(&args.arg_input)?.read_exact(&mut magic).is_ok() && &magic == ARROW_MAGIC;
+    if is_file {
+        let reader = FileReader::try_new(File::open(&args.arg_input)?, None)?;
+        let (roots, order) = projection(&reader.schema(), args.flag_select.as_ref())?;
+        let reader = FileReader::try_new(File::open(&args.arg_input)?, Some(roots))?;
+        let mut writer = BatchWriter::new(args, header_names(&reader.schema(), &order))?;
+        for batch in reader {
+            writer.write(&batch?, &order)?;
+        }
+        writer.finish()
+    } else {
+        let reader = StreamReader::try_new(File::open(&args.arg_input)?, None)?;
+        let (roots, order) = projection(&reader.schema(), args.flag_select.as_ref())?;
+        let reader = StreamReader::try_new(File::open(&args.arg_input)?, Some(roots))?;
+        let mut writer = BatchWriter::new(args, header_names(&reader.schema(), &order))?;
+        for batch in reader {
+            writer.write(&batch?, &order)?;
+        }
+        writer.finish()
+    }
+}
+
+/// the sorted & deduplicated indices of the selected columns, to be read,
+/// and the order to write them in, as indices into the read columns
+fn projection(
+    schema: &Schema,
+    select: Option<&SelectColumns>,
+) -> CliResult<(Vec<usize>, Vec<usize>)> {
+    let selection: Vec<usize> = match select {
+        Some(select) => {
+            let headers: csv::ByteRecord = schema
+                .fields()
+                .iter()
+                .map(|f| f.name().as_bytes())
+                .collect();
+            select.selection(&headers, true)?.to_vec()
+        }
+        None => (0..schema.fields().len()).collect(),
+    };
+    let mut roots = selection.clone();
+    roots.sort_unstable();
+    roots.dedup();
+    let order = selection
+        .iter()
+        .map(|i| roots.binary_search(i).unwrap())
+        .collect();
+    Ok((roots, order))
+}
+
+fn header_names(schema: &Schema, order: &[usize]) -> Vec<String> {
+    order
+        .iter()
+        .map(|i| schema.field(*i).name().clone())
+        .collect()
+}
+
+/// writes record batches as CSV rows
+struct BatchWriter {
+    wtr:    csv::Writer<Box<dyn std::io::Write + 'static>>,
+    record: csv::StringRecord,
+}
+
+impl BatchWriter {
+    fn new(args: &Args, headers: Vec<String>) -> CliResult<Self> {
+        let mut wtr = Config::new(&args.flag_output).writer()?;
+        wtr.write_record(&headers)?;
+        Ok(BatchWriter {
+            wtr,
+            record: csv::StringRecord::with_capacity(256, headers.len()),
+        })
+    }
+
+    fn write(&mut self, batch: &RecordBatch, order: &[usize]) -> CliResult<()> {
+        for row in 0..batch.num_rows() {
+            self.record.clear();
+            for column in order.iter().map(|i| batch.column(*i)) {
+                if column.is_null(row) {
+                    self.record.push_field("");
+                } else {
+                    self.record.push_field(&array_value_to_string(column, row)?);
+                }
+            }
+            self.wtr.write_record(&self.record)?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> CliResult<()> {
+        Ok(self.wtr.flush()?)
+    }
+}
+
+/// parse the conditions of a filter, separated by semicolons
+fn parse_filter(
+    filter: &str,
+    builder: &ParquetRecordBatchReaderBuilder<File>,
+) -> CliResult<Vec<Condition>> {
+    let schema = builder.schema();
+    let leaves = builder.parquet_schema().columns();
+
+    let mut conditions = Vec::new();
+    for condition in filter.split(';').map(str::trim).filter(|c| !c.is_empty()) {
+        let Some(op_start) = condition.find(['=', '<', '>']) else {
+            return fail_clierror!("Invalid filter condition: {condition}");
+        };
+        let (name, rest) = condition.split_at(op_start);
+        let (op, value) = if let Some(value) = rest.strip_prefix("<=") {
+            (Op::Le, value)
+        } else if let Some(value) = rest.strip_prefix(">=") {
+            (Op::Ge, value)
+        } else if let Some(value) = rest.strip_prefix('<') {
+            (Op::Lt, value)
+        } else if let Some(value) = rest.strip_prefix('>') {
+            (Op::Gt, value)
+        } else {
+            (Op::Eq, &rest[1..])
+        };
+        let (name, value) = (name.trim(), value.trim());
+
+        let Some(column) = leaves.iter().position(|leaf| leaf.path().string() == name) else {
+            return fail_clierror!("Filter column not found: {name}");
+        };
+        let Ok(field) = schema.field_with_name(name) else {
+            return fail_clierror!("Filter column not found: {name}");
+        };
+        let Some(value) = filter_value(field.data_type(), value) else {
+            return fail_clierror!(
+                "Cannot compare {name} column of type {} with {value}",
+                field.data_type()
+            );
+        };
+        let unsigned = matches!(
+            field.data_type(),
+            DataType::UInt8 | DataType::UInt16 | DataType::UInt32
+        );
+        conditions.push(Condition {
+            column,
+            unsigned,
+            op,
+            value,
+        });
+    }
+    Ok(conditions)
+}
+
+/// parse a filter value as the Parquet physical value of a column
+fn filter_value(data_type: &DataType, value: &str) -> Option<FilterValue> {
+    let timestamp = || parse_with_preference(value, false).ok();
+    match data_type {
+        DataType::Boolean => value.parse().ok().map(FilterValue::Bool),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32 => value.parse().ok().map(FilterValue::Int),
+        DataType::Float32 | DataType::Float64 => value.parse().ok().map(FilterValue::Float),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary => {
+            Some(FilterValue::Bytes(value.as_bytes().to_vec()))
+        }
+        DataType::Date32 => {
+            timestamp().map(|dt| FilterValue::Int(dt.timestamp().div_euclid(86_400)))
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            timestamp().map(|dt| FilterValue::Int(dt.timestamp_millis()))
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            timestamp().map(|dt| FilterValue::Int(dt.timestamp_micros()))
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => timestamp().map(|dt| {
+            FilterValue::Int(
+                dt.timestamp() * 1_000_000_000 + i64::from(dt.timestamp_subsec_nanos()),
+            )
+        }),
+        _ => None,
+    }
+}
+
+/// the min & max values of the statistics of a column chunk. The INT32 statistics
+/// of unsigned integers are reinterpreted as u32, as they're ordered as such.
+fn min_max(statistics: &Statistics, unsigned: bool) -> Option<(FilterValue, FilterValue)> {
+    if !statistics.has_min_max_set() {
+        return None;
+    }
+    Some(match statistics {
+        Statistics::Boolean(s) => (FilterValue::Bool(*s.min()), FilterValue::Bool(*s.max())),
+        Statistics::Int32(s) if unsigned => (
+            FilterValue::Int(i64::from(*s.min() as u32)),
+            FilterValue::Int(i64::from(*s.max() as u32)),
+        ),
+        Statistics::Int32(s) => (
+            FilterValue::Int(i64::from(*s.min())),
+            FilterValue::Int(i64::from(*s.max())),
+        ),
+        Statistics::Int64(s) => (FilterValue::Int(*s.min()), FilterValue::Int(*s.max())),
+        Statistics::Float(s) => (
+            FilterValue::Float(f64::from(*s.min())),
+            FilterValue::Float(f64::from(*s.max())),
+        ),
+        Statistics::Double(s) => (FilterValue::Float(*s.min()), FilterValue::Float(*s.max())),
+        Statistics::ByteArray(s) => (
+            FilterValue::Bytes(s.min().data().to_vec()),
+            FilterValue::Bytes(s.max().data().to_vec()),
+        ),
+        Statistics::FixedLenByteArray(s) => (
+            FilterValue::Bytes(s.min().data().to_vec()),
+            FilterValue::Bytes(s.max().data().to_vec()),
+        ),
+        Statistics::Int96(_) => return None,
+    })
+}
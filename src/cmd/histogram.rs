@@ -0,0 +1,450 @@
+static USAGE: &str = r#"
+Compute histograms of the numeric and date columns of CSV data.
+
+Unlike frequency, which counts each distinct value, histogram groups the values of
+continuous data into bins. The column types and their min/max values are inferred
+with the stats command, and columns that are not Integer, Float, Date or DateTime
+are skipped.
+
+The histogram is formatted as CSV data:
+
+    field,lower,upper,count
+
+Bins include their lower edge and exclude their upper edge, except for the last bin,
+which includes both. For Date & DateTime columns, bin edges are in RFC3339 format.
+
+The following binning methods are supported:
+
+    fixed     --bins bins of the same width between the column's min & max.
+    quantile  --bins bins with (about) the same number of values in each bin.
+    fd        Bins of the same width, with the width computed using the
+              Freedman-Diaconis rule (2 * IQR / cbrt(n)), which is robust to outliers.
+              Falls back to fixed if the IQR is zero.
+
+With --bar, a bar chart of the histogram is rendered instead, e.g.
+
+    price
+      0.00 -  10.00 | ████████████████████ 120
+     10.00 -  20.00 | ████████              48
+
+Note that the values of the selected columns are loaded into memory.
+
+For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_histogram.rs.
+
+Usage:
+    qsv histogram [options] [<input>]
+    qsv histogram --help
+
+histogram options:
+    -s, --select <arg>        Select a subset of columns to compute histograms for.
+                              See 'qsv select --help' for the format details.
+    -m, --method <arg>        The binning method - fixed, quantile or fd.
+                              [default: fixed]
+    -b, --bins <arg>          The number of bins for the fixed & quantile methods.
+                              [default: 10]
+    --max-bins <arg>          The maximum number of bins for the fd method.
+                              [default: 100]
+    --bar                     Render a bar chart of the histogram instead of CSV.
+    --bar-width <arg>         The width of the longest bar in the bar chart.
+                              [default: 40]
+    --round <decimal_places>  Round bin edges to <decimal_places>.
+                              [default: 4]
+    --infer-dates             Infer date/datetime datatypes, so date columns
+                              can have histograms too.
+                              See 'qsv stats --help' for more info.
+    --dates-whitelist <list>  The case-insensitive patterns to look for when
+                              shortlisting fields for date inferencing.
+                              [default: date,time,due,open,close,created]
+    --prefer-dmy              Parse dates in dmy format. Otherwise, use mdy format.
+    -j, --jobs <arg>          The number of jobs to run in parallel when computing
+                              stats. This works only when the given CSV has an index.
+                              When not set, the number of jobs is set to the
+                              number of CPUs detected.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will NOT be interpreted
+                           as column names. i.e., They will be included
+                           in the histograms.
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::io::Write;
+
+use ahash::AHashMap;
+use qsv_dateparser::parse_with_preference;
+use serde::Deserialize;
+
+use crate::{
+    cmd::stats::{timestamp_ms_to_rfc3339, FieldType, DEFAULT_FALSY, DEFAULT_TRUTHY},
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:            Option<String>,
+    flag_select:          SelectColumns,
+    flag_method:          String,
+    flag_bins:            usize,
+    flag_max_bins:        usize,
+    flag_bar:             bool,
+    flag_bar_width:       usize,
+    flag_round:           u32,
+    flag_infer_dates:     bool,
+    flag_dates_whitelist: String,
+    flag_prefer_dmy:      bool,
+    flag_jobs:            Option<usize>,
+    flag_output:          Option<String>,
+    flag_no_headers:      bool,
+    flag_delimiter:       Option<Delimiter>,
+}
+
+#[derive(PartialEq)]
+enum BinMethod {
+    Fixed,
+    Quantile,
+    FreedmanDiaconis,
+}
+
+/// a histogram-able column, with the info from stats needed to bin its values
+struct Column {
+    name:   String,
+    typ:    FieldType,
+    min:    f64,
+    max:    f64,
+    iqr:    f64,
+    values: Vec<f64>,
+}
+
+struct Bin {
+    lower: f64,
+    upper: f64,
+    count: u64,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let method = match args.flag_method.to_lowercase().as_str() {
+        "fixed" => BinMethod::Fixed,
+        "quantile" => BinMethod::Quantile,
+        "fd" => BinMethod::FreedmanDiaconis,
+        _ => {
+            return fail_clierror!("Invalid binning method: {}", args.flag_method);
+        }
+    };
+    if args.flag_bins == 0 {
+        return fail_clierror!("--bins must be greater than zero.");
+    }
+    let prefer_dmy = args.flag_prefer_dmy || std::env::var("QSV_PREFER_DMY").is_ok();
+
+    let mut columns = get_columns(&args, prefer_dmy)?;
+    if columns.is_empty() {
+        return fail_clierror!(
+            "No Integer, Float, Date or DateTime columns to compute histograms for."
+        );
+    }
+    load_values(&args, &mut columns, prefer_dmy)?;
+    if method == BinMethod::Quantile {
+        for col in &mut columns {
+            col.values.sort_unstable_by(f64::total_cmp);
+        }
+    }
+
+    let histograms = columns
+        .iter()
+        .map(|col| {
+            let bins = match method {
+                BinMethod::Fixed => equal_width_bins(col, args.flag_bins),
+                BinMethod::Quantile => quantile_bins(col, args.flag_bins),
+                BinMethod::FreedmanDiaconis => fd_bins(col, args.flag_bins, args.flag_max_bins),
+            };
+            (col, bins)
+        })
+        .collect::<Vec<_>>();
+
+    if args.flag_bar {
+        write_bar_chart(&args, &histograms)
+    } else {
+        let mut wtr = Config::new(&args.flag_output).writer()?;
+        wtr.write_record(["field", "lower", "upper", "count"])?;
+        for (col, bins) in &histograms {
+            for bin in bins {
+                wtr.write_record([
+                    &col.name,
+                    &format_edge(bin.lower, col.typ, args.flag_round),
+                    &format_edge(bin.upper, col.typ, args.flag_round),
+                    &bin.count.to_string(),
+                ])?;
+            }
+        }
+        Ok(wtr.flush()?)
+    }
+}
+
+/// use stats to infer the column types & get the min, max & IQR of the
+/// Integer, Float, Date & DateTime columns
+fn get_columns(args: &Args, prefer_dmy: bool) -> CliResult<Vec<Column>> {
+    let stats_args = crate::cmd::stats::Args {
+        arg_input:            args.arg_input.clone(),
+        flag_select:          args.flag_select.clone(),
+        flag_everything:      false,
+        flag_typesonly:       false,
+        flag_mode:            false,
+        flag_cardinality:     false,
+        flag_median:          false,
+        flag_quartiles:       true,
+        flag_mad:             false,
+        flag_nulls:           false,
+        flag_round:           args.flag_round,
+        flag_infer_dates:     args.flag_infer_dates,
+        flag_dates_whitelist: args.flag_dates_whitelist.to_string(),
+        flag_prefer_dmy:      prefer_dmy,
+        flag_infer_boolean:   false,
+        flag_truthy:          DEFAULT_TRUTHY.to_string(),
+        flag_falsy:           DEFAULT_FALSY.to_string(),
+        flag_infer_subtypes:  false,
+        flag_jobs:            Some(util::njobs(args.flag_jobs)),
+        flag_output:          None,
+        flag_no_headers:      args.flag_no_headers,
+        flag_delimiter:       args.flag_delimiter,
+    };
+
+    let (headers, stats) = match stats_args.rconfig().indexed()? {
+        Some(idx) => stats_args.parallel_stats(&stats_args.flag_dates_whitelist, &idx),
+        None => stats_args.sequential_stats(&stats_args.flag_dates_whitelist),
+    }?;
+    let stats_records = stats_args.stats_to_records(stats);
+
+    let mut stats_col_index_map = AHashMap::new();
+    for (i, col) in stats_args.stat_headers().iter().enumerate() {
+        if col != "field" {
+            // need offset by 1 due to extra "field" column in headers that's not in stats records
+            stats_col_index_map.insert(col.to_owned(), i - 1);
+        }
+    }
+
+    let mut columns = Vec::with_capacity(headers.len());
+    for (i, (header, record)) in headers.iter().zip(stats_records.iter()).enumerate() {
+        let typ = match record.get(stats_col_index_map["type"]).unwrap_or_default() {
+            "Integer" => FieldType::TInteger,
+            "Float" => FieldType::TFloat,
+            "Date" => FieldType::TDate,
+            "DateTime" => FieldType::TDateTime,
+            _ => continue,
+        };
+        let stat = |name: &str| {
+            record
+                .get(stats_col_index_map[name])
+                .and_then(|s| parse_value(s, typ, prefer_dmy))
+        };
+        let (Some(min), Some(max)) = (stat("min"), stat("max")) else {
+            continue;
+        };
+        let iqr = stat("q1").zip(stat("q3")).map_or(0.0, |(q1, q3)| q3 - q1);
+
+        let name = if args.flag_no_headers {
+            (i + 1).to_string()
+        } else {
+            String::from_utf8_lossy(header).to_string()
+        };
+        columns.push(Column {
+            name,
+            typ,
+            min,
+            max,
+            iqr,
+            values: Vec::new(),
+        });
+    }
+    Ok(columns)
+}
+
+/// load the non-null values of the histogram columns
+fn load_values(args: &Args, columns: &mut [Column], prefer_dmy: bool) -> CliResult<()> {
+    let rconfig = Config::new(&args.arg_input)
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers)
+        .select(args.flag_select.clone());
+    let mut rdr = rconfig.reader()?;
+    let headers = rdr.byte_headers()?.clone();
+    let sel = rconfig.selection(&headers)?;
+
+    // map each histogram column to its index in the record
+    let selected_names = sel
+        .select(&headers)
+        .enumerate()
+        .map(|(i, h)| {
+            if args.flag_no_headers {
+                (i + 1).to_string()
+            } else {
+                String::from_utf8_lossy(h).to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    let col_indices = columns
+        .iter()
+        .map(|col| {
+            selected_names
+                .iter()
+                .position(|name| *name == col.name)
+                .map(|pos| sel[pos])
+        })
+        .collect::<Vec<_>>();
+
+    let mut record = csv::StringRecord::new();
+    while rdr.read_record(&mut record)? {
+        for (col, col_idx) in columns.iter_mut().zip(col_indices.iter()) {
+            let Some(field) = col_idx.and_then(|idx| record.get(idx)) else {
+                continue;
+            };
+            if let Some(value) = parse_value(field.trim(), col.typ, prefer_dmy) {
+                col.values.push(value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// parse a value as f64. Dates are parsed to their timestamp in milliseconds.
+#[inline]
+fn parse_value(value: &str, typ: FieldType, prefer_dmy: bool) -> Option<f64> {
+    if value.is_empty() {
+        return None;
+    }
+    match typ {
+        FieldType::TDate | FieldType::TDateTime => {
+            let timestamp = parse_with_preference(value, prefer_dmy)
+                .ok()?
+                .timestamp_millis();
+            #[allow(clippy::cast_precision_loss)]
+            Some(timestamp as f64)
+        }
+        _ => value.parse::<f64>().ok(),
+    }
+}
+
+#[inline]
+fn format_edge(edge: f64, typ: FieldType, round_places: u32) -> String {
+    match typ {
+        FieldType::TDate | FieldType::TDateTime => timestamp_ms_to_rfc3339(edge as i64, typ),
+        _ => util::round_num(edge, round_places),
+    }
+}
+
+/// count the values into `nbins` bins of the same width between the column's min & max
+#[allow(clippy::cast_precision_loss)]
+fn equal_width_bins(col: &Column, nbins: usize) -> Vec<Bin> {
+    let width = (col.max - col.min) / nbins as f64;
+    if width <= 0.0 {
+        // all the values are the same, so there's only one bin
+        return vec![Bin {
+            lower: col.min,
+            upper: col.max,
+            count: col.values.len() as u64,
+        }];
+    }
+
+    let mut bins: Vec<Bin> = (0..nbins)
+        .map(|i| Bin {
+            lower: (i as f64).mul_add(width, col.min),
+            upper: if i == nbins - 1 {
+                col.max
+            } else {
+                ((i + 1) as f64).mul_add(width, col.min)
+            },
+            count: 0,
+        })
+        .collect();
+    for value in &col.values {
+        let idx = (((value - col.min) / width).floor().max(0.0) as usize).min(nbins - 1);
+        bins[idx].count += 1;
+    }
+    bins
+}
+
+/// Freedman-Diaconis rule: bin width = 2 * IQR / cbrt(n)
+fn fd_bins(col: &Column, fallback_bins: usize, max_bins: usize) -> Vec<Bin> {
+    #[allow(clippy::cast_precision_loss)]
+    let width = 2.0 * col.iqr / (col.values.len() as f64).cbrt();
+    if width <= 0.0 || !width.is_finite() {
+        return equal_width_bins(col, fallback_bins);
+    }
+    let nbins = (((col.max - col.min) / width).ceil() as usize).clamp(1, max_bins.max(1));
+    equal_width_bins(col, nbins)
+}
+
+/// bins with (about) the same number of values each. The bin edges are the
+/// quantiles of the column's sorted values, so ties may result in fewer bins.
+#[allow(clippy::cast_precision_loss)]
+fn quantile_bins(col: &Column, nbins: usize) -> Vec<Bin> {
+    if col.values.is_empty() {
+        return vec![];
+    }
+    let n = col.values.len();
+
+    let mut edges: Vec<f64> = (0..=nbins)
+        .map(|i| col.values[((i * (n - 1)) as f64 / nbins as f64).round() as usize])
+        .collect();
+    edges.dedup();
+    if edges.len() == 1 {
+        edges.push(edges[0]);
+    }
+
+    let last = edges.len() - 2;
+    let mut bins: Vec<Bin> = edges
+        .windows(2)
+        .map(|edge| Bin {
+            lower: edge[0],
+            upper: edge[1],
+            count: 0,
+        })
+        .collect();
+    for value in &col.values {
+        // the index of the first bin whose upper edge is greater than the value
+        let idx = bins.partition_point(|bin| bin.upper <= *value).min(last);
+        bins[idx].count += 1;
+    }
+    bins
+}
+
+fn write_bar_chart(args: &Args, histograms: &[(&Column, Vec<Bin>)]) -> CliResult<()> {
+    let mut wtr = Config::new(&args.flag_output).io_writer()?;
+
+    for (i, (col, bins)) in histograms.iter().enumerate() {
+        if i > 0 {
+            writeln!(wtr)?;
+        }
+        writeln!(wtr, "{}", col.name)?;
+
+        let labels = bins
+            .iter()
+            .map(|bin| {
+                (
+                    format_edge(bin.lower, col.typ, args.flag_round),
+                    format_edge(bin.upper, col.typ, args.flag_round),
+                )
+            })
+            .collect::<Vec<_>>();
+        let lower_width = labels.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
+        let upper_width = labels.iter().map(|(_, u)| u.len()).max().unwrap_or(0);
+        let max_count = bins.iter().map(|bin| bin.count).max().unwrap_or(0).max(1);
+
+        for ((lower, upper), bin) in labels.iter().zip(bins) {
+            #[allow(clippy::cast_precision_loss)]
+            let bar_len = ((bin.count as f64 / max_count as f64) * args.flag_bar_width as f64)
+                .round() as usize;
+            writeln!(
+                wtr,
+                "  {lower:>lower_width$} - {upper:>upper_width$} | {} {}",
+                "█".repeat(bar_len),
+                bin.count
+            )?;
+        }
+    }
+    Ok(wtr.flush()?)
+}
@@ -6,7 +6,14 @@ intersection of rows on the keys specified.
 
 Joins are always done by ignoring leading and trailing whitespace. By default,
 joins are done case sensitively, but this can be disabled with the --ignore-case
-flag.
+flag. The join keys can be further normalized with --normalize, e.g. to join
+"Café-Zürich" with "cafe zurich".
+
+With --fuzzy, each row of the first data set is joined to the rows of the second
+data set with the most similar key, if its similarity is at least
+--fuzzy-threshold. The similarity is added as a 'fuzzy_score' column, from 0
+to 1, where 1 is an exact match. Fuzzy joins compare each key of the first data
+set with every distinct key of the second, and only support inner & left joins.
 
 The columns arguments specify the columns to join for each input. Columns can
 be referenced by name or index, starting at 1. Specify multiple columns by
@@ -14,10 +21,69 @@ separating them with a comma. Specify a range of columns with `-`. Both
 columns1 and columns2 must specify exactly the same number of columns.
 (See 'qsv select --help' for the full syntax.)
 
+By default, the second CSV data set is indexed in memory (the first data set for
+right outer joins). When the index would be larger than --memory-limit, both data
+sets are instead partitioned by the hash of their join keys into temporary files,
+which are then joined one partition at a time, so only the index of a partition
+is in memory. The joined rows are the same, but are grouped by partition instead
+of being in the order of the first data set.
+
+When both inputs are sorted on the join columns, use --sorted to do a streaming
+sort-merge join instead, which only keeps the rows of the second data set with
+the current join key in memory. The inputs must be sorted lexicographically on
+the join columns, in the order they are given, e.g. with 'qsv sort --select <columns>'
+('qsv sort --ignore-case' with --ignore-case). The order of the join keys, after
+--ignore-case, --trim & --normalize, is checked while joining, and the join fails
+at the first row that is out of order, rather than silently missing matches.
+With --sorted, the rows are returned in the order of the join keys.
+
+More than two inputs can be joined in one pass with a multi-way join, e.g. to
+join a fact table to several dimension tables of a star schema. <input1> is the
+fact table, and every other input is a dimension table that gets its own in-memory
+hash index. Each row of <input1> is joined to each dimension in turn - on
+<columns1> & <columns2> for <input2>, and on <columns> for every other <input>.
+<columns> is either the join columns of both the fact table & the dimension
+when they have the same names (e.g. 'store_id'), or
+'<fact columns>=<dimension columns>' when they don't (e.g. 'store_id=id').
+Each dimension is inner joined by default, or left joined with --left, which
+can be set for each dimension with --joins. Multi-way joins only support
+inner & left joins.
+
+    e.g. 'qsv join product_id sales.csv id products.csv store_id=id stores.csv --joins left,inner'
+
+As-of & interval joins join rows by the ordering of their keys instead of their
+equality, e.g. to attach the latest exchange rate on or before the date of each
+transaction, or the tariff band containing each amount. Their keys are compared
+as numbers if the first non-empty key is a number, or otherwise parsed as dates,
+and a key that isn't of the same type is an error. Compact dates such as 20230101
+are numbers, so they can't be mixed with other date formats.
+With --asof, <columns1> & <columns2> are each a single column, and each row of
+the first data set is joined to the row of the second data set with the nearest
+key in the given --direction, within the --tolerance if set.
+With --interval, <columns1> is a single column and <columns2> the two columns
+of the lower & upper bounds of intervals, and each row of the first data set is
+joined to every row of the second data set whose interval contains its key.
+Both can also require the --by columns to be equal, e.g. to get the latest rate
+of the transaction's currency. The second data set is loaded in memory, and as-of
+& interval joins only support inner & left joins.
+
+    e.g. 'qsv join --asof --by currency date transactions.csv date rates.csv'
+         'qsv join --interval --left amount orders.csv min,max tariffs.csv'
+
+The output has all the columns of the first data set followed by all the columns
+of the second data set, which can be limited with --right-select. Columns with
+the same name in both data sets can be renamed with --left-prefix, --left-suffix,
+--right-prefix & --right-suffix, so the output has no duplicate column names,
+and --coalesce-keys merges the join columns of both data sets into one column
+each, e.g. for full outer joins. These options are not supported by multi-way
+joins, and left semi & anti joins only output the columns of the first data set.
+
+    e.g. 'qsv join --full --coalesce-keys --right-prefix r_ id a.csv id b.csv'
+
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_join.rs.
 
 Usage:
-    qsv join [options] <columns1> <input1> <columns2> <input2>
+    qsv join [options] <columns1> <input1> <columns2> <input2> [<columns> <input>]...
     qsv join --help
 
 input parameters:
@@ -56,6 +122,66 @@ join options:
                            Otherwise, empty fields are completely ignored.
                            (In fact, any row that has an empty field in the
                            key specified is ignored.)
+    --sorted               Do a streaming sort-merge join, in constant memory.
+                           Both inputs must be sorted on the join columns,
+                           and the join fails at the first row that is out
+                           of order. Cannot be used with --cross.
+    --check-sorted         The same as --sorted, as the order of the inputs
+                           is always checked.
+    --memory-limit <mb>    The maximum estimated size in MB of the in-memory
+                           index of a hash join. When the index would be
+                           larger, the inputs are partitioned on disk and
+                           joined one partition at a time.
+                           Not used by sorted, cross & multi-way joins.
+    --normalize <arg>      Normalize the join keys with a comma-separated list
+                           of normalizers, applied in the given order:
+                             nfkc - Unicode compatibility normalization (NFKC)
+                             diacritics - remove diacritics, e.g. é becomes e
+                             punctuation - remove punctuation & symbols
+                             whitespace - remove all whitespace
+                             eudex - replace the key with its Eudex phonetic
+                                     hash, to join keys that sound alike
+                           e.g. 'nfkc,diacritics,punctuation'
+    --fuzzy                Join each row of the first data set to the rows of
+                           the second data set with the most similar key.
+    --fuzzy-metric <arg>   The similarity metric of fuzzy joins - jarowinkler
+                           (Jaro-Winkler) or damerau (normalized
+                           Damerau-Levenshtein).
+                           [default: jarowinkler]
+    --fuzzy-threshold <arg>  The minimum similarity of a fuzzy match,
+                           from 0 to 1.
+                           [default: 0.9]
+    --asof                 Do an as-of join on the nearest key, instead of an
+                           equal key.
+    --direction <arg>      The direction of the nearest key of an as-of join -
+                           backward (the last key <= the key of the first data
+                           set), forward (the first key >=) or nearest.
+                           [default: backward]
+    --tolerance <arg>      The maximum distance between the keys of an as-of
+                           join, in days for dates (e.g. 0.5 for 12 hours).
+    --interval             Do an interval join, on the rows of the second data
+                           set whose interval contains the key.
+    --by <arg>             The columns that must also be equal for an as-of or
+                           interval join, as either the same columns for both
+                           data sets or '<columns1>=<columns2>'.
+    --prefer-dmy           Parse dates in dmy format. Otherwise, use mdy format.
+    --right-select <arg>   The columns of the second data set to output.
+                           See 'qsv select --help' for the format details.
+    --coalesce-keys        Merge each pair of join columns into one column,
+                           with the value of the first data set, or of the
+                           second data set when it is empty.
+    --left-prefix <arg>    The prefix of the columns of the first data set
+                           with the same name as a column of the second.
+    --left-suffix <arg>    The suffix of the columns of the first data set
+                           with the same name as a column of the second.
+    --right-prefix <arg>   The prefix of the columns of the second data set
+                           with the same name as a column of the first.
+    --right-suffix <arg>   The suffix of the columns of the second data set
+                           with the same name as a column of the first.
+    --joins <arg>          The join operation of each dimension of a
+                           multi-way join, as a comma-separated list of
+                           'inner' or 'left', in the order of the inputs.
+                           e.g. 'left,inner,left' for 4 inputs.
 
 Common options:
     -h, --help             Display this message
@@ -67,11 +193,22 @@ Common options:
                            Must be a single character. (default: ,)
 "#;
 
-use std::{collections::hash_map::Entry, fmt, io, iter::repeat, str};
+use std::{
+    collections::hash_map::{DefaultHasher, Entry},
+    fmt,
+    hash::{Hash, Hasher},
+    io,
+    iter::repeat,
+    path::{Path, PathBuf},
+    str,
+    str::FromStr,
+};
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use byteorder::{BigEndian, WriteBytesExt};
+use qsv_dateparser::parse_with_preference;
 use serde::Deserialize;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
 
 use crate::{
     config::{Config, Delimiter, SeekRead},
@@ -82,26 +219,87 @@ use crate::{
 
 #[derive(Deserialize)]
 struct Args {
-    arg_columns1:     SelectColumns,
-    arg_input1:       String,
-    arg_columns2:     SelectColumns,
-    arg_input2:       String,
-    flag_left:        bool,
-    flag_left_anti:   bool,
-    flag_left_semi:   bool,
-    flag_right:       bool,
-    flag_full:        bool,
-    flag_cross:       bool,
-    flag_output:      Option<String>,
-    flag_no_headers:  bool,
-    flag_ignore_case: bool,
-    flag_nulls:       bool,
-    flag_delimiter:   Option<Delimiter>,
+    arg_columns1:         SelectColumns,
+    arg_input1:           String,
+    arg_columns2:         SelectColumns,
+    arg_input2:           String,
+    arg_columns:          Vec<String>,
+    arg_input:            Vec<String>,
+    flag_left:            bool,
+    flag_left_anti:       bool,
+    flag_left_semi:       bool,
+    flag_right:           bool,
+    flag_full:            bool,
+    flag_cross:           bool,
+    flag_output:          Option<String>,
+    flag_no_headers:      bool,
+    flag_ignore_case:     bool,
+    flag_nulls:           bool,
+    flag_sorted:          bool,
+    flag_check_sorted:    bool,
+    flag_memory_limit:    Option<usize>,
+    flag_joins:           Option<String>,
+    flag_normalize:       Option<String>,
+    flag_fuzzy:           bool,
+    flag_fuzzy_metric:    String,
+    flag_fuzzy_threshold: f64,
+    flag_asof:            bool,
+    flag_direction:       String,
+    flag_tolerance:       Option<f64>,
+    flag_interval:        bool,
+    flag_by:              Option<String>,
+    flag_prefer_dmy:      bool,
+    flag_right_select:    Option<SelectColumns>,
+    flag_coalesce_keys:   bool,
+    flag_left_prefix:     Option<String>,
+    flag_left_suffix:     Option<String>,
+    flag_right_prefix:    Option<String>,
+    flag_right_suffix:    Option<String>,
+    flag_delimiter:       Option<Delimiter>,
+}
+
+/// the join operations of sort-merge & hash joins
+#[derive(Clone, Copy, PartialEq)]
+enum JoinType {
+    Inner,
+    Left,
+    LeftAnti,
+    LeftSemi,
+    Right,
+    Full,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
+    if !args.arg_input.is_empty() || args.flag_joins.is_some() {
+        return args.multi_join();
+    }
+    let sorted = args.flag_sorted || args.flag_check_sorted;
     let mut state = args.new_io_state()?;
+    if let Some(fuzzy) = args.fuzzy_match()? {
+        if args.flag_left_anti
+            || args.flag_left_semi
+            || args.flag_right
+            || args.flag_full
+            || args.flag_cross
+            || sorted
+        {
+            return fail_clierror!("Fuzzy joins only support inner & left joins.");
+        }
+        return state.fuzzy_join(args.flag_left, &fuzzy);
+    }
+    if let Some(range) = args.range_join()? {
+        if args.flag_left_anti
+            || args.flag_left_semi
+            || args.flag_right
+            || args.flag_full
+            || args.flag_cross
+            || sorted
+        {
+            return fail_clierror!("As-of & interval joins only support inner & left joins.");
+        }
+        return state.range_join(&range);
+    }
     match (
         args.flag_left,
         args.flag_left_anti,
@@ -112,45 +310,75 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     ) {
         (true, false, false, false, false, false) => {
             state.write_headers()?;
-            state.outer_join(false)
+            if sorted {
+                state.merge_join(JoinType::Left)
+            } else {
+                state.hash_join(JoinType::Left)
+            }
         }
         (false, true, false, false, false, false) => {
             state.write_headers1()?;
-            state.left_join(true)
+            if sorted {
+                state.merge_join(JoinType::LeftAnti)
+            } else {
+                state.hash_join(JoinType::LeftAnti)
+            }
         }
         (false, false, true, false, false, false) => {
             state.write_headers1()?;
-            state.left_join(false)
+            if sorted {
+                state.merge_join(JoinType::LeftSemi)
+            } else {
+                state.hash_join(JoinType::LeftSemi)
+            }
         }
         (false, false, false, true, false, false) => {
             state.write_headers()?;
-            state.outer_join(true)
+            if sorted {
+                state.merge_join(JoinType::Right)
+            } else {
+                state.hash_join(JoinType::Right)
+            }
         }
         (false, false, false, false, true, false) => {
             state.write_headers()?;
-            state.full_outer_join()
+            if sorted {
+                state.merge_join(JoinType::Full)
+            } else {
+                state.hash_join(JoinType::Full)
+            }
         }
         (false, false, false, false, false, true) => {
+            if sorted {
+                return fail!("--sorted cannot be used with --cross.");
+            }
             state.write_headers()?;
             state.cross_join()
         }
         (false, false, false, false, false, false) => {
             state.write_headers()?;
-            state.inner_join()
+            if sorted {
+                state.merge_join(JoinType::Inner)
+            } else {
+                state.hash_join(JoinType::Inner)
+            }
         }
         _ => fail!("Please pick exactly one join operation."),
     }
 }
 
 struct IoState<R, W: io::Write> {
-    wtr:        csv::Writer<W>,
-    rdr1:       csv::Reader<R>,
-    sel1:       Selection,
-    rdr2:       csv::Reader<R>,
-    sel2:       Selection,
-    no_headers: bool,
-    casei:      bool,
-    nulls:      bool,
+    wtr:          JoinWriter<W>,
+    rdr1:         csv::Reader<R>,
+    sel1:         Selection,
+    rdr2:         csv::Reader<R>,
+    sel2:         Selection,
+    no_headers:   bool,
+    transform:    KeyTransform,
+    nulls:        bool,
+    /// the maximum estimated size in bytes of the index of a hash join
+    memory_limit: Option<usize>,
+    output:       Option<String>,
 }
 
 impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
@@ -158,12 +386,14 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
         if !self.no_headers {
             let mut headers = self.rdr1.byte_headers()?.clone();
             headers.extend(self.rdr2.byte_headers()?.iter());
-            self.wtr.write_record(&headers)?;
+            self.wtr.write_headers(&headers)?;
         }
         Ok(())
     }
 
     fn write_headers1(&mut self) -> CliResult<()> {
+        // only the columns of the first input are written
+        self.wtr.columns = None;
         if !self.no_headers {
             let headers = self.rdr1.byte_headers()?;
             self.wtr.write_record(headers)?;
@@ -173,10 +403,10 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
 
     fn inner_join(mut self) -> CliResult<()> {
         let mut scratch = csv::ByteRecord::new();
-        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.casei, self.nulls)?;
+        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, &self.transform, self.nulls)?;
         for row in self.rdr1.byte_records() {
             let row = row?;
-            let key = get_row_key(&self.sel1, &row, self.casei);
+            let key = get_row_key(&self.sel1, &row, &self.transform);
             if let Some(rows) = validx.values.get(&key) {
                 for &rowi in rows.iter() {
                     validx.idx.seek(rowi as u64)?;
@@ -198,10 +428,10 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
 
         let mut scratch = csv::ByteRecord::new();
         let (_, pad2) = self.get_padding()?;
-        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.casei, self.nulls)?;
+        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, &self.transform, self.nulls)?;
         for row in self.rdr1.byte_records() {
             let row = row?;
-            let key = get_row_key(&self.sel1, &row, self.casei);
+            let key = get_row_key(&self.sel1, &row, &self.transform);
             if let Some(rows) = validx.values.get(&key) {
                 for &rowi in rows.iter() {
                     validx.idx.seek(rowi as u64)?;
@@ -223,24 +453,12 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
     }
 
     fn left_join(mut self, anti: bool) -> CliResult<()> {
-        let validx = ValueIndex::new(self.rdr2, &self.sel2, self.casei, self.nulls)?;
-        let mut first_row: bool = true;
+        let validx = ValueIndex::new(self.rdr2, &self.sel2, &self.transform, self.nulls)?;
         for row in self.rdr1.byte_records() {
             let row = row?;
-            let key = get_row_key(&self.sel1, &row, self.casei);
-            if validx.values.get(&key).is_none() {
-                if anti {
-                    self.wtr.write_record(&row)?;
-                }
-            } else if !anti {
-                // semi_join
-                if first_row {
-                    first_row = false;
-                } else {
-                    // since the first row in a left-semi is
-                    // the header, even if no-header is on
-                    self.wtr.write_record(&row)?;
-                }
+            let key = get_row_key(&self.sel1, &row, &self.transform);
+            if validx.values.contains_key(&key) != anti {
+                self.wtr.write_record(&row)?;
             }
         }
         Ok(())
@@ -249,13 +467,13 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
     fn full_outer_join(mut self) -> CliResult<()> {
         let mut scratch = csv::ByteRecord::new();
         let (pad1, pad2) = self.get_padding()?;
-        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, self.casei, self.nulls)?;
+        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, &self.transform, self.nulls)?;
 
         // Keep track of which rows we've written from rdr2.
         let mut rdr2_written: Vec<_> = repeat(false).take(validx.num_rows).collect();
         for row1 in self.rdr1.byte_records() {
             let row1 = row1?;
-            let key = get_row_key(&self.sel1, &row1, self.casei);
+            let key = get_row_key(&self.sel1, &row1, &self.transform);
             if let Some(rows) = validx.values.get(&key) {
                 for &rowi in rows.iter() {
                     rdr2_written[rowi] = true;
@@ -300,6 +518,354 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
         Ok(())
     }
 
+    /// Does a hash join, spilling both inputs to partitions on disk when the index
+    /// would be larger than the memory limit.
+    fn hash_join(mut self, join: JoinType) -> CliResult<()> {
+        if let Some(limit) = self.memory_limit {
+            let partitions = self.index_partitions(join == JoinType::Right, limit)?;
+            if partitions > 1 {
+                return self.spilled_join(join, partitions);
+            }
+        }
+        match join {
+            JoinType::Inner => self.inner_join(),
+            JoinType::Left => self.outer_join(false),
+            JoinType::LeftAnti => self.left_join(true),
+            JoinType::LeftSemi => self.left_join(false),
+            JoinType::Right => self.outer_join(true),
+            JoinType::Full => self.full_outer_join(),
+        }
+    }
+
+    /// the number of partitions needed for the index of a hash join to fit in
+    /// `limit` bytes, estimated from the size of the join keys of the input that
+    /// is indexed, which is rewound afterwards
+    fn index_partitions(&mut self, right: bool, limit: usize) -> CliResult<usize> {
+        let (rdr, sel) = if right {
+            (&mut self.rdr1, &self.sel1)
+        } else {
+            (&mut self.rdr2, &self.sel2)
+        };
+        let mut size = 0_usize;
+        let mut row = csv::ByteRecord::new();
+        while rdr.read_byte_record(&mut row)? {
+            size += sel.select(&row).map(<[u8]>::len).sum::<usize>() + INDEX_ROW_OVERHEAD;
+        }
+
+        let mut pos = csv::Position::new();
+        pos.set_byte(0);
+        rdr.seek(pos)?;
+        if rdr.has_headers() {
+            // skip the header row, since CSV readers disable the header
+            // skipping logic after being seeked.
+            rdr.read_byte_record(&mut row)?;
+        }
+        Ok((size + limit - 1) / limit)
+    }
+
+    /// Writes the rows of both inputs to partition files by the hash of their
+    /// join keys, and joins the partitions one at a time.
+    fn spilled_join(mut self, join: JoinType, partitions: usize) -> CliResult<()> {
+        let dir = tempfile::tempdir()?;
+        let paths1 = write_partitions(
+            &mut self.rdr1,
+            &self.sel1,
+            &self.transform,
+            dir.path(),
+            "input1",
+            partitions,
+        )?;
+        let paths2 = write_partitions(
+            &mut self.rdr2,
+            &self.sel2,
+            &self.transform,
+            dir.path(),
+            "input2",
+            partitions,
+        )?;
+
+        let columns = self.wtr.columns.take();
+        let joined_len = self.wtr.joined_len;
+        let wconf = Config::new(&self.output);
+        let mut out = self.wtr.wtr.into_inner().map_err(|err| err.into_error())?;
+        for (path1, path2) in paths1.iter().zip(&paths2) {
+            let part = IoState {
+                wtr:          JoinWriter {
+                    wtr: wconf.from_writer(&mut out),
+                    columns: columns.clone(),
+                    joined_len,
+                    record: csv::ByteRecord::new(),
+                },
+                rdr1:         csv::Reader::from_path(path1)?,
+                sel1:         self.sel1.clone(),
+                rdr2:         csv::Reader::from_path(path2)?,
+                sel2:         self.sel2.clone(),
+                no_headers:   true,
+                transform:    self.transform.clone(),
+                nulls:        self.nulls,
+                memory_limit: None,
+                output:       None,
+            };
+            match join {
+                JoinType::Inner => part.inner_join()?,
+                JoinType::Left => part.outer_join(false)?,
+                JoinType::LeftAnti => part.left_join(true)?,
+                JoinType::LeftSemi => part.left_join(false)?,
+                JoinType::Right => part.outer_join(true)?,
+                JoinType::Full => part.full_outer_join()?,
+            }
+        }
+        Ok(out.flush()?)
+    }
+
+    /// Joins each row of the first input to the rows of the second input with the
+    /// most similar key, adding the similarity as a column.
+    fn fuzzy_join(mut self, left: bool, fuzzy: &FuzzyMatch) -> CliResult<()> {
+        if !self.no_headers {
+            let mut headers = self.rdr1.byte_headers()?.clone();
+            headers.extend(self.rdr2.byte_headers()?.iter());
+            headers.push_field(b"fuzzy_score");
+            self.wtr.write_headers(&headers)?;
+        }
+
+        let mut scratch = csv::ByteRecord::new();
+        let (_, pad2) = self.get_padding()?;
+        let mut validx = ValueIndex::new(self.rdr2, &self.sel2, &self.transform, self.nulls)?;
+
+        // the distinct keys of the second input in the order of their first row,
+        // so ties go to the first key
+        let mut candidates: Vec<(String, Vec<usize>)> = validx
+            .values
+            .iter()
+            .map(|(key, rows)| (fuzzy_key(key), rows.clone()))
+            .collect();
+        candidates.sort_unstable_by_key(|(_, rows)| rows[0]);
+
+        for row in self.rdr1.byte_records() {
+            let row = row?;
+            let key = get_row_key(&self.sel1, &row, &self.transform);
+
+            let best = if !self.nulls && key.iter().any(std::vec::Vec::is_empty) {
+                None
+            } else if let Some(rows) = validx.values.get(&key) {
+                Some((1.0, rows.clone()))
+            } else {
+                let key = fuzzy_key(&key);
+                let mut best: Option<(f64, &Vec<usize>)> = None;
+                for (candidate, rows) in &candidates {
+                    let score = fuzzy.score(&key, candidate);
+                    if score >= fuzzy.threshold && best.map_or(true, |(best, _)| score > best) {
+                        best = Some((score, rows));
+                    }
+                }
+                best.map(|(score, rows)| (score, rows.clone()))
+            };
+
+            match best {
+                Some((score, rows)) => {
+                    let score = util::round_num(score, 4);
+                    for rowi in rows {
+                        validx.idx.seek(rowi as u64)?;
+                        validx.idx.read_byte_record(&mut scratch)?;
+                        self.wtr.write_record(
+                            row.iter().chain(scratch.iter()).chain([score.as_bytes()]),
+                        )?;
+                    }
+                }
+                None if left => {
+                    self.wtr
+                        .write_record(row.iter().chain(&pad2).chain([&b""[..]]))?;
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Joins each row of the first input to the rows of the second input by the
+    /// ordering of their keys, with an as-of or an interval join.
+    fn range_join(mut self, range: &RangeJoin) -> CliResult<()> {
+        let interval = matches!(range.kind, RangeKind::Interval);
+        if self.sel1.len() != 1 {
+            return fail_clierror!(
+                "<columns1> must be a single column for as-of & interval joins, but has {} \
+                 columns.",
+                self.sel1.len()
+            );
+        }
+        if interval && self.sel2.len() != 2 {
+            return fail_clierror!(
+                "<columns2> must be the two columns of the lower & upper bounds for interval \
+                 joins, but has {} columns.",
+                self.sel2.len()
+            );
+        }
+        let (by1, by2) = match range.by {
+            Some((ref columns1, ref columns2)) => {
+                let by1 = columns1.selection(self.rdr1.byte_headers()?, !self.no_headers)?;
+                let by2 = columns2.selection(self.rdr2.byte_headers()?, !self.no_headers)?;
+                if by1.len() != by2.len() {
+                    return fail_clierror!(
+                        "--by must have the same number of columns for both inputs, but has {} \
+                         and {} columns.",
+                        by1.len(),
+                        by2.len()
+                    );
+                }
+                (Some(by1), Some(by2))
+            }
+            None => (None, None),
+        };
+        self.write_headers()?;
+        let (_, pad2) = self.get_padding()?;
+
+        // the rows of the second input, and their keys sorted by partition
+        let mut rows2 = Vec::new();
+        let mut partitions: AHashMap<Vec<ByteString>, Vec<RangeRow>> = AHashMap::new();
+        let mut values = RangeValues::new(range.prefer_dmy);
+        for row in self.rdr2.byte_records() {
+            let row = row?;
+            let key = range_key(by2.as_ref(), &row, &self.transform, self.nulls);
+            let lo = values.parse(&row[self.sel2[0]])?;
+            let hi = if interval {
+                values.parse(&row[self.sel2[1]])?
+            } else {
+                lo
+            };
+            if let (Some(key), Some(lo), Some(hi)) = (key, lo, hi) {
+                partitions.entry(key).or_default().push(RangeRow {
+                    lo,
+                    hi,
+                    rowi: rows2.len(),
+                });
+            }
+            rows2.push(row);
+        }
+        for entries in partitions.values_mut() {
+            entries.sort_unstable_by(|a, b| a.lo.total_cmp(&b.lo).then(a.rowi.cmp(&b.rowi)));
+        }
+
+        let mut matches = Vec::new();
+        for row in self.rdr1.byte_records() {
+            let row = row?;
+            matches.clear();
+            let entries = range_key(by1.as_ref(), &row, &self.transform, self.nulls)
+                .and_then(|key| partitions.get(&key));
+            let value = values.parse(&row[self.sel1[0]])?;
+            if let (Some(entries), Some(value)) = (entries, value) {
+                match range.kind {
+                    RangeKind::AsOf {
+                        direction,
+                        tolerance,
+                    } => {
+                        if let Some(entry) = asof_match(entries, value, direction, tolerance) {
+                            matches.push(entry.rowi);
+                        }
+                    }
+                    RangeKind::Interval => {
+                        let end = entries.partition_point(|entry| entry.lo <= value);
+                        matches.extend(
+                            entries[..end]
+                                .iter()
+                                .filter(|entry| entry.hi >= value)
+                                .map(|entry| entry.rowi),
+                        );
+                        matches.sort_unstable();
+                    }
+                }
+            }
+
+            if matches.is_empty() {
+                if range.left {
+                    self.wtr.write_record(row.iter().chain(&pad2))?;
+                }
+            } else {
+                for &rowi in &matches {
+                    self.wtr.write_record(row.iter().chain(&rows2[rowi]))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Joins two inputs that are sorted on their join keys, by merging them.
+    /// Only the rows of the second input with the current join key are kept
+    /// in memory.
+    fn merge_join(mut self, join: JoinType) -> CliResult<()> {
+        let (pad1, pad2) = self.get_padding()?;
+        let emit_left = matches!(join, JoinType::Left | JoinType::LeftAnti | JoinType::Full);
+        let emit_right = matches!(join, JoinType::Right | JoinType::Full);
+
+        let nulls = self.nulls;
+        let mut rdr1 = SortedReader::new(
+            &mut self.rdr1,
+            &self.sel1,
+            &self.transform,
+            nulls,
+            "<input1>",
+        );
+        let mut rdr2 = SortedReader::new(
+            &mut self.rdr2,
+            &self.sel2,
+            &self.transform,
+            nulls,
+            "<input2>",
+        );
+
+        let mut group2 = rdr2.next_group()?;
+        while let Some((key1, row1)) = rdr1.next_record()? {
+            if rdr1.is_joinable(&key1) {
+                // skip the groups of the second input that sort before the key,
+                // as they have no match in the first input
+                while let Some(group) = &group2 {
+                    if group.joinable && group.key >= key1 {
+                        break;
+                    }
+                    if emit_right && !group.matched {
+                        for row2 in &group.rows {
+                            self.wtr.write_record(pad1.iter().chain(row2))?;
+                        }
+                    }
+                    group2 = rdr2.next_group()?;
+                }
+
+                if let Some(group) = group2.as_mut().filter(|group| group.key == key1) {
+                    group.matched = true;
+                    match join {
+                        JoinType::LeftAnti => {}
+                        JoinType::LeftSemi => self.wtr.write_record(&row1)?,
+                        _ => {
+                            for row2 in &group.rows {
+                                self.wtr.write_record(row1.iter().chain(row2))?;
+                            }
+                        }
+                    }
+                    continue;
+                }
+            }
+
+            if emit_left {
+                if join == JoinType::LeftAnti {
+                    self.wtr.write_record(&row1)?;
+                } else {
+                    self.wtr.write_record(row1.iter().chain(&pad2))?;
+                }
+            }
+        }
+
+        if emit_right {
+            while let Some(group) = group2 {
+                if !group.matched {
+                    for row2 in &group.rows {
+                        self.wtr.write_record(pad1.iter().chain(row2))?;
+                    }
+                }
+                group2 = rdr2.next_group()?;
+            }
+        }
+        Ok(())
+    }
+
     fn get_padding(&mut self) -> CliResult<(csv::ByteRecord, csv::ByteRecord)> {
         let len1 = self.rdr1.byte_headers()?.len();
         let len2 = self.rdr2.byte_headers()?.len();
@@ -311,9 +877,233 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
 }
 
 impl Args {
+    fn key_transform(&self) -> CliResult<KeyTransform> {
+        let normalizers = match self.flag_normalize {
+            Some(ref normalizers) => normalizers
+                .split(',')
+                .map(KeyNormalizer::from_str)
+                .collect::<Result<_, String>>()?,
+            None => vec![],
+        };
+        Ok(KeyTransform {
+            casei: self.flag_ignore_case,
+            normalizers,
+        })
+    }
+
+    fn fuzzy_match(&self) -> CliResult<Option<FuzzyMatch>> {
+        if !self.flag_fuzzy {
+            return Ok(None);
+        }
+        let metric = match self.flag_fuzzy_metric.to_lowercase().as_str() {
+            "jarowinkler" => FuzzyMetric::JaroWinkler,
+            "damerau" => FuzzyMetric::DamerauLevenshtein,
+            _ => {
+                return fail_clierror!(
+                    "Invalid --fuzzy-metric: {}. Valid values are jarowinkler & damerau.",
+                    self.flag_fuzzy_metric
+                )
+            }
+        };
+        if !(0.0..=1.0).contains(&self.flag_fuzzy_threshold) {
+            return fail_clierror!("--fuzzy-threshold must be between 0 and 1.");
+        }
+        Ok(Some(FuzzyMatch {
+            metric,
+            threshold: self.flag_fuzzy_threshold,
+        }))
+    }
+
+    fn range_join(&self) -> CliResult<Option<RangeJoin>> {
+        let kind = match (self.flag_asof, self.flag_interval) {
+            (false, false) => return Ok(None),
+            (true, true) => return fail_clierror!("Please pick one of --asof & --interval."),
+            (true, false) => {
+                let direction = match self.flag_direction.to_lowercase().as_str() {
+                    "backward" => AsOfDirection::Backward,
+                    "forward" => AsOfDirection::Forward,
+                    "nearest" => AsOfDirection::Nearest,
+                    _ => {
+                        return fail_clierror!(
+                            "Invalid --direction: {}. Valid values are backward, forward & \
+                             nearest.",
+                            self.flag_direction
+                        )
+                    }
+                };
+                if self
+                    .flag_tolerance
+                    .map_or(false, |tolerance| tolerance < 0.0)
+                {
+                    return fail_clierror!("--tolerance cannot be negative.");
+                }
+                RangeKind::AsOf {
+                    direction,
+                    tolerance: self.flag_tolerance,
+                }
+            }
+            (false, true) => RangeKind::Interval,
+        };
+        if self.flag_fuzzy {
+            return fail_clierror!("--fuzzy cannot be used with as-of & interval joins.");
+        }
+        let by = match self.flag_by {
+            Some(ref by) => Some(parse_column_pair(by)?),
+            None => None,
+        };
+        Ok(Some(RangeJoin {
+            kind,
+            left: self.flag_left,
+            by,
+            prefer_dmy: self.flag_prefer_dmy || std::env::var("QSV_PREFER_DMY").is_ok(),
+        }))
+    }
+
+    fn reader_config(&self, input: &str, columns: SelectColumns) -> Config {
+        Config::new(&Some(input.to_string()))
+            .delimiter(self.flag_delimiter)
+            .no_headers(self.flag_no_headers)
+            .checkutf8(false)
+            .select(columns)
+    }
+
+    /// Joins <input1> to every other input in one pass, with a hash index for
+    /// each of the other inputs.
+    fn multi_join(&self) -> CliResult<()> {
+        if self.flag_left_anti
+            || self.flag_left_semi
+            || self.flag_right
+            || self.flag_full
+            || self.flag_cross
+            || self.flag_sorted
+            || self.flag_check_sorted
+            || self.flag_fuzzy
+            || self.flag_asof
+            || self.flag_interval
+        {
+            return fail_clierror!("Multi-way joins only support inner & left joins.");
+        }
+        if self.has_output_columns() {
+            return fail_clierror!(
+                "--right-select, --coalesce-keys and the prefixes & suffixes of columns are not \
+                 supported by multi-way joins."
+            );
+        }
+
+        // the fact table is joined on <columns1> to <input2>, and on the fact columns
+        // of <columns> to every other input
+        let mut dim_specs = vec![(
+            self.arg_columns1.clone(),
+            self.arg_columns2.clone(),
+            self.arg_input2.clone(),
+        )];
+        for (columns, input) in self.arg_columns.iter().zip(&self.arg_input) {
+            let (fact_columns, dim_columns) = parse_column_pair(columns)?;
+            dim_specs.push((fact_columns, dim_columns, input.clone()));
+        }
+
+        let lefts: Vec<bool> = match self.flag_joins {
+            Some(ref joins) => joins
+                .split(',')
+                .map(|join| match join.trim().to_lowercase().as_str() {
+                    "inner" => Ok(false),
+                    "left" => Ok(true),
+                    _ => fail_format!("Invalid join operation in --joins: {join}"),
+                })
+                .collect::<Result<_, String>>()?,
+            None => vec![self.flag_left; dim_specs.len()],
+        };
+        if lefts.len() != dim_specs.len() {
+            return fail_clierror!(
+                "--joins must have a join operation for each of the {} inputs after <input1>, but \
+                 has {}.",
+                dim_specs.len(),
+                lefts.len()
+            );
+        }
+
+        let transform = self.key_transform()?;
+        let mut rdr1 = self
+            .reader_config(&self.arg_input1, SelectColumns::parse("")?)
+            .reader_file_stdin()?;
+        let mut headers = rdr1.byte_headers()?.clone();
+        let fact_headers = headers.clone();
+
+        let mut dims = Vec::with_capacity(dim_specs.len());
+        for ((fact_columns, dim_columns, input), left) in dim_specs.into_iter().zip(lefts) {
+            let rconf = self.reader_config(&input, dim_columns);
+            let mut rdr = rconf.reader_file_stdin()?;
+            let dim_headers = rdr.byte_headers()?.clone();
+            let dim_sel = rconf.selection(&dim_headers)?;
+            let fact_sel = fact_columns.selection(&fact_headers, !self.flag_no_headers)?;
+            if fact_sel.len() != dim_sel.len() {
+                return fail_clierror!(
+                    "Column selections must have the same number of columns, but found column \
+                     selections with {} and {} columns for {input}.",
+                    fact_sel.len(),
+                    dim_sel.len()
+                );
+            }
+
+            headers.extend(dim_headers.iter());
+            dims.push(Dimension {
+                validx: ValueIndex::new(rdr, &dim_sel, &transform, self.flag_nulls)?,
+                fact_sel,
+                left,
+                pad: repeat(b"").take(dim_headers.len()).collect(),
+            });
+        }
+
+        let mut wtr = Config::new(&self.flag_output).writer()?;
+        if !self.flag_no_headers {
+            wtr.write_record(&headers)?;
+        }
+
+        let mut scratch = csv::ByteRecord::new();
+        for row in rdr1.byte_records() {
+            let row = row?;
+            let mut combined = vec![row.clone()];
+            for dim in &mut dims {
+                let key = get_row_key(&dim.fact_sel, &row, &transform);
+                if let Some(rows) = dim.validx.values.get(&key) {
+                    let mut dim_rows = Vec::with_capacity(rows.len());
+                    for &rowi in rows {
+                        dim.validx.idx.seek(rowi as u64)?;
+                        dim.validx.idx.read_byte_record(&mut scratch)?;
+                        dim_rows.push(scratch.clone());
+                    }
+                    combined = combined
+                        .iter()
+                        .flat_map(|record| {
+                            dim_rows.iter().map(move |dim_row| {
+                                let mut record = record.clone();
+                                record.extend(dim_row.iter());
+                                record
+                            })
+                        })
+                        .collect();
+                } else if dim.left {
+                    for record in &mut combined {
+                        record.extend(dim.pad.iter());
+                    }
+                } else {
+                    combined.clear();
+                    break;
+                }
+            }
+            for record in &combined {
+                wtr.write_record(record)?;
+            }
+        }
+        Ok(wtr.flush()?)
+    }
+
     fn new_io_state(
         &self,
     ) -> CliResult<IoState<Box<dyn SeekRead + 'static>, Box<dyn io::Write + 'static>>> {
+        if self.flag_memory_limit == Some(0) {
+            return fail_clierror!("--memory-limit must be greater than 0.");
+        }
         let rconf1 = Config::new(&Some(self.arg_input1.clone()))
             .delimiter(self.flag_delimiter)
             .no_headers(self.flag_no_headers)
@@ -328,18 +1118,125 @@ impl Args {
         let mut rdr1 = rconf1.reader_file_stdin()?;
         let mut rdr2 = rconf2.reader_file_stdin()?;
         let (sel1, sel2) = self.get_selections(&rconf1, &mut rdr1, &rconf2, &mut rdr2)?;
+        let joined_len = rdr1.byte_headers()?.len() + rdr2.byte_headers()?.len();
+        let columns = if self.has_output_columns() {
+            Some(self.output_columns(
+                rdr1.byte_headers()?,
+                rdr2.byte_headers()?,
+                &sel1,
+                &sel2,
+                rconf1.no_headers,
+            )?)
+        } else {
+            None
+        };
         Ok(IoState {
-            wtr: Config::new(&self.flag_output).writer()?,
+            wtr: JoinWriter {
+                wtr: Config::new(&self.flag_output).writer()?,
+                columns,
+                joined_len,
+                record: csv::ByteRecord::new(),
+            },
             rdr1,
             sel1,
             rdr2,
             sel2,
             no_headers: rconf1.no_headers,
-            casei: self.flag_ignore_case,
+            transform: self.key_transform()?,
             nulls: self.flag_nulls,
+            memory_limit: self.flag_memory_limit.map(|mb| mb * 1024 * 1024),
+            output: self.flag_output.clone(),
         })
     }
 
+    fn has_output_columns(&self) -> bool {
+        self.flag_right_select.is_some()
+            || self.flag_coalesce_keys
+            || self.flag_left_prefix.is_some()
+            || self.flag_left_suffix.is_some()
+            || self.flag_right_prefix.is_some()
+            || self.flag_right_suffix.is_some()
+    }
+
+    /// the columns of the joined rows, with the columns of the second input
+    /// limited by --right-select, the join columns merged by --coalesce-keys, and
+    /// the columns with the same name in both inputs renamed
+    fn output_columns(
+        &self,
+        headers1: &csv::ByteRecord,
+        headers2: &csv::ByteRecord,
+        sel1: &Selection,
+        sel2: &Selection,
+        no_headers: bool,
+    ) -> CliResult<Vec<OutputColumn>> {
+        let len1 = headers1.len();
+        let right: Vec<usize> = match self.flag_right_select {
+            Some(ref columns) => columns.selection(headers2, !no_headers)?.to_vec(),
+            None => (0..headers2.len()).collect(),
+        };
+
+        let mut columns: Vec<OutputColumn> = (0..len1)
+            .map(|i| OutputColumn {
+                index:    i,
+                coalesce: None,
+                name:     None,
+            })
+            .collect();
+        if self.flag_coalesce_keys {
+            for (&i, &j) in sel1.iter().zip(sel2.iter()) {
+                columns[i].coalesce = Some(len1 + j);
+            }
+        }
+        columns.extend(
+            right
+                .into_iter()
+                .filter(|j| !self.flag_coalesce_keys || !sel2.contains(j))
+                .map(|j| OutputColumn {
+                    index:    len1 + j,
+                    coalesce: None,
+                    name:     None,
+                }),
+        );
+        if no_headers {
+            return Ok(columns);
+        }
+
+        let names: Vec<&[u8]> = columns
+            .iter()
+            .map(|column| {
+                if column.index < len1 {
+                    &headers1[column.index]
+                } else {
+                    &headers2[column.index - len1]
+                }
+            })
+            .collect();
+        let (names1, names2): (AHashSet<&[u8]>, AHashSet<&[u8]>) = {
+            let (names1, names2): (Vec<_>, Vec<_>) = columns
+                .iter()
+                .zip(&names)
+                .partition(|(column, _)| column.index < len1);
+            (
+                names1.into_iter().map(|(_, &name)| name).collect(),
+                names2.into_iter().map(|(_, &name)| name).collect(),
+            )
+        };
+        for (column, name) in columns.iter_mut().zip(names) {
+            let (others, prefix, suffix) = if column.index < len1 {
+                (&names2, &self.flag_left_prefix, &self.flag_left_suffix)
+            } else {
+                (&names1, &self.flag_right_prefix, &self.flag_right_suffix)
+            };
+            if (prefix.is_some() || suffix.is_some()) && others.contains(name) {
+                let mut renamed = prefix.as_deref().unwrap_or_default().as_bytes().to_vec();
+                renamed.extend_from_slice(name);
+                renamed.extend_from_slice(suffix.as_deref().unwrap_or_default().as_bytes());
+                column.name = Some(renamed);
+            }
+        }
+        Ok(columns)
+    }
+
     #[allow(clippy::unused_self)]
     fn get_selections<R: io::Read>(
         &self,
@@ -352,7 +1249,8 @@ impl Args {
         let headers2 = rdr2.byte_headers()?;
         let select1 = rconf1.selection(headers1)?;
         let select2 = rconf2.selection(headers2)?;
-        if select1.len() != select2.len() {
+        // the columns of an interval join are checked when joining
+        if !self.flag_interval && select1.len() != select2.len() {
             return fail_clierror!(
                 "Column selections must have the same number of columns, but found column \
                  selections with {} and {} columns.",
@@ -364,6 +1262,123 @@ impl Args {
     }
 }
 
+/// a column of the joined rows
+#[derive(Clone)]
+struct OutputColumn {
+    /// the index of the column in the joined row
+    index:    usize,
+    /// the index of the column to use when this column is empty
+    coalesce: Option<usize>,
+    /// the new name of the column
+    name:     Option<ByteString>,
+}
+
+/// Writes the joined rows, with the output columns when set.
+/// Any trailing fields after the columns of both inputs, like the score of
+/// fuzzy joins, are written as is.
+struct JoinWriter<W: io::Write> {
+    wtr:        csv::Writer<W>,
+    columns:    Option<Vec<OutputColumn>>,
+    /// the number of columns of both inputs
+    joined_len: usize,
+    record:     csv::ByteRecord,
+}
+
+impl<W: io::Write> JoinWriter<W> {
+    fn write_headers<I, T>(&mut self, headers: I) -> csv::Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        self.write(headers, true)
+    }
+
+    fn write_record<I, T>(&mut self, record: I) -> csv::Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        self.write(record, false)
+    }
+
+    fn write<I, T>(&mut self, record: I, headers: bool) -> csv::Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let Some(ref columns) = self.columns else {
+            return self.wtr.write_record(record);
+        };
+        let joined: Vec<T> = record.into_iter().collect();
+        self.record.clear();
+        for column in columns {
+            match column.name {
+                Some(ref name) if headers => self.record.push_field(name),
+                _ => {
+                    let value = joined[column.index].as_ref();
+                    match column.coalesce {
+                        Some(coalesce) if value.is_empty() => {
+                            self.record.push_field(joined[coalesce].as_ref());
+                        }
+                        _ => self.record.push_field(value),
+                    }
+                }
+            }
+        }
+        for extra in joined.iter().skip(self.joined_len) {
+            self.record.push_field(extra.as_ref());
+        }
+        self.wtr.write_record(&self.record)
+    }
+}
+
+/// the estimated memory of each row of the index of a hash join, besides its key
+const INDEX_ROW_OVERHEAD: usize = 64;
+
+/// Writes the rows of an input to `partitions` CSV files in `dir` by the hash
+/// of their join keys. Each file starts with the header row of the input (its
+/// first row with --no-headers), so the partitions are joined like the inputs.
+fn write_partitions<R: io::Read>(
+    rdr: &mut csv::Reader<R>,
+    sel: &Selection,
+    transform: &KeyTransform,
+    dir: &Path,
+    name: &str,
+    partitions: usize,
+) -> CliResult<Vec<PathBuf>> {
+    let paths: Vec<PathBuf> = (0..partitions)
+        .map(|i| dir.join(format!("{name}-{i}.csv")))
+        .collect();
+    let headers = rdr.byte_headers()?.clone();
+    let mut wtrs = Vec::with_capacity(partitions);
+    for path in &paths {
+        let mut wtr = csv::Writer::from_path(path)?;
+        wtr.write_record(&headers)?;
+        wtrs.push(wtr);
+    }
+
+    let mut row = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut row)? {
+        let mut hasher = DefaultHasher::new();
+        get_row_key(sel, &row, transform).hash(&mut hasher);
+        wtrs[(hasher.finish() % partitions as u64) as usize].write_byte_record(&row)?;
+    }
+    for mut wtr in wtrs {
+        wtr.flush()?;
+    }
+    Ok(paths)
+}
+
+/// A dimension table of a multi-way join, with the columns of the fact table
+/// it is joined on.
+struct Dimension<R> {
+    validx:   ValueIndex<R>,
+    fact_sel: Selection,
+    /// whether the dimension is left joined
+    left:     bool,
+    pad:      csv::ByteRecord,
+}
+
 struct ValueIndex<R> {
     // This maps tuples of values to corresponding rows.
     values:   AHashMap<Vec<ByteString>, Vec<usize>>,
@@ -375,7 +1390,7 @@ impl<R: io::Read + io::Seek> ValueIndex<R> {
     fn new(
         mut rdr: csv::Reader<R>,
         sel: &Selection,
-        casei: bool,
+        transform: &KeyTransform,
         nulls: bool,
     ) -> CliResult<ValueIndex<R>> {
         let mut val_idx = AHashMap::with_capacity(10000);
@@ -406,7 +1421,7 @@ impl<R: io::Read + io::Seek> ValueIndex<R> {
             // indexes in one pass.
             row_idx.write_u64::<BigEndian>(row.position().unwrap().byte())?;
 
-            let fields: Vec<_> = sel.select(&row).map(|v| transform(v, casei)).collect();
+            let fields: Vec<_> = sel.select(&row).map(|v| transform.apply(v)).collect();
             if nulls || !fields.iter().any(std::vec::Vec::is_empty) {
                 match val_idx.entry(fields) {
                     Entry::Vacant(v) => {
@@ -450,27 +1465,375 @@ impl<R> fmt::Debug for ValueIndex<R> {
     }
 }
 
+/// The rows of the second input of a sort-merge join with the same join key.
+struct MergeGroup {
+    key:      Vec<ByteString>,
+    rows:     Vec<csv::ByteRecord>,
+    /// rows with empty join keys are only joined with --nulls
+    joinable: bool,
+    matched:  bool,
+}
+
+/// Reads the rows of an input of a sort-merge join with their join keys,
+/// checking that the keys are sorted.
+struct SortedReader<'a, R> {
+    rdr:       &'a mut csv::Reader<R>,
+    sel:       &'a Selection,
+    transform: &'a KeyTransform,
+    nulls:     bool,
+    name:      &'static str,
+    prev_key:  Option<Vec<ByteString>>,
+    peeked:    Option<(Vec<ByteString>, csv::ByteRecord)>,
+    rowi:      u64,
+}
+
+impl<'a, R: io::Read> SortedReader<'a, R> {
+    fn new(
+        rdr: &'a mut csv::Reader<R>,
+        sel: &'a Selection,
+        transform: &'a KeyTransform,
+        nulls: bool,
+        name: &'static str,
+    ) -> Self {
+        SortedReader {
+            rdr,
+            sel,
+            transform,
+            nulls,
+            name,
+            prev_key: None,
+            peeked: None,
+            rowi: 0,
+        }
+    }
+
+    fn is_joinable(&self, key: &[ByteString]) -> bool {
+        self.nulls || !key.iter().any(std::vec::Vec::is_empty)
+    }
+
+    fn next_record(&mut self) -> CliResult<Option<(Vec<ByteString>, csv::ByteRecord)>> {
+        if let Some(peeked) = self.peeked.take() {
+            return Ok(Some(peeked));
+        }
+
+        let mut row = csv::ByteRecord::new();
+        if !self.rdr.read_byte_record(&mut row)? {
+            return Ok(None);
+        }
+        self.rowi += 1;
+        let key = get_row_key(self.sel, &row, self.transform);
+
+        // a single comparison per row, so the order is always checked. An input
+        // sorted on the raw keys is not necessarily sorted on the transformed keys.
+        if let Some(prev_key) = &self.prev_key {
+            if key < *prev_key {
+                return fail_clierror!(
+                    "{} is not sorted on the join columns: row {} with key ({}) comes after key \
+                     ({}).",
+                    self.name,
+                    self.rowi,
+                    display_key(&key),
+                    display_key(prev_key)
+                );
+            }
+        }
+        self.prev_key = Some(key.clone());
+        Ok(Some((key, row)))
+    }
+
+    /// the next run of rows with the same join key
+    fn next_group(&mut self) -> CliResult<Option<MergeGroup>> {
+        let Some((key, row)) = self.next_record()? else {
+            return Ok(None);
+        };
+        let mut group = MergeGroup {
+            joinable: self.is_joinable(&key),
+            key,
+            rows: vec![row],
+            matched: false,
+        };
+        if !group.joinable {
+            return Ok(Some(group));
+        }
+
+        while let Some((key, row)) = self.next_record()? {
+            if key != group.key {
+                self.peeked = Some((key, row));
+                break;
+            }
+            group.rows.push(row);
+        }
+        Ok(Some(group))
+    }
+}
+
+fn display_key(key: &[ByteString]) -> String {
+    key.iter()
+        .map(|k| String::from_utf8_lossy(k))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[inline]
-fn get_row_key(sel: &Selection, row: &csv::ByteRecord, casei: bool) -> Vec<ByteString> {
-    sel.select(row).map(|v| transform(v, casei)).collect()
+fn get_row_key(
+    sel: &Selection,
+    row: &csv::ByteRecord,
+    transform: &KeyTransform,
+) -> Vec<ByteString> {
+    sel.select(row).map(|v| transform.apply(v)).collect()
 }
 
 pub type ByteString = Vec<u8>;
 
-#[inline]
-pub fn transform(bs: &[u8], casei: bool) -> ByteString {
-    if let Ok(s) = str::from_utf8(bs) {
-        if casei {
-            let norm: String = s
-                .trim()
+/// The normalizations of the join keys, besides trimming and --ignore-case.
+#[derive(Clone, Copy)]
+enum KeyNormalizer {
+    /// Unicode compatibility normalization (NFKC)
+    Nfkc,
+    /// removes diacritics, e.g. "é" becomes "e"
+    Diacritics,
+    /// removes punctuation and symbols
+    Punctuation,
+    /// removes all whitespace
+    Whitespace,
+    /// replaces the key with its Eudex phonetic hash
+    Eudex,
+}
+
+impl FromStr for KeyNormalizer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "nfkc" => Ok(KeyNormalizer::Nfkc),
+            "diacritics" => Ok(KeyNormalizer::Diacritics),
+            "punctuation" => Ok(KeyNormalizer::Punctuation),
+            "whitespace" => Ok(KeyNormalizer::Whitespace),
+            "eudex" => Ok(KeyNormalizer::Eudex),
+            _ => fail_format!("Invalid key normalizer: {s}"),
+        }
+    }
+}
+
+impl KeyNormalizer {
+    fn apply(self, s: &str) -> String {
+        match self {
+            KeyNormalizer::Nfkc => s.nfkc().collect(),
+            KeyNormalizer::Diacritics => s.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect(),
+            KeyNormalizer::Punctuation => s
                 .chars()
+                .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+                .collect(),
+            KeyNormalizer::Whitespace => s.chars().filter(|c| !c.is_whitespace()).collect(),
+            KeyNormalizer::Eudex if s.is_empty() => String::new(),
+            KeyNormalizer::Eudex => format!("{:016x}", u64::from(eudex::Hash::new(s))),
+        }
+    }
+}
+
+/// the direction of the nearest key of an as-of join
+#[derive(Clone, Copy)]
+enum AsOfDirection {
+    Backward,
+    Forward,
+    Nearest,
+}
+
+#[derive(Clone, Copy)]
+enum RangeKind {
+    AsOf {
+        direction: AsOfDirection,
+        tolerance: Option<f64>,
+    },
+    Interval,
+}
+
+/// an as-of or interval join
+struct RangeJoin {
+    kind:       RangeKind,
+    left:       bool,
+    /// the columns of both inputs that must also be equal
+    by:         Option<(SelectColumns, SelectColumns)>,
+    prefer_dmy: bool,
+}
+
+/// a row of the second input of an as-of or interval join, with its key as
+/// the interval from `lo` to `hi` (the same key for as-of joins)
+struct RangeRow {
+    lo:   f64,
+    hi:   f64,
+    rowi: usize,
+}
+
+/// the partition of a row of an as-of or interval join, by its --by columns,
+/// or None if it has an empty --by column and nulls aren't joined
+fn range_key(
+    by: Option<&Selection>,
+    row: &csv::ByteRecord,
+    transform: &KeyTransform,
+    nulls: bool,
+) -> Option<Vec<ByteString>> {
+    let Some(by) = by else {
+        return Some(vec![]);
+    };
+    let key = get_row_key(by, row, transform);
+    if !nulls && key.iter().any(std::vec::Vec::is_empty) {
+        return None;
+    }
+    Some(key)
+}
+
+#[derive(Clone, Copy)]
+enum RangeType {
+    Number,
+    /// in days since the Unix epoch
+    Date,
+}
+
+/// parses the keys of an as-of or interval join, all as numbers or all as
+/// dates, by the type of the first non-empty key
+struct RangeValues {
+    typ:        Option<RangeType>,
+    prefer_dmy: bool,
+}
+
+impl RangeValues {
+    const fn new(prefer_dmy: bool) -> Self {
+        Self {
+            typ: None,
+            prefer_dmy,
+        }
+    }
+
+    /// parses a key, or returns None if it is empty
+    #[allow(clippy::cast_precision_loss)]
+    fn parse(&mut self, value: &[u8]) -> CliResult<Option<f64>> {
+        let value = String::from_utf8_lossy(value);
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        let number = value
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite());
+        let typ = *self.typ.get_or_insert(if number.is_some() {
+            RangeType::Number
+        } else {
+            RangeType::Date
+        });
+        match typ {
+            RangeType::Number => match number {
+                Some(number) => Ok(Some(number)),
+                None => fail_clierror!(
+                    "The as-of/interval join key \"{value}\" is not a number, like the first key."
+                ),
+            },
+            RangeType::Date => match parse_with_preference(value, self.prefer_dmy) {
+                Ok(date) if number.is_none() => {
+                    Ok(Some(date.timestamp_millis() as f64 / 86_400_000.0))
+                }
+                _ => fail_clierror!(
+                    "The as-of/interval join key \"{value}\" is not a date, like the first key."
+                ),
+            },
+        }
+    }
+}
+
+/// finds the row with the nearest key to `value` in the given direction, in the
+/// rows of a partition sorted by key. Ties go to the last row for backward
+/// matches, and to the first row for forward matches.
+fn asof_match(
+    entries: &[RangeRow],
+    value: f64,
+    direction: AsOfDirection,
+    tolerance: Option<f64>,
+) -> Option<&RangeRow> {
+    let backward = || entries[..entries.partition_point(|entry| entry.lo <= value)].last();
+    let forward = || entries.get(entries.partition_point(|entry| entry.lo < value));
+    let entry = match direction {
+        AsOfDirection::Backward => backward(),
+        AsOfDirection::Forward => forward(),
+        AsOfDirection::Nearest => match (backward(), forward()) {
+            (Some(back), Some(fwd)) if fwd.lo - value < value - back.lo => Some(fwd),
+            (back, fwd) => back.or(fwd),
+        },
+    };
+    entry.filter(|entry| tolerance.map_or(true, |tolerance| (entry.lo - value).abs() <= tolerance))
+}
+
+/// parses the columns of both inputs, as either the same columns for both or
+/// '<columns1>=<columns2>'
+fn parse_column_pair(columns: &str) -> CliResult<(SelectColumns, SelectColumns)> {
+    Ok(match columns.split_once('=') {
+        Some((columns1, columns2)) => (
+            SelectColumns::parse(columns1)?,
+            SelectColumns::parse(columns2)?,
+        ),
+        None => {
+            let columns = SelectColumns::parse(columns)?;
+            (columns.clone(), columns)
+        }
+    })
+}
+
+#[derive(Clone, Copy)]
+enum FuzzyMetric {
+    JaroWinkler,
+    DamerauLevenshtein,
+}
+
+struct FuzzyMatch {
+    metric:    FuzzyMetric,
+    threshold: f64,
+}
+
+impl FuzzyMatch {
+    fn score(&self, a: &str, b: &str) -> f64 {
+        match self.metric {
+            FuzzyMetric::JaroWinkler => strsim::jaro_winkler(a, b),
+            FuzzyMetric::DamerauLevenshtein => strsim::normalized_damerau_levenshtein(a, b),
+        }
+    }
+}
+
+/// the join key as a string, to compute its similarity with other keys
+fn fuzzy_key(key: &[ByteString]) -> String {
+    key.iter()
+        .map(|k| String::from_utf8_lossy(k))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Transforms the values of the join columns into join keys.
+#[derive(Clone)]
+struct KeyTransform {
+    casei:       bool,
+    normalizers: Vec<KeyNormalizer>,
+}
+
+impl KeyTransform {
+    #[inline]
+    fn apply(&self, bs: &[u8]) -> ByteString {
+        let Ok(s) = str::from_utf8(bs) else {
+            return bs.to_vec();
+        };
+        let s = s.trim();
+        if !self.casei && self.normalizers.is_empty() {
+            return s.as_bytes().to_vec();
+        }
+
+        let mut norm: String = if self.casei {
+            s.chars()
                 .map(|c| c.to_lowercase().next().unwrap())
-                .collect();
-            norm.into_bytes()
+                .collect()
         } else {
-            s.trim().as_bytes().to_vec()
+            s.to_string()
+        };
+        for normalizer in &self.normalizers {
+            norm = normalizer.apply(&norm);
         }
-    } else {
-        bs.to_vec()
+        norm.into_bytes()
     }
 }
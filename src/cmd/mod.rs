@@ -5,8 +5,14 @@ pub mod applydp;
 #[cfg(any(feature = "full", feature = "lite"))]
 pub mod behead;
 #[cfg(any(feature = "full", feature = "lite"))]
+pub mod cast;
+#[cfg(any(feature = "full", feature = "lite"))]
 pub mod cat;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod correlate;
 pub mod count;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod crosstab;
 pub mod dedup;
 #[cfg(feature = "full")]
 pub mod diff;
@@ -33,9 +39,13 @@ pub mod fmt;
 #[cfg(all(feature = "foreach", target_family = "unix", not(feature = "lite")))]
 pub mod foreach;
 pub mod frequency;
+#[cfg(all(feature = "from", not(feature = "lite")))]
+pub mod from;
 #[cfg(all(feature = "generate", not(feature = "lite")))]
 pub mod generate;
 pub mod headers;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod histogram;
 pub mod index;
 pub mod input;
 #[cfg(any(feature = "full", feature = "lite"))]
@@ -46,6 +56,8 @@ pub mod jsonl;
 pub mod luau;
 #[cfg(any(feature = "full", feature = "lite"))]
 pub mod partition;
+#[cfg(feature = "full")]
+pub mod patch;
 pub mod pseudo;
 #[cfg(all(feature = "python", not(feature = "lite")))]
 pub mod python;
@@ -57,6 +69,8 @@ pub mod safenames;
 pub mod sample;
 #[cfg(any(feature = "full", feature = "lite"))]
 pub mod schema;
+#[cfg(any(feature = "full", feature = "lite"))]
+pub mod schemadiff;
 pub mod search;
 pub mod searchset;
 pub mod select;
@@ -68,6 +82,8 @@ pub mod sortcheck;
 pub mod split;
 pub mod stats;
 #[cfg(any(feature = "full", feature = "lite"))]
+pub mod statsdiff;
+#[cfg(any(feature = "full", feature = "lite"))]
 pub mod table;
 #[cfg(all(feature = "to", not(feature = "lite")))]
 pub mod to;
@@ -0,0 +1,411 @@
+static USAGE: &str = r#"
+Applies the changes of a diff to a CSV, to rebuild the right CSV of the diff from
+its left CSV.
+
+The changes are the output of 'qsv diff' in its default csv format, where each row
+is prefixed by a 'diffresult' column:
+  -  a row of the base CSV that was removed or modified
+  +  a row that was added or modified
+A modified row has both a '-' and a '+' row with the same primary key.
+
+Rows are matched on the same primary key columns as the diff, which is the first
+column by default, and can be set with --key. The primary key of the base CSV must
+be unique.
+
+By default, the changes are loaded in memory and the base CSV is streamed: its
+modified rows are replaced in place, its removed rows are dropped, and the added
+rows are written at the end, in the order of the changes.
+With --sorted, both the base CSV and the changes are streamed instead, and must be
+sorted on the primary key, e.g. with 'qsv sort --select id base.csv' and
+'qsv sort --select 2 changes.csv', as the columns of the changes come after their
+diffresult column. The rows are then written in the order of the primary key.
+
+The changes conflict with the base CSV when a removed or modified row is not in
+the base CSV, or when an added row is already in it. Without --check, patch fails
+at the first conflict. With --check, the patched CSV is not written. Instead, all
+the conflicts are written, including the fields of removed & modified rows that
+don't match their '-' row, with the primary key columns followed by conflict
+(missing, exists or modified), column, expected & actual columns.
+patch then fails if there are any conflicts.
+
+e.g. 'qsv diff --key id old.csv new.csv > changes.csv'
+     'qsv patch --key id old.csv changes.csv > new.csv'
+
+For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_patch.rs.
+
+Usage:
+    qsv patch [options] <base> <changes>
+    qsv patch --help
+
+patch options:
+    -k, --key <arg>        The primary key columns, by name or by index
+                           starting at 1.
+                           See 'qsv select --help' for the format details.
+                           [default: 1]
+    --sorted               Stream both inputs, which must be sorted on the
+                           primary key.
+    --check                Write the conflicts between the base CSV and the
+                           changes, instead of the patched CSV.
+
+Common options:
+    -h, --help             Display this message
+    -o, --output <file>    Write output to <file> instead of stdout.
+    -n, --no-headers       When set, the first row will not be interpreted
+                           as headers. (i.e., They are not searched, analyzed,
+                           sliced, etc.)
+    -d, --delimiter <arg>  The field delimiter for reading CSV data.
+                           Must be a single character. (default: ,)
+"#;
+
+use std::{cmp::Ordering, io};
+
+use ahash::AHashMap;
+use serde::Deserialize;
+
+use crate::{
+    config::{Config, Delimiter},
+    select::{SelectColumns, Selection},
+    util, CliResult,
+};
+
+type ByteString = Vec<u8>;
+
+#[derive(Deserialize)]
+struct Args {
+    arg_base:        String,
+    arg_changes:     String,
+    flag_key:        SelectColumns,
+    flag_sorted:     bool,
+    flag_check:      bool,
+    flag_output:     Option<String>,
+    flag_no_headers: bool,
+    flag_delimiter:  Option<Delimiter>,
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let rconf_base = Config::new(&Some(args.arg_base.clone()))
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers)
+        .select(args.flag_key.clone());
+    let rconf_changes = Config::new(&Some(args.arg_changes.clone()))
+        .delimiter(args.flag_delimiter)
+        .no_headers(args.flag_no_headers);
+
+    let mut rdr_base = rconf_base.reader()?;
+    let mut rdr_changes = rconf_changes.reader()?;
+    let headers = rdr_base.byte_headers()?.clone();
+    if !rconf_base.no_headers
+        && rdr_changes
+            .byte_headers()?
+            .iter()
+            .skip(1)
+            .ne(headers.iter())
+    {
+        return fail_clierror!(
+            "The columns of the changes after their diffresult column don't match the columns of \
+             the base CSV."
+        );
+    }
+
+    let key = rconf_base.selection(&headers)?;
+    // the names of the columns, or their indices starting at 1 without headers
+    let names: Vec<ByteString> = if rconf_base.no_headers {
+        (1..=headers.len())
+            .map(|i| i.to_string().into_bytes())
+            .collect()
+    } else {
+        headers.iter().map(<[u8]>::to_vec).collect()
+    };
+
+    let mut patcher = Patcher {
+        wtr: Config::new(&args.flag_output).writer()?,
+        key,
+        names,
+        check: args.flag_check,
+        conflicts: 0,
+    };
+    patcher.write_headers(&headers, rconf_base.no_headers)?;
+    if args.flag_sorted {
+        patcher.patch_sorted(&mut rdr_base, rdr_changes)?;
+    } else {
+        patcher.patch(&mut rdr_base, &mut rdr_changes)?;
+    }
+    patcher.wtr.flush()?;
+
+    match patcher.conflicts {
+        0 => Ok(()),
+        1 => fail_clierror!("1 conflict found."),
+        n => fail_clierror!("{n} conflicts found."),
+    }
+}
+
+/// the change of a row: its expected row in the base CSV, and its new row,
+/// both with their diffresult column
+#[derive(Default)]
+struct Change {
+    before: Option<csv::ByteRecord>,
+    after:  Option<csv::ByteRecord>,
+}
+
+impl Change {
+    fn add(&mut self, row: csv::ByteRecord, key: &[ByteString]) -> CliResult<()> {
+        let (slot, diffresult) = match row.get(0) {
+            Some(b"-") => (&mut self.before, "-"),
+            Some(b"+") => (&mut self.after, "+"),
+            diffresult => {
+                return fail_clierror!(
+                    "Invalid diffresult {:?} for the primary key {}. It must be - or +.",
+                    String::from_utf8_lossy(diffresult.unwrap_or_default()),
+                    display_key(key)
+                )
+            }
+        };
+        if slot.is_some() {
+            return fail_clierror!(
+                "More than one {diffresult} row for the primary key {}.",
+                display_key(key)
+            );
+        }
+        *slot = Some(row);
+        Ok(())
+    }
+}
+
+struct Patcher<W: io::Write> {
+    wtr:       csv::Writer<W>,
+    key:       Selection,
+    names:     Vec<ByteString>,
+    check:     bool,
+    conflicts: u64,
+}
+
+impl<W: io::Write> Patcher<W> {
+    fn write_headers(&mut self, headers: &csv::ByteRecord, no_headers: bool) -> CliResult<()> {
+        if self.check {
+            let mut conflict_headers: Vec<&[u8]> =
+                self.key.iter().map(|&i| &self.names[i][..]).collect();
+            conflict_headers.extend([&b"conflict"[..], b"column", b"expected", b"actual"]);
+            self.wtr.write_record(conflict_headers)?;
+        } else if !no_headers {
+            self.wtr.write_record(headers)?;
+        }
+        Ok(())
+    }
+
+    fn base_key(&self, row: &csv::ByteRecord) -> Vec<ByteString> {
+        self.key.select(row).map(<[u8]>::to_vec).collect()
+    }
+
+    /// Loads the changes in memory, and streams the base CSV.
+    fn patch<R: io::Read>(
+        &mut self,
+        rdr_base: &mut csv::Reader<R>,
+        rdr_changes: &mut csv::Reader<R>,
+    ) -> CliResult<()> {
+        let mut changes: AHashMap<Vec<ByteString>, Change> = AHashMap::new();
+        // the keys of the changes in their order, for the added rows
+        let mut keys = Vec::new();
+        for row in rdr_changes.byte_records() {
+            let row = row?;
+            let key = change_key(&self.key, &row);
+            let change = changes.entry(key.clone()).or_insert_with(|| {
+                keys.push(key.clone());
+                Change::default()
+            });
+            change.add(row, &key)?;
+        }
+
+        for row in rdr_base.byte_records() {
+            let row = row?;
+            let key = self.base_key(&row);
+            match changes.remove(&key) {
+                Some(change) => self.apply(&key, Some(&row), &change)?,
+                None => self.write_base(&row)?,
+            }
+        }
+        for key in keys {
+            if let Some(change) = changes.remove(&key) {
+                self.apply(&key, None, &change)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Streams both the base CSV and the changes, sorted on the primary key.
+    fn patch_sorted<R: io::Read>(
+        &mut self,
+        rdr_base: &mut csv::Reader<R>,
+        rdr_changes: csv::Reader<R>,
+    ) -> CliResult<()> {
+        let mut changes = SortedChanges {
+            rdr:      rdr_changes,
+            row:      csv::ByteRecord::new(),
+            has_row:  false,
+            last_key: None,
+        };
+        changes.has_row = changes.rdr.read_byte_record(&mut changes.row)?;
+        let mut change = changes.next(&self.key)?;
+
+        let mut base_row = csv::ByteRecord::new();
+        let mut has_base = rdr_base.read_byte_record(&mut base_row)?;
+        let mut last_base_key: Option<Vec<ByteString>> = None;
+        loop {
+            let base_key = if has_base {
+                let base_key = self.base_key(&base_row);
+                if last_base_key
+                    .as_ref()
+                    .map_or(false, |last| base_key <= *last)
+                {
+                    return fail_clierror!(
+                        "The base CSV is not sorted on the primary key, or has a duplicate \
+                         primary key, at the primary key {}.",
+                        display_key(&base_key)
+                    );
+                }
+                Some(base_key)
+            } else {
+                None
+            };
+
+            let order = match (&base_key, &change) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(base_key), Some((change_key, _))) => base_key.cmp(change_key),
+            };
+            // the row of the base CSV, if it comes first or has a change
+            let base = match order {
+                Ordering::Less => {
+                    self.write_base(&base_row)?;
+                    last_base_key = base_key;
+                    has_base = rdr_base.read_byte_record(&mut base_row)?;
+                    continue;
+                }
+                Ordering::Equal => Some(&base_row),
+                Ordering::Greater => None,
+            };
+            if let Some((key, row_change)) = change.take() {
+                self.apply(&key, base, &row_change)?;
+                change = changes.next(&self.key)?;
+            }
+            if order.is_eq() {
+                last_base_key = base_key;
+                has_base = rdr_base.read_byte_record(&mut base_row)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_base(&mut self, row: &csv::ByteRecord) -> CliResult<()> {
+        if !self.check {
+            self.wtr.write_record(row)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the change of a row to its row in the base CSV, if any.
+    fn apply(
+        &mut self,
+        key: &[ByteString],
+        base: Option<&csv::ByteRecord>,
+        change: &Change,
+    ) -> CliResult<()> {
+        match (base, &change.before) {
+            (Some(base), Some(before)) => {
+                if self.check {
+                    for (i, actual) in base.iter().enumerate() {
+                        let expected = before.get(i + 1).unwrap_or_default();
+                        if actual != expected {
+                            let column = self.names.get(i).cloned().unwrap_or_default();
+                            self.conflict(key, "modified", &column, expected, actual)?;
+                        }
+                    }
+                }
+            }
+            (None, Some(_)) => self.conflict(key, "missing", b"", b"", b"")?,
+            (Some(_), None) => self.conflict(key, "exists", b"", b"", b"")?,
+            (None, None) => {}
+        }
+        if let (false, Some(after)) = (self.check, &change.after) {
+            self.wtr.write_record(after.iter().skip(1))?;
+        }
+        Ok(())
+    }
+
+    fn conflict(
+        &mut self,
+        key: &[ByteString],
+        conflict: &str,
+        column: &[u8],
+        expected: &[u8],
+        actual: &[u8],
+    ) -> CliResult<()> {
+        if !self.check {
+            return fail_clierror!(
+                "The changes conflict with the base CSV at the primary key {}: {}. Use --check to \
+                 list all the conflicts.",
+                display_key(key),
+                match conflict {
+                    "missing" => "the row to remove or modify is missing",
+                    _ => "the row to add already exists",
+                }
+            );
+        }
+        self.conflicts += 1;
+        self.wtr.write_record(key.iter().map(Vec::as_slice).chain([
+            conflict.as_bytes(),
+            column,
+            expected,
+            actual,
+        ]))?;
+        Ok(())
+    }
+}
+
+/// Reads the changes sorted on their primary key, as the change of each key.
+struct SortedChanges<R> {
+    rdr:      csv::Reader<R>,
+    row:      csv::ByteRecord,
+    has_row:  bool,
+    last_key: Option<Vec<ByteString>>,
+}
+
+impl<R: io::Read> SortedChanges<R> {
+    fn next(&mut self, sel: &Selection) -> CliResult<Option<(Vec<ByteString>, Change)>> {
+        if !self.has_row {
+            return Ok(None);
+        }
+        let key = change_key(sel, &self.row);
+        if self.last_key.as_ref().map_or(false, |last| key <= *last) {
+            return fail_clierror!(
+                "The changes are not sorted on the primary key, at the primary key {}.",
+                display_key(&key)
+            );
+        }
+
+        let mut change = Change::default();
+        while self.has_row && change_key(sel, &self.row) == key {
+            change.add(self.row.clone(), &key)?;
+            self.has_row = self.rdr.read_byte_record(&mut self.row)?;
+        }
+        self.last_key = Some(key.clone());
+        Ok(Some((key, change)))
+    }
+}
+
+/// the primary key of a row of the changes, whose columns come after the
+/// diffresult column
+fn change_key(sel: &Selection, row: &csv::ByteRecord) -> Vec<ByteString> {
+    sel.iter()
+        .map(|&i| row.get(i + 1).unwrap_or_default().to_vec())
+        .collect()
+}
+
+fn display_key(key: &[ByteString]) -> String {
+    key.iter()
+        .map(|k| String::from_utf8_lossy(k))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -10,6 +10,17 @@ should not flag any invalid records.
 Generated schema file has `.schema.json` postfix appended. For example, 
 for input `mydata.csv`, schema file would be `mydata.csv.schema.json`.
 
+The same inferred types & constraints can also be emitted in other schema
+formats with the --format option. The schema file postfix then depends on
+the format:
+    tableschema   Frictionless Table Schema   mydata.csv.tableschema.json
+    csvw          CSVW metadata               mydata.csv-metadata.json
+    postgresql    PostgreSQL CREATE TABLE     mydata.csv.sql
+    sqlite        SQLite CREATE TABLE         mydata.csv.sql
+    mysql         MySQL CREATE TABLE          mydata.csv.sql
+    avro          Avro schema                 mydata.csv.avsc
+    arrow         Arrow/Parquet schema JSON   mydata.csv.arrow.json
+
 If piped from stdin, then schema file would be `stdin.csv.schema.json` and
 a `stdin.csv` file will created with stdin's contents as well.
 
@@ -40,7 +51,40 @@ Schema options:
                                [default: date,time,due,open,close,created]
     --prefer-dmy               Prefer to parse dates in dmy format.
                                Otherwise, use mdy format.
-    --stdout                   Send generated JSON schema file to stdout instead.
+    --infer-boolean            Infer boolean columns, setting their type to "boolean".
+                               A column is inferred as boolean if all its non-empty
+                               values are in the --truthy or --falsy sets. The case
+                               variants of these values are listed in the
+                               "x-trueValues" & "x-falseValues" properties of the
+                               column, for the `validate` & `cast` commands.
+    --truthy <list>            The case-insensitive comma-separated list of values
+                               that are considered true when inferring booleans.
+                               Ignored if --infer-boolean is false.
+                               [default: true,t,yes,y,1]
+    --falsy <list>             The case-insensitive comma-separated list of values
+                               that are considered false when inferring booleans.
+                               Ignored if --infer-boolean is false.
+                               [default: false,f,no,n,0]
+    --infer-subtypes           Infer semantic subtypes of string columns (email, url,
+                               uuid, ipv4, ipv6, currency, percentage & postalcode),
+                               and add them as "format" constraints.
+    --slack <pct>              Widen the inferred minimum/maximum and minLength/maxLength
+                               constraints by this percentage of their values, so
+                               slightly smaller or larger values in similar CSVs
+                               still validate. e.g. with a slack of 10, a maximum of
+                               200 becomes 220 and a minLength of 10 becomes 9.
+                               [default: 0]
+    --unique-hints             Mark columns whose cardinality equals the row count
+                               with an "x-unique" property, and set the first such
+                               column without empty values as the "primaryKey".
+                               The `validate` command enforces the primary key.
+    --format <arg>             The schema format to generate. One of jsonschema,
+                               tableschema, csvw, postgresql, sqlite, mysql,
+                               avro or arrow. Formats other than jsonschema
+                               always type date/datetime columns, as if
+                               --strict-dates was set.
+                               [default: jsonschema]
+    --stdout                   Send generated schema file to stdout instead.
     -j, --jobs <arg>           The number of jobs to run in parallel.
                                When not set, the number of jobs is set to the
                                number of CPUs detected.
@@ -67,7 +111,7 @@ use serde_json::{json, value::Number, Map, Value};
 use stats::Frequencies;
 
 use crate::{
-    cmd::stats::Stats,
+    cmd::stats::{boolean_values, Stats, SubType, DEFAULT_FALSY, DEFAULT_TRUTHY},
     config::{Config, Delimiter},
     select::SelectColumns,
     util, CliResult,
@@ -80,6 +124,13 @@ pub struct Args {
     pub flag_pattern_columns: SelectColumns,
     pub flag_dates_whitelist: String,
     pub flag_prefer_dmy:      bool,
+    pub flag_infer_boolean:   bool,
+    pub flag_truthy:          String,
+    pub flag_falsy:           String,
+    pub flag_infer_subtypes:  bool,
+    pub flag_slack:           f64,
+    pub flag_unique_hints:    bool,
+    pub flag_format:          String,
     pub flag_stdout:          bool,
     pub flag_jobs:            Option<usize>,
     pub flag_no_headers:      bool,
@@ -89,10 +140,65 @@ pub struct Args {
 
 const STDIN_CSV: &str = "stdin.csv";
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SchemaFormat {
+    JsonSchema,
+    TableSchema,
+    Csvw,
+    Sql(SqlDialect),
+    Avro,
+    Arrow,
+}
+
+impl SchemaFormat {
+    const fn file_suffix(self) -> &'static str {
+        match self {
+            SchemaFormat::JsonSchema => ".schema.json",
+            SchemaFormat::TableSchema => ".tableschema.json",
+            SchemaFormat::Csvw => "-metadata.json",
+            SchemaFormat::Sql(_) => ".sql",
+            SchemaFormat::Avro => ".avsc",
+            SchemaFormat::Arrow => ".arrow.json",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SqlDialect {
+    PostgreSql,
+    Sqlite,
+    MySql,
+}
+
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let preargs: Args = util::get_args(USAGE, argv)?;
     let mut args = preargs.clone();
 
+    let format = match args.flag_format.to_lowercase().as_str() {
+        "jsonschema" => SchemaFormat::JsonSchema,
+        "tableschema" => SchemaFormat::TableSchema,
+        "csvw" => SchemaFormat::Csvw,
+        "postgresql" => SchemaFormat::Sql(SqlDialect::PostgreSql),
+        "sqlite" => SchemaFormat::Sql(SqlDialect::Sqlite),
+        "mysql" => SchemaFormat::Sql(SqlDialect::MySql),
+        "avro" => SchemaFormat::Avro,
+        "arrow" => SchemaFormat::Arrow,
+        _ => {
+            return fail_clierror!(
+                "Invalid --format: {}. Valid values are jsonschema, tableschema, csvw, \
+                 postgresql, sqlite, mysql, avro & arrow.",
+                args.flag_format
+            )
+        }
+    };
+    if args.flag_slack < 0.0 {
+        return fail_clierror!("--slack cannot be negative.");
+    }
+    // the other formats have native date types, so always type date/datetime columns
+    if format != SchemaFormat::JsonSchema {
+        args.flag_strict_dates = true;
+    }
+
     // if using stdin, we create a stdin.csv file as stdin is not seekable and we need to
     // open the file multiple times to compile stats/unique values, etc.
     let (input_path, input_filename) = if preargs.arg_input.is_none() {
@@ -137,37 +243,54 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         }
     }
 
-    // generate list of required fields
-    let required_fields = get_required_fields(&properties_map);
-
-    // create final JSON object for output
-    let schema = json!({
-        "$schema": "https://json-schema.org/draft-07/schema",
-        "title": format!("JSON Schema for {input_filename}"),
-        "description": "Inferred JSON Schema from QSV schema command",
-        "type": "object",
-        "properties": Value::Object(properties_map),
-        "required": Value::Array(required_fields)
-    });
-
-    let schema_pretty = match serde_json::to_string_pretty(&schema) {
-        Ok(s) => s,
-        Err(e) => return fail_clierror!("Cannot prettify schema json: {e}"),
+    let primary_key = get_primary_key(&properties_map);
+
+    let output = if format == SchemaFormat::JsonSchema {
+        // generate list of required fields
+        let required_fields = get_required_fields(&properties_map);
+
+        // create final JSON object for output
+        let mut schema = json!({
+            "$schema": "https://json-schema.org/draft-07/schema",
+            "title": format!("JSON Schema for {input_filename}"),
+            "description": "Inferred JSON Schema from QSV schema command",
+            "type": "object",
+            "properties": Value::Object(properties_map),
+            "required": Value::Array(required_fields)
+        });
+        if let Some(primary_key) = primary_key {
+            schema["primaryKey"] = Value::String(primary_key);
+        }
+        to_pretty_json(&schema)?
+    } else {
+        let columns = schema_columns(&properties_map, primary_key.as_deref());
+        let table_name = Path::new(&input_filename).file_stem().map_or_else(
+            || input_filename.clone(),
+            |s| s.to_string_lossy().to_string(),
+        );
+        match format {
+            SchemaFormat::TableSchema => to_pretty_json(&to_table_schema(&columns))?,
+            SchemaFormat::Csvw => to_pretty_json(&to_csvw(&input_filename, &columns))?,
+            SchemaFormat::Sql(dialect) => to_sql_ddl(&table_name, &columns, dialect),
+            SchemaFormat::Avro => to_pretty_json(&to_avro(&table_name, &columns))?,
+            SchemaFormat::Arrow => to_pretty_json(&to_arrow(&columns))?,
+            SchemaFormat::JsonSchema => unreachable!(),
+        }
     };
 
     if args.flag_stdout {
         let stdout = std::io::stdout();
         let mut handle = stdout.lock();
 
-        handle.write_all(schema_pretty.as_bytes())?;
+        handle.write_all(output.as_bytes())?;
         handle.flush()?;
 
         info!("Schema written to stdout");
     } else {
-        let schema_output_filename = input_path + ".schema.json";
+        let schema_output_filename = input_path + format.file_suffix();
         let mut schema_output_file = File::create(&schema_output_filename)?;
 
-        schema_output_file.write_all(schema_pretty.as_bytes())?;
+        schema_output_file.write_all(output.as_bytes())?;
         schema_output_file.flush()?;
 
         woutinfo!("Schema written to {schema_output_filename}");
@@ -184,10 +307,21 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 ///  * maxLength
 ///  * min
 ///  * max
+/// With --unique-hints, columns with as many unique values as rows also get "x-unique".
 pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<Map<String, Value>> {
     // invoke cmd::stats
     let (csv_fields, csv_stats, stats_col_index_map) = get_stats_records(args)?;
 
+    // the row count, to identify the columns whose values are all unique
+    let row_count = if args.flag_unique_hints {
+        let rconfig = Config::new(&args.arg_input)
+            .delimiter(args.flag_delimiter)
+            .no_headers(args.flag_no_headers);
+        Some(count_input_rows(&rconfig)?)
+    } else {
+        None
+    };
+
     // amortize memory allocation
     let mut low_cardinality_column_indices: Vec<usize> =
         Vec::with_capacity(args.flag_enum_threshold);
@@ -252,9 +386,27 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
             "String" => {
                 type_list.push(Value::String("string".to_string()));
 
+                // format constraint, if a semantic subtype was inferred
+                if let Some(subtype) = stats_col_index_map
+                    .get("subtype")
+                    .and_then(|idx| stats_record.get(*idx))
+                    .and_then(|s| s.parse::<SubType>().ok())
+                {
+                    field_map.insert(
+                        "format".to_string(),
+                        Value::String(subtype.json_schema_format().to_string()),
+                    );
+                }
+
                 // minLength constraint
                 if let Some(min_length_str) = stats_record.get(stats_col_index_map["min_length"]) {
-                    let min_length = min_length_str.parse::<u32>().unwrap();
+                    let min_length = slack_bound(
+                        f64::from(min_length_str.parse::<u32>().unwrap()),
+                        args.flag_slack,
+                        false,
+                    )
+                    .floor()
+                    .max(0.0) as u32;
                     field_map.insert(
                         "minLength".to_string(),
                         Value::Number(Number::from(min_length)),
@@ -263,7 +415,12 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
 
                 // maxLength constraint
                 if let Some(max_length_str) = stats_record.get(stats_col_index_map["max_length"]) {
-                    let max_length = max_length_str.parse::<u32>().unwrap();
+                    let max_length = slack_bound(
+                        f64::from(max_length_str.parse::<u32>().unwrap()),
+                        args.flag_slack,
+                        true,
+                    )
+                    .ceil() as u32;
                     field_map.insert(
                         "maxLength".to_string(),
                         Value::Number(Number::from(max_length)),
@@ -281,12 +438,14 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
                 type_list.push(Value::String("integer".to_string()));
 
                 if let Some(min_str) = stats_record.get(stats_col_index_map["min"]) {
-                    let min = min_str.parse::<i64>().unwrap();
+                    let min =
+                        slack_int_bound(min_str.parse::<i64>().unwrap(), args.flag_slack, false);
                     field_map.insert("minimum".to_string(), Value::Number(Number::from(min)));
                 };
 
                 if let Some(max_str) = stats_record.get(stats_col_index_map["max"]) {
-                    let max = max_str.parse::<i64>().unwrap();
+                    let max =
+                        slack_int_bound(max_str.parse::<i64>().unwrap(), args.flag_slack, true);
                     field_map.insert("maximum".to_string(), Value::Number(Number::from(max)));
                 };
 
@@ -302,7 +461,7 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
                 type_list.push(Value::String("number".to_string()));
 
                 if let Some(min_str) = stats_record.get(stats_col_index_map["min"]) {
-                    let min = min_str.parse::<f64>().unwrap();
+                    let min = slack_bound(min_str.parse::<f64>().unwrap(), args.flag_slack, false);
                     field_map.insert(
                         "minimum".to_string(),
                         Value::Number(Number::from_f64(min).unwrap()),
@@ -310,7 +469,7 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
                 };
 
                 if let Some(max_str) = stats_record.get(stats_col_index_map["max"]) {
-                    let max = max_str.parse::<f64>().unwrap();
+                    let max = slack_bound(max_str.parse::<f64>().unwrap(), args.flag_slack, true);
                     field_map.insert(
                         "maximum".to_string(),
                         Value::Number(Number::from_f64(max).unwrap()),
@@ -320,6 +479,19 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
             "NULL" => {
                 type_list.push(Value::String("null".to_string()));
             }
+            "Boolean" => {
+                type_list.push(Value::String("boolean".to_string()));
+
+                // validate only accepts true/false for booleans, unless their values are listed
+                field_map.insert(
+                    "x-trueValues".to_string(),
+                    json!(boolean_values(&args.flag_truthy)),
+                );
+                field_map.insert(
+                    "x-falseValues".to_string(),
+                    json!(boolean_values(&args.flag_falsy)),
+                );
+            }
             "Date" => {
                 type_list.push(Value::String("string".to_string()));
 
@@ -361,6 +533,16 @@ pub fn infer_schema_from_stats(args: &Args, input_filename: &str) -> CliResult<M
             field_map.insert("enum".to_string(), Value::Array(enum_list.clone()));
         }
 
+        if let Some(row_count) = row_count {
+            let col_cardinality = stats_record
+                .get(stats_col_index_map["cardinality"])
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(0);
+            if row_count > 0 && col_cardinality == row_count {
+                field_map.insert("x-unique".to_string(), Value::Bool(true));
+            }
+        }
+
         // add current field definition to properties map
         properties_map.insert(header_string, Value::Object(field_map.clone()));
     }
@@ -386,6 +568,10 @@ fn get_stats_records(args: &Args) -> CliResult<(ByteRecord, Vec<Stats>, AHashMap
         flag_infer_dates:     true,
         flag_dates_whitelist: args.flag_dates_whitelist.to_string(),
         flag_prefer_dmy:      args.flag_prefer_dmy,
+        flag_infer_boolean:   args.flag_infer_boolean,
+        flag_truthy:          args.flag_truthy.clone(),
+        flag_falsy:           args.flag_falsy.clone(),
+        flag_infer_subtypes:  args.flag_infer_subtypes,
         flag_jobs:            Some(util::njobs(args.flag_jobs)),
         flag_output:          None,
         flag_no_headers:      args.flag_no_headers,
@@ -423,6 +609,22 @@ fn get_stats_records(args: &Args) -> CliResult<(ByteRecord, Vec<Stats>, AHashMap
     Ok((csv_fields, csv_stats, stats_col_index_map))
 }
 
+/// count the rows of the input, from its index if it has one.
+/// Unlike util::count_rows, the count isn't cached process-wide, as that
+/// cache would hand a stale count to other inputs counted in the same process.
+fn count_input_rows(rconfig: &Config) -> CliResult<u64> {
+    if let Some(idx) = rconfig.indexed()? {
+        return Ok(idx.count());
+    }
+    let mut rdr = rconfig.reader()?;
+    let mut record = ByteRecord::new();
+    let mut count = 0u64;
+    while rdr.read_byte_record(&mut record)? {
+        count += 1;
+    }
+    Ok(count)
+}
+
 /// get column selector argument string for low cardinality columns
 fn build_low_cardinality_column_selector_arg(
     low_cardinality_column_indices: &mut Vec<usize>,
@@ -469,15 +671,23 @@ fn get_unique_values(
 ) -> CliResult<AHashMap<String, Vec<String>>> {
     // prepare arg for invoking cmd::frequency
     let freq_args = crate::cmd::frequency::Args {
-        arg_input:       args.arg_input.clone(),
-        flag_select:     crate::select::SelectColumns::parse(column_select_arg).unwrap(),
-        flag_limit:      args.flag_enum_threshold,
-        flag_asc:        false,
-        flag_no_nulls:   true,
-        flag_jobs:       Some(util::njobs(args.flag_jobs)),
-        flag_output:     None,
-        flag_no_headers: args.flag_no_headers,
-        flag_delimiter:  args.flag_delimiter,
+        arg_input:            args.arg_input.clone(),
+        flag_select:          crate::select::SelectColumns::parse(column_select_arg).unwrap(),
+        flag_limit:           args.flag_enum_threshold,
+        flag_asc:             false,
+        flag_no_nulls:        true,
+        flag_weight:          None,
+        flag_rank:            false,
+        flag_pct:             false,
+        flag_other:           false,
+        flag_other_text:      String::new(),
+        flag_json:            false,
+        flag_approx:          false,
+        flag_approx_capacity: 0,
+        flag_jobs:            Some(util::njobs(args.flag_jobs)),
+        flag_output:          None,
+        flag_no_headers:      args.flag_no_headers,
+        flag_delimiter:       args.flag_delimiter,
     };
 
     let (headers, ftables) = match freq_args.rconfig().indexed()? {
@@ -555,6 +765,41 @@ fn get_required_fields(properties_map: &Map<String, Value>) -> Vec<Value> {
     fields
 }
 
+/// the first unique column without empty values, if any
+fn get_primary_key(properties_map: &Map<String, Value>) -> Option<String> {
+    properties_map
+        .iter()
+        .find(|(_, field_def)| {
+            field_def.get("x-unique") == Some(&Value::Bool(true))
+                && !field_def["type"]
+                    .as_array()
+                    .map_or(false, |types| types.contains(&json!("null")))
+        })
+        .map(|(name, _)| name.clone())
+}
+
+/// widen an inferred lower or upper bound by the slack percentage of its value
+fn slack_bound(value: f64, slack: f64, upper: bool) -> f64 {
+    let delta = value.abs() * slack / 100.0;
+    if upper {
+        value + delta
+    } else {
+        value - delta
+    }
+}
+
+fn slack_int_bound(value: i64, slack: f64, upper: bool) -> i64 {
+    if slack == 0.0 {
+        return value;
+    }
+    let bound = slack_bound(value as f64, slack, upper);
+    if upper {
+        bound.ceil() as i64
+    } else {
+        bound.floor() as i64
+    }
+}
+
 /// generate map of regex patterns from selected String column of CSV
 fn generate_string_patterns(
     args: &Args,
@@ -640,3 +885,382 @@ fn should_emit_pattern_constraint(field_def: &Value) -> bool {
 
     type_list.contains(&Value::String("string".to_string())) && !has_enum
 }
+
+fn to_pretty_json(schema: &Value) -> CliResult<String> {
+    match serde_json::to_string_pretty(schema) {
+        Ok(s) => Ok(s),
+        Err(e) => fail_clierror!("Cannot prettify schema json: {e}"),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Date,
+    DateTime,
+}
+
+/// an inferred column, as needed by the non-JSON Schema formats
+struct SchemaColumn {
+    name:        String,
+    kind:        ColumnKind,
+    nullable:    bool,
+    unique:      bool,
+    primary_key: bool,
+    def:         Map<String, Value>,
+}
+
+impl SchemaColumn {
+    fn description(&self) -> Option<&str> {
+        self.def.get("description").and_then(Value::as_str)
+    }
+
+    fn format(&self) -> Option<&str> {
+        self.def.get("format").and_then(Value::as_str)
+    }
+
+    /// the enum constraint, without the null that marks optional columns
+    fn enum_values(&self) -> Option<Vec<Value>> {
+        let values: Vec<Value> = self
+            .def
+            .get("enum")?
+            .as_array()?
+            .iter()
+            .filter(|v| !v.is_null())
+            .cloned()
+            .collect();
+        (!values.is_empty()).then_some(values)
+    }
+
+    /// true if the integer column has values outside of the i32 range
+    fn is_big_integer(&self) -> bool {
+        ["minimum", "maximum"].iter().any(|key| {
+            self.def
+                .get(*key)
+                .and_then(Value::as_i64)
+                .map_or(false, |n| i32::try_from(n).is_err())
+        })
+    }
+}
+
+/// derive the column type & nullability from the JSON Schema properties map
+fn schema_columns(
+    properties_map: &Map<String, Value>,
+    primary_key: Option<&str>,
+) -> Vec<SchemaColumn> {
+    properties_map
+        .iter()
+        .map(|(name, def)| {
+            let types: Vec<&str> = def
+                .get("type")
+                .and_then(Value::as_array)
+                .map(|types| types.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let format = def.get("format").and_then(Value::as_str);
+            let kind = match types.iter().find(|t| **t != "null") {
+                Some(&"integer") => ColumnKind::Integer,
+                Some(&"number") => ColumnKind::Number,
+                Some(&"boolean") => ColumnKind::Boolean,
+                Some(&"string") if format == Some("date") => ColumnKind::Date,
+                Some(&"string") if format == Some("date-time") => ColumnKind::DateTime,
+                // an all-empty column is typed as a nullable string
+                _ => ColumnKind::String,
+            };
+            SchemaColumn {
+                name: name.clone(),
+                kind,
+                nullable: types.contains(&"null") || types.is_empty(),
+                unique: def.get("x-unique") == Some(&Value::Bool(true)),
+                primary_key: primary_key == Some(name.as_str()),
+                def: def.as_object().cloned().unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// generate a Frictionless Table Schema
+fn to_table_schema(columns: &[SchemaColumn]) -> Value {
+    let fields: Vec<Value> = columns
+        .iter()
+        .map(|col| {
+            let mut field = Map::new();
+            field.insert("name".to_string(), json!(col.name));
+            if let Some(description) = col.description() {
+                field.insert("description".to_string(), json!(description));
+            }
+            let field_type = match col.kind {
+                ColumnKind::String => "string",
+                ColumnKind::Integer => "integer",
+                ColumnKind::Number => "number",
+                ColumnKind::Boolean => "boolean",
+                ColumnKind::Date => "date",
+                ColumnKind::DateTime => "datetime",
+            };
+            field.insert("type".to_string(), json!(field_type));
+            match (col.kind, col.format()) {
+                (ColumnKind::String, Some(format @ ("email" | "uri" | "uuid"))) => {
+                    field.insert("format".to_string(), json!(format));
+                }
+                // inferred dates are not necessarily in ISO 8601 format
+                (ColumnKind::Date | ColumnKind::DateTime, _) => {
+                    field.insert("format".to_string(), json!("any"));
+                }
+                (ColumnKind::Boolean, _) => {
+                    // the inferred values, or the default ones
+                    for (key, list, defaults) in [
+                        ("trueValues", "x-trueValues", DEFAULT_TRUTHY),
+                        ("falseValues", "x-falseValues", DEFAULT_FALSY),
+                    ] {
+                        let values = col
+                            .def
+                            .get(list)
+                            .cloned()
+                            .unwrap_or_else(|| json!(boolean_values(defaults)));
+                        field.insert(key.to_string(), values);
+                    }
+                }
+                _ => {}
+            }
+
+            let mut constraints = Map::new();
+            if !col.nullable {
+                constraints.insert("required".to_string(), json!(true));
+            }
+            if col.unique && !col.primary_key {
+                constraints.insert("unique".to_string(), json!(true));
+            }
+            for key in ["minLength", "maxLength", "minimum", "maximum", "pattern"] {
+                if let Some(value) = col.def.get(key) {
+                    constraints.insert(key.to_string(), value.clone());
+                }
+            }
+            if let Some(values) = col.enum_values() {
+                constraints.insert("enum".to_string(), Value::Array(values));
+            }
+            if !constraints.is_empty() {
+                field.insert("constraints".to_string(), Value::Object(constraints));
+            }
+            Value::Object(field)
+        })
+        .collect();
+
+    let mut table_schema = json!({
+        "fields": fields,
+        "missingValues": [""]
+    });
+    if let Some(col) = columns.iter().find(|col| col.primary_key) {
+        table_schema["primaryKey"] = json!(col.name);
+    }
+    table_schema
+}
+
+/// generate CSV on the Web (CSVW) metadata
+fn to_csvw(input_filename: &str, columns: &[SchemaColumn]) -> Value {
+    let csvw_columns: Vec<Value> = columns
+        .iter()
+        .map(|col| {
+            let base = match col.kind {
+                ColumnKind::String => "string",
+                ColumnKind::Integer => "integer",
+                ColumnKind::Number => "double",
+                ColumnKind::Boolean => "boolean",
+                ColumnKind::Date => "date",
+                ColumnKind::DateTime => "dateTime",
+            };
+            let mut datatype = Map::new();
+            datatype.insert("base".to_string(), json!(base));
+            for key in ["minLength", "maxLength", "minimum", "maximum"] {
+                if let Some(value) = col.def.get(key) {
+                    datatype.insert(key.to_string(), value.clone());
+                }
+            }
+            // for string datatypes, the CSVW format is a regular expression
+            if let Some(pattern) = col.def.get("pattern") {
+                datatype.insert("format".to_string(), pattern.clone());
+            }
+
+            let mut column = Map::new();
+            column.insert("name".to_string(), json!(identifier(&col.name)));
+            column.insert("titles".to_string(), json!(col.name));
+            if let Some(description) = col.description() {
+                column.insert("dc:description".to_string(), json!(description));
+            }
+            column.insert(
+                "datatype".to_string(),
+                if datatype.len() == 1 {
+                    json!(base)
+                } else {
+                    Value::Object(datatype)
+                },
+            );
+            column.insert("required".to_string(), json!(!col.nullable));
+            Value::Object(column)
+        })
+        .collect();
+
+    let mut metadata = json!({
+        "@context": "http://www.w3.org/ns/csvw",
+        "url": input_filename,
+        "tableSchema": {
+            "columns": csvw_columns
+        }
+    });
+    if let Some(col) = columns.iter().find(|col| col.primary_key) {
+        metadata["tableSchema"]["primaryKey"] = json!(identifier(&col.name));
+    }
+    metadata
+}
+
+impl SqlDialect {
+    fn quote(self, name: &str) -> String {
+        let quote = if self == SqlDialect::MySql { '`' } else { '"' };
+        let escaped = name.replace(quote, &format!("{quote}{quote}"));
+        format!("{quote}{escaped}{quote}")
+    }
+
+    fn column_type(self, col: &SchemaColumn) -> String {
+        let sql_type = match (self, col.kind) {
+            (SqlDialect::MySql, ColumnKind::String) => {
+                // VARCHAR columns have to fit in MySQL's 65,535 byte row size limit,
+                // and key columns in InnoDB's 3,072 byte index key limit
+                let limit = if col.primary_key || col.unique {
+                    MYSQL_MAX_KEY_LENGTH
+                } else {
+                    16_383
+                };
+                match col.def.get("maxLength").and_then(Value::as_u64) {
+                    Some(max_length) if max_length <= limit => {
+                        return format!("VARCHAR({})", max_length.max(1));
+                    }
+                    _ => "TEXT",
+                }
+            }
+            (_, ColumnKind::String) => "TEXT",
+            (SqlDialect::Sqlite, ColumnKind::Integer) => "INTEGER",
+            (SqlDialect::PostgreSql, ColumnKind::Integer) if !col.is_big_integer() => "INTEGER",
+            (SqlDialect::MySql, ColumnKind::Integer) if !col.is_big_integer() => "INT",
+            (_, ColumnKind::Integer) => "BIGINT",
+            (SqlDialect::PostgreSql, ColumnKind::Number) => "DOUBLE PRECISION",
+            (SqlDialect::Sqlite, ColumnKind::Number) => "REAL",
+            (SqlDialect::MySql, ColumnKind::Number) => "DOUBLE",
+            // SQLite has no boolean and date types, and uses INTEGER & TEXT instead
+            (SqlDialect::Sqlite, ColumnKind::Boolean) => "INTEGER",
+            (SqlDialect::Sqlite, ColumnKind::Date | ColumnKind::DateTime) => "TEXT",
+            (_, ColumnKind::Boolean) => "BOOLEAN",
+            (_, ColumnKind::Date) => "DATE",
+            (SqlDialect::PostgreSql, ColumnKind::DateTime) => "TIMESTAMP",
+            (SqlDialect::MySql, ColumnKind::DateTime) => "DATETIME",
+        };
+        sql_type.to_string()
+    }
+}
+
+/// the longest VARCHAR that fits in an InnoDB index key, with 4 byte utf8mb4 characters
+const MYSQL_MAX_KEY_LENGTH: u64 = 768;
+
+/// generate a CREATE TABLE statement, with NOT NULL for columns without empty values
+fn to_sql_ddl(table_name: &str, columns: &[SchemaColumn], dialect: SqlDialect) -> String {
+    let column_defs = columns
+        .iter()
+        .map(|col| {
+            let column_type = dialect.column_type(col);
+            let not_null = if col.nullable { "" } else { " NOT NULL" };
+            let name = dialect.quote(&col.name);
+            let key = match (col.primary_key, col.unique) {
+                (true, _) => " PRIMARY KEY",
+                (false, true) => " UNIQUE",
+                (false, false) => "",
+            };
+            // MySQL cannot index TEXT columns without a prefix length,
+            // so leave the key out and say so in the DDL
+            if dialect == SqlDialect::MySql && column_type == "TEXT" && !key.is_empty() {
+                return format!(
+                    "    -- too long for{key} in MySQL, index a prefix instead\n    {name} \
+                     {column_type}{not_null}"
+                );
+            }
+            format!("    {name} {column_type}{not_null}{key}")
+        })
+        .join(",\n");
+    format!(
+        "CREATE TABLE {} (\n{column_defs}\n);\n",
+        dialect.quote(table_name)
+    )
+}
+
+/// generate an Avro record schema
+fn to_avro(table_name: &str, columns: &[SchemaColumn]) -> Value {
+    let fields: Vec<Value> = columns
+        .iter()
+        .map(|col| {
+            let avro_type = match col.kind {
+                ColumnKind::String => json!("string"),
+                ColumnKind::Integer => json!("long"),
+                ColumnKind::Number => json!("double"),
+                ColumnKind::Boolean => json!("boolean"),
+                ColumnKind::Date => json!({"type": "int", "logicalType": "date"}),
+                ColumnKind::DateTime => {
+                    json!({"type": "long", "logicalType": "timestamp-millis"})
+                }
+            };
+            let mut field = Map::new();
+            field.insert("name".to_string(), json!(identifier(&col.name)));
+            if col.nullable {
+                field.insert("type".to_string(), json!(["null", avro_type]));
+                field.insert("default".to_string(), Value::Null);
+            } else {
+                field.insert("type".to_string(), avro_type);
+            }
+            if let Some(description) = col.description() {
+                field.insert("doc".to_string(), json!(description));
+            }
+            Value::Object(field)
+        })
+        .collect();
+
+    json!({
+        "type": "record",
+        "name": identifier(table_name),
+        "fields": fields
+    })
+}
+
+/// generate an Arrow schema, in the Arrow JSON format also used for Parquet
+fn to_arrow(columns: &[SchemaColumn]) -> Value {
+    let fields: Vec<Value> = columns
+        .iter()
+        .map(|col| {
+            let arrow_type = match col.kind {
+                ColumnKind::String => json!({"name": "utf8"}),
+                ColumnKind::Integer => json!({"name": "int", "bitWidth": 64, "isSigned": true}),
+                ColumnKind::Number => json!({"name": "floatingpoint", "precision": "DOUBLE"}),
+                ColumnKind::Boolean => json!({"name": "bool"}),
+                ColumnKind::Date => json!({"name": "date", "unit": "DAY"}),
+                ColumnKind::DateTime => json!({"name": "timestamp", "unit": "MILLISECOND"}),
+            };
+            json!({
+                "name": col.name,
+                "nullable": col.nullable,
+                "type": arrow_type,
+                "children": []
+            })
+        })
+        .collect();
+
+    json!({ "fields": fields })
+}
+
+/// make a valid CSVW/Avro name from a column or file name
+fn identifier(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    ident
+}
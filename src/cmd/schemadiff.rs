@@ -0,0 +1,520 @@
+static USAGE: &str = r#"
+Compare two schemas to detect changes in the format of a CSV extract, and flag the
+changes that are not backward compatible.
+
+Each of <old> and <new> is either a JSON Schema (e.g. generated by the `schema`
+command) or a Frictionless Table Schema if it has a .json extension, or a CSV file
+whose schema is inferred the same way as the `schema` command does. To check a new
+extract against the schema of a previous one, use:
+
+    qsv schemadiff old.csv.schema.json new.csv
+
+Columns are aligned by name. A removed column and an added column with the same
+types are reported as "renamed" if their names are similar (see --rename-threshold)
+or if they are at the same position.
+
+When one of the inputs is a CSV file, its inferred enum, range & length constraints
+only describe the values of the extract, and vary with every extract. They are not
+compared then, and only the names, types, nullability, formats & patterns of the
+columns are. Use the `validate` command to check the values of an extract against a
+schema.
+
+The changes are formatted as CSV data:
+
+    field,constraint,old,new,change,breaking
+
+where constraint is the compared property of the column (name, type, nullable,
+enum, format, dateformat, pattern, minimum, maximum, minLength or maxLength), and
+change is one of:
+    added      the column is only in the new schema
+    removed    the column is only in the old schema
+    renamed    the column was probably renamed
+    widened    the new schema accepts more values, e.g. an integer column
+               becoming a number, or a column becoming nullable
+    narrowed   the new schema accepts fewer values, e.g. a larger minimum
+    changed    the old and new schemas accept different values,
+               e.g. an integer column becoming a boolean
+For enum values, old lists the values that are no longer allowed, and new the
+values that are now allowed.
+
+A change is breaking if data that is valid for the new schema may not be valid for
+the old schema, i.e. for the loads built for the old format. All the changes are
+breaking, except for added columns and narrowed constraints.
+
+schemadiff exits with a non-zero exit code if there are breaking changes.
+
+For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_schemadiff.rs.
+
+Usage:
+    qsv schemadiff [options] <old> <new>
+    qsv schemadiff --help
+
+schemadiff options:
+    --ignore-constraints       Only compare the names, types & nullability of the
+                               columns, and not their enum, format, pattern,
+                               range & length constraints.
+    --rename-threshold <arg>   The minimum similarity of the names of a removed
+                               and an added column to report them as renamed,
+                               from 0 to 1 (normalized Levenshtein distance).
+                               [default: 0.6]
+
+Schema inference options, for CSV inputs:
+    --enum-threshold NUM       Cardinality threshold for adding enum constraints.
+                               [default: 50]
+    --strict-dates             Type date/datetime columns with a format.
+    --dates-whitelist <list>   The case-insensitive patterns to look for when
+                               shortlisting fields for date inference.
+                               [default: date,time,due,open,close,created]
+    --prefer-dmy               Prefer to parse dates in dmy format.
+    --infer-boolean            Infer boolean columns.
+    -j, --jobs <arg>           The number of jobs to run in parallel.
+                               When not set, the number of jobs is set to the
+                               number of CPUs detected.
+
+Common options:
+    -h, --help                 Display this message
+    -o, --output <file>        Write output to <file> instead of stdout.
+    -d, --delimiter <arg>      The field delimiter for reading CSV data.
+                               Must be a single character. (default: ,)
+"#;
+
+use std::{cmp::Ordering, fs, path::Path};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    cmd::{
+        schema,
+        stats::{DEFAULT_FALSY, DEFAULT_TRUTHY},
+        validate,
+    },
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_old:                 String,
+    arg_new:                 String,
+    flag_ignore_constraints: bool,
+    flag_rename_threshold:   f64,
+    flag_enum_threshold:     usize,
+    flag_strict_dates:       bool,
+    flag_dates_whitelist:    String,
+    flag_prefer_dmy:         bool,
+    flag_infer_boolean:      bool,
+    flag_jobs:               Option<usize>,
+    flag_output:             Option<String>,
+    flag_delimiter:          Option<Delimiter>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Added,
+    Removed,
+    Renamed,
+    Widened,
+    Narrowed,
+    Changed,
+}
+
+impl Change {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Change::Added => "added",
+            Change::Removed => "removed",
+            Change::Renamed => "renamed",
+            Change::Widened => "widened",
+            Change::Narrowed => "narrowed",
+            Change::Changed => "changed",
+        }
+    }
+
+    /// data valid for the new schema may not be valid for the old schema
+    const fn is_breaking(self) -> bool {
+        !matches!(self, Change::Added | Change::Narrowed)
+    }
+}
+
+/// a column of a schema, from its JSON Schema property
+struct Column {
+    name:     String,
+    /// the JSON types of the column, without null. None if any type is valid
+    types:    Option<Vec<String>>,
+    nullable: bool,
+    def:      Map<String, Value>,
+}
+
+impl Column {
+    fn new(name: &str, def: &Value) -> Self {
+        let (types, nullable) = match def.get("type") {
+            Some(Value::String(json_type)) => (vec![json_type.as_str()], json_type == "null"),
+            Some(Value::Array(json_types)) => {
+                let json_types: Vec<&str> = json_types.iter().filter_map(Value::as_str).collect();
+                let nullable = json_types.contains(&"null");
+                (json_types, nullable)
+            }
+            _ => (Vec::new(), true),
+        };
+        let types = (!types.is_empty()).then(|| {
+            let mut types: Vec<String> = types
+                .into_iter()
+                .filter(|t| *t != "null")
+                .map(ToString::to_string)
+                .collect();
+            types.sort_unstable();
+            types
+        });
+        Column {
+            name: name.to_string(),
+            types,
+            nullable,
+            def: def.as_object().cloned().unwrap_or_default(),
+        }
+    }
+
+    fn type_label(&self) -> String {
+        match &self.types {
+            Some(types) if types.is_empty() => "null".to_string(),
+            Some(types) => types.join("|"),
+            None => "any".to_string(),
+        }
+    }
+
+    /// the enum constraint, without the null that marks optional columns
+    fn enum_values(&self) -> Option<Vec<Value>> {
+        let values = self.def.get("enum")?.as_array()?;
+        Some(values.iter().filter(|v| !v.is_null()).cloned().collect())
+    }
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let (old, old_inferred) = load_columns(&args, &args.arg_old)?;
+    let (new, new_inferred) = load_columns(&args, &args.arg_new)?;
+    // the enum, range & length constraints inferred from a CSV describe its data,
+    // rather than its format
+    let ignore_data_constraints = old_inferred || new_inferred;
+
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+    wtr.write_record(["field", "constraint", "old", "new", "change", "breaking"])?;
+
+    let mut breaking_count = 0_u64;
+    let mut write_change =
+        |field: &str, constraint: &str, old_val: &str, new_val: &str, change: Change| {
+            if change.is_breaking() {
+                breaking_count += 1;
+            }
+            wtr.write_record([
+                field,
+                constraint,
+                old_val,
+                new_val,
+                change.as_str(),
+                if change.is_breaking() {
+                    "true"
+                } else {
+                    "false"
+                },
+            ])
+        };
+
+    let renames = find_renames(&old, &new, args.flag_rename_threshold);
+
+    for (i, old_col) in old.iter().enumerate() {
+        let new_col = if let Some(new_col) = new.iter().find(|c| c.name == old_col.name) {
+            new_col
+        } else if let Some((_, j)) = renames.iter().find(|(r, _)| *r == i) {
+            let new_col = &new[*j];
+            write_change(
+                &new_col.name,
+                "name",
+                &old_col.name,
+                &new_col.name,
+                Change::Renamed,
+            )?;
+            new_col
+        } else {
+            write_change(&old_col.name, "name", &old_col.name, "", Change::Removed)?;
+            continue;
+        };
+
+        for (constraint, old_val, new_val, change) in compare_columns(
+            old_col,
+            new_col,
+            args.flag_ignore_constraints,
+            ignore_data_constraints,
+        ) {
+            write_change(&new_col.name, constraint, &old_val, &new_val, change)?;
+        }
+    }
+
+    for (j, new_col) in new.iter().enumerate() {
+        if !old.iter().any(|c| c.name == new_col.name) && !renames.iter().any(|(_, a)| *a == j) {
+            write_change(&new_col.name, "name", "", &new_col.name, Change::Added)?;
+        }
+    }
+    wtr.flush()?;
+
+    match breaking_count {
+        0 => Ok(()),
+        1 => fail_clierror!("1 breaking change found."),
+        n => fail_clierror!("{n} breaking changes found."),
+    }
+}
+
+/// load the columns of a JSON Schema or Table Schema file,
+/// or infer them from a CSV file, along with whether they were inferred
+fn load_columns(args: &Args, path: &str) -> CliResult<(Vec<Column>, bool)> {
+    let is_schema = Path::new(path)
+        .extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("json"));
+
+    let properties = if is_schema {
+        let schema: Value = match serde_json::from_str(&fs::read_to_string(path)?) {
+            Ok(schema) => schema,
+            Err(e) => return fail_clierror!("Cannot parse schema {path}: {e}"),
+        };
+        if validate::is_table_schema(&schema) {
+            let Some(fields) = schema.get("fields").and_then(Value::as_array) else {
+                return fail_clierror!(
+                    "{path} is a Data Package. Use the Table Schema of one of its resources \
+                     instead."
+                );
+            };
+            let primary_key = validate::field_names(schema.get("primaryKey"));
+            validate::table_schema_properties(fields, &primary_key)?.0
+        } else if let Some(Value::Object(properties)) = schema.get("properties") {
+            properties.clone()
+        } else {
+            return fail_clierror!("Schema {path} has no properties.");
+        }
+    } else {
+        let schema_args = schema::Args {
+            flag_enum_threshold:  args.flag_enum_threshold,
+            flag_strict_dates:    args.flag_strict_dates,
+            flag_pattern_columns: SelectColumns::parse("")?,
+            flag_dates_whitelist: args.flag_dates_whitelist.clone(),
+            flag_prefer_dmy:      args.flag_prefer_dmy || std::env::var("QSV_PREFER_DMY").is_ok(),
+            flag_infer_boolean:   args.flag_infer_boolean,
+            flag_truthy:          DEFAULT_TRUTHY.to_string(),
+            flag_falsy:           DEFAULT_FALSY.to_string(),
+            flag_infer_subtypes:  false,
+            flag_slack:           0.0,
+            flag_unique_hints:    false,
+            flag_format:          "jsonschema".to_string(),
+            flag_stdout:          false,
+            flag_jobs:            Some(util::njobs(args.flag_jobs)),
+            flag_no_headers:      false,
+            flag_delimiter:       args.flag_delimiter,
+            arg_input:            Some(path.to_string()),
+        };
+        let filename = Path::new(path)
+            .file_name()
+            .map_or_else(|| path.to_string(), |f| f.to_string_lossy().to_string());
+        schema::infer_schema_from_stats(&schema_args, &filename)?
+    };
+
+    let columns = properties
+        .iter()
+        .map(|(name, def)| Column::new(name, def))
+        .collect();
+    Ok((columns, !is_schema))
+}
+
+/// pair the removed columns with the added columns they were probably renamed to,
+/// returning the indices of the old & new columns
+fn find_renames(old: &[Column], new: &[Column], threshold: f64) -> Vec<(usize, usize)> {
+    let mut renames: Vec<(usize, usize)> = Vec::new();
+    for (i, old_col) in old.iter().enumerate() {
+        if new.iter().any(|c| c.name == old_col.name) {
+            continue;
+        }
+        let old_name = old_col.name.to_lowercase();
+        let mut best: Option<(usize, f64)> = None;
+        for (j, new_col) in new.iter().enumerate() {
+            if old.iter().any(|c| c.name == new_col.name)
+                || renames.iter().any(|(_, a)| *a == j)
+                || new_col.types != old_col.types
+            {
+                continue;
+            }
+            let similarity =
+                strsim::normalized_levenshtein(&old_name, &new_col.name.to_lowercase());
+            if (similarity >= threshold || i == j)
+                && best.map_or(true, |(_, best_similarity)| similarity > best_similarity)
+            {
+                best = Some((j, similarity));
+            }
+        }
+        if let Some((j, _)) = best {
+            renames.push((i, j));
+        }
+    }
+    renames
+}
+
+/// the changes between the definitions of a column in the old & new schemas
+/// The data constraints (enum, range & length) are skipped with ignore_data_constraints.
+fn compare_columns(
+    old: &Column,
+    new: &Column,
+    ignore_constraints: bool,
+    ignore_data_constraints: bool,
+) -> Vec<(&'static str, String, String, Change)> {
+    let mut changes = Vec::new();
+
+    if let Some(change) = type_change(&old.types, &new.types) {
+        changes.push(("type", old.type_label(), new.type_label(), change));
+    }
+    if old.nullable != new.nullable {
+        changes.push((
+            "nullable",
+            old.nullable.to_string(),
+            new.nullable.to_string(),
+            if new.nullable {
+                Change::Widened
+            } else {
+                Change::Narrowed
+            },
+        ));
+    }
+    if ignore_constraints {
+        return changes;
+    }
+
+    // an enum constraint restricts the values, so removing values narrows the column
+    let enum_values = if ignore_data_constraints {
+        (None, None)
+    } else {
+        (old.enum_values(), new.enum_values())
+    };
+    match enum_values {
+        (Some(old_values), None) => {
+            changes.push((
+                "enum",
+                json_list(&old_values),
+                String::new(),
+                Change::Widened,
+            ));
+        }
+        (None, Some(new_values)) => {
+            changes.push((
+                "enum",
+                String::new(),
+                json_list(&new_values),
+                Change::Narrowed,
+            ));
+        }
+        (Some(old_values), Some(new_values)) => {
+            let added: Vec<Value> = new_values
+                .iter()
+                .filter(|v| !old_values.contains(v))
+                .cloned()
+                .collect();
+            let removed: Vec<Value> = old_values
+                .iter()
+                .filter(|v| !new_values.contains(v))
+                .cloned()
+                .collect();
+            if !added.is_empty() {
+                changes.push(("enum", String::new(), json_list(&added), Change::Widened));
+            }
+            if !removed.is_empty() {
+                changes.push(("enum", json_list(&removed), String::new(), Change::Narrowed));
+            }
+        }
+        (None, None) => {}
+    }
+
+    for constraint in ["format", "dateformat", "pattern"] {
+        let (old_val, new_val) = (old.def.get(constraint), new.def.get(constraint));
+        let change = match (old_val, new_val) {
+            (Some(_), None) => Change::Widened,
+            (None, Some(_)) => Change::Narrowed,
+            (Some(o), Some(n)) if o != n => Change::Changed,
+            _ => continue,
+        };
+        changes.push((
+            constraint,
+            value_label(old_val),
+            value_label(new_val),
+            change,
+        ));
+    }
+
+    if ignore_data_constraints {
+        return changes;
+    }
+
+    // for a lower bound, a smaller value widens the column, and for an upper bound, a larger one
+    for (constraint, is_lower_bound) in [
+        ("minimum", true),
+        ("maximum", false),
+        ("minLength", true),
+        ("maxLength", false),
+    ] {
+        let (old_val, new_val) = (old.def.get(constraint), new.def.get(constraint));
+        let change = match (
+            old_val.and_then(Value::as_f64),
+            new_val.and_then(Value::as_f64),
+        ) {
+            (Some(_), None) => Change::Widened,
+            (None, Some(_)) => Change::Narrowed,
+            (Some(o), Some(n)) => match (n.partial_cmp(&o), is_lower_bound) {
+                (Some(Ordering::Less), true) | (Some(Ordering::Greater), false) => Change::Widened,
+                (Some(Ordering::Less), false) | (Some(Ordering::Greater), true) => Change::Narrowed,
+                _ => continue,
+            },
+            (None, None) => continue,
+        };
+        changes.push((
+            constraint,
+            value_label(old_val),
+            value_label(new_val),
+            change,
+        ));
+    }
+
+    changes
+}
+
+/// classify the change between the old & new types of a column,
+/// where an integer is also a valid number
+fn type_change(old: &Option<Vec<String>>, new: &Option<Vec<String>>) -> Option<Change> {
+    let covers = |types: &Option<Vec<String>>, json_type: &str| match types {
+        Some(types) => types
+            .iter()
+            .any(|t| t == json_type || (t == "number" && json_type == "integer")),
+        None => true,
+    };
+    let new_covers_old = match old {
+        Some(old_types) => old_types.iter().all(|t| covers(new, t)),
+        None => new.is_none(),
+    };
+    let old_covers_new = match new {
+        Some(new_types) => new_types.iter().all(|t| covers(old, t)),
+        None => old.is_none(),
+    };
+    match (new_covers_old, old_covers_new) {
+        (true, true) => None,
+        (true, false) => Some(Change::Widened),
+        (false, true) => Some(Change::Narrowed),
+        (false, false) => Some(Change::Changed),
+    }
+}
+
+fn value_label(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+        None => String::new(),
+    }
+}
+
+fn json_list(values: &[Value]) -> String {
+    Value::Array(values.to_vec()).to_string()
+}
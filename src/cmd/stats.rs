@@ -32,6 +32,17 @@ Each column's data type is also inferred (NULL, Integer, String, Float, Date & D
 Unlike the sniff command, stats' data type inferences are GUARANTEED, as the entire file
 is scanned, and not just sampled.
 
+With --infer-boolean, a column is inferred as Boolean if all its non-NULL values are
+found (case-insensitive) in either the --truthy or the --falsy sets. Boolean is only
+the reported type: the values are still typed as usual while scanning (e.g. 1/0 as
+Integer, yes/no as String), and the other statistics of a Boolean column are those
+of that underlying type.
+
+With --infer-subtypes, String columns are further checked for semantic subtypes
+(email, url, uuid, ipv4, ipv6, currency, percentage & postalcode), which are reported
+in an additional "subtype" column. A subtype is only reported if ALL the column's
+non-NULL values match it.
+
 Note that the Date and DateTime data types are only inferred with the --infer-dates option 
 as its an expensive operation to match a date candidate against 19 possible date formats,
 with each format, having several variants.
@@ -90,6 +101,19 @@ stats options:
                               [default: date,time,due,open,close,created]
     --prefer-dmy              Parse dates in dmy format. Otherwise, use mdy format.
                               Ignored if --infer-dates is false.
+    --infer-boolean           Infer boolean data type. A column is inferred as Boolean
+                              if all its non-NULL values are in the --truthy or
+                              --falsy sets.
+    --truthy <list>           The case-insensitive comma-separated list of values
+                              that are considered true when inferring booleans.
+                              Ignored if --infer-boolean is false.
+                              [default: true,t,yes,y,1]
+    --falsy <list>            The case-insensitive comma-separated list of values
+                              that are considered false when inferring booleans.
+                              Ignored if --infer-boolean is false.
+                              [default: false,f,no,n,0]
+    --infer-subtypes          Infer semantic subtypes for String columns and report
+                              them in a "subtype" column.
     -j, --jobs <arg>          The number of jobs to run in parallel.
                               This works only when the given CSV has an index.
                               Note that a file handle is opened for each job.
@@ -127,21 +151,24 @@ use std::{
     default::Default,
     fmt, fs, io,
     iter::repeat,
+    net::{Ipv4Addr, Ipv6Addr},
     str::{self, FromStr},
     sync::atomic::{AtomicBool, Ordering},
 };
 
 use itertools::Itertools;
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
 use qsv_dateparser::parse_with_preference;
+use regex::Regex;
 use serde::Deserialize;
 use stats::{merge_all, Commute, MinMax, OnlineStats, Unsorted};
 use threadpool::ThreadPool;
 
-use self::FieldType::{TDate, TDateTime, TFloat, TInteger, TNull, TString};
+use self::FieldType::{TBoolean, TDate, TDateTime, TFloat, TInteger, TNull, TString};
 use crate::{
     config::{Config, Delimiter},
     index::Indexed,
+    regex_once_cell,
     select::{SelectColumns, Selection},
     util, CliResult,
 };
@@ -163,16 +190,43 @@ pub struct Args {
     pub flag_infer_dates:     bool,
     pub flag_dates_whitelist: String,
     pub flag_prefer_dmy:      bool,
+    pub flag_infer_boolean:   bool,
+    pub flag_truthy:          String,
+    pub flag_falsy:           String,
+    pub flag_infer_subtypes:  bool,
     pub flag_jobs:            Option<usize>,
     pub flag_output:          Option<String>,
     pub flag_no_headers:      bool,
     pub flag_delimiter:       Option<Delimiter>,
 }
 
-static INFER_DATE_FLAGS: once_cell::sync::OnceCell<Vec<bool>> = OnceCell::new();
 static DMY_PREFERENCE: AtomicBool = AtomicBool::new(false);
 static RECORD_COUNT: once_cell::sync::OnceCell<u64> = OnceCell::new();
 
+/// the default truthy/falsy sets for boolean inferencing,
+/// for use by commands that call stats with --infer-boolean
+pub const DEFAULT_TRUTHY: &str = "true,t,yes,y,1";
+pub const DEFAULT_FALSY: &str = "false,f,no,n,0";
+
+/// the case variants of a comma-separated list of truthy/falsy values,
+/// as Table Schema true/falseValues are case-sensitive
+pub fn boolean_values(values: &str) -> Vec<String> {
+    let mut variants = Vec::new();
+    for value in values.split(',') {
+        let mut chars = value.chars();
+        let capitalized = chars
+            .next()
+            .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+            .unwrap_or_default();
+        for variant in [value.to_string(), capitalized, value.to_ascii_uppercase()] {
+            if !variants.contains(&variant) {
+                variants.push(variant);
+            }
+        }
+    }
+    variants
+}
+
 // number of milliseconds per day
 const MS_IN_DAY: f64 = 86_400_000.0;
 // number of decimal places when rounding days
@@ -230,14 +284,16 @@ impl Args {
         let mut rdr = self.rconfig().reader()?;
         let (headers, sel) = self.sel_headers(&mut rdr)?;
 
-        init_date_inference(
+        let infer_date_flags = init_date_inference(
             self.flag_infer_dates,
             self.flag_prefer_dmy,
             &headers,
             whitelist,
         )?;
+        let boolean_sets =
+            init_boolean_inference(self.flag_infer_boolean, &self.flag_truthy, &self.flag_falsy)?;
 
-        let stats = self.compute(&sel, rdr.byte_records());
+        let stats = self.compute(&sel, rdr.byte_records(), &infer_date_flags, &boolean_sets);
         Ok((headers, stats))
     }
 
@@ -255,12 +311,14 @@ impl Args {
         let mut rdr = self.rconfig().reader()?;
         let (headers, sel) = self.sel_headers(&mut rdr)?;
 
-        init_date_inference(
+        let infer_date_flags = init_date_inference(
             self.flag_infer_dates,
             self.flag_prefer_dmy,
             &headers,
             whitelist,
         )?;
+        let boolean_sets =
+            init_boolean_inference(self.flag_infer_boolean, &self.flag_truthy, &self.flag_falsy)?;
 
         let chunk_size = util::chunk_size(idx.count() as usize, util::njobs(self.flag_jobs));
         let nchunks = util::num_of_chunks(idx.count() as usize, chunk_size);
@@ -268,7 +326,13 @@ impl Args {
         let pool = ThreadPool::new(util::njobs(self.flag_jobs));
         let (send, recv) = channel::bounded(0);
         for i in 0..nchunks {
-            let (send, args, sel) = (send.clone(), self.clone(), sel.clone());
+            let (send, args, sel, infer_date_flags, boolean_sets) = (
+                send.clone(),
+                self.clone(),
+                sel.clone(),
+                infer_date_flags.clone(),
+                boolean_sets.clone(),
+            );
             pool.execute(move || unsafe {
                 let mut idx = args
                     .rconfig()
@@ -277,7 +341,8 @@ impl Args {
                     .unwrap_unchecked();
                 idx.seek((i * chunk_size) as u64).unwrap_unchecked();
                 let it = idx.byte_records().take(chunk_size);
-                send.send(args.compute(&sel, it)).unwrap_unchecked();
+                send.send(args.compute(&sel, it, &infer_date_flags, &boolean_sets))
+                    .unwrap_unchecked();
             });
         }
         drop(send);
@@ -304,11 +369,17 @@ impl Args {
     }
 
     #[inline]
-    fn compute<I>(&self, sel: &Selection, it: I) -> Vec<Stats>
+    fn compute<I>(
+        &self,
+        sel: &Selection,
+        it: I,
+        infer_date_flags: &[bool],
+        boolean_sets: &Option<BooleanSets>,
+    ) -> Vec<Stats>
     where
         I: Iterator<Item = csv::Result<csv::ByteRecord>>,
     {
-        let mut stats = self.new_stats(sel.len());
+        let mut stats = self.new_stats(sel.len(), boolean_sets);
 
         // amortize allocation
         #[allow(unused_assignments)]
@@ -320,7 +391,7 @@ impl Args {
                     // we use unchecked here so we skip unnecessary bounds checking
                     stats
                         .get_unchecked_mut(i)
-                        .add(field, *INFER_DATE_FLAGS.get_unchecked().get_unchecked(i));
+                        .add(field, *infer_date_flags.get_unchecked(i));
                 }
             });
         });
@@ -344,7 +415,7 @@ impl Args {
     }
 
     #[inline]
-    fn new_stats(&self, record_len: usize) -> Vec<Stats> {
+    fn new_stats(&self, record_len: usize, boolean_sets: &Option<BooleanSets>) -> Vec<Stats> {
         let mut stats: Vec<Stats> = Vec::with_capacity(record_len);
         stats.extend(
             repeat(Stats::new(WhichStats {
@@ -358,6 +429,8 @@ impl Args {
                 quartiles:     self.flag_everything || self.flag_quartiles,
                 mode:          self.flag_everything || self.flag_mode,
                 typesonly:     self.flag_typesonly,
+                boolean:       boolean_sets.clone(),
+                subtypes:      self.flag_infer_subtypes,
             }))
             .take(record_len),
         );
@@ -366,14 +439,19 @@ impl Args {
 
     pub fn stat_headers(&self) -> csv::StringRecord {
         if self.flag_typesonly {
+            if self.flag_infer_subtypes {
+                return csv::StringRecord::from(vec!["field", "type", "subtype"]);
+            }
             return csv::StringRecord::from(vec!["field", "type"]);
         }
 
-        // with --everything, we have 30 columns at most
-        let mut fields = Vec::with_capacity(30);
+        // with --everything, we have 31 columns at most
+        let mut fields = Vec::with_capacity(31);
+        fields.extend_from_slice(&["field", "type"]);
+        if self.flag_infer_subtypes {
+            fields.push("subtype");
+        }
         fields.extend_from_slice(&[
-            "field",
-            "type",
             "sum",
             "min",
             "max",
@@ -427,7 +505,7 @@ fn init_date_inference(
     prefer_dmy: bool,
     headers: &csv::ByteRecord,
     flag_whitelist: &str,
-) -> Result<(), String> {
+) -> Result<Vec<bool>, String> {
     if infer_dates {
         let dmy_preferred = prefer_dmy || std::env::var("QSV_PREFER_DMY").is_ok();
         DMY_PREFERENCE.store(dmy_preferred, Ordering::Relaxed);
@@ -437,9 +515,7 @@ fn init_date_inference(
 
         if whitelist_lower == "all" {
             log::info!("inferring dates for ALL fields with DMY preference: {dmy_preferred}");
-            if let Err(e) = INFER_DATE_FLAGS.set(vec![true; headers.len()]) {
-                return fail_format!("Cannot init date inference flags for ALL fields: {e:?}");
-            };
+            Ok(vec![true; headers.len()])
         } else {
             let whitelist = whitelist_lower
                 .split(',')
@@ -461,17 +537,69 @@ fn init_date_inference(
                 }
                 infer_date_flags.push(date_found);
             }
-            if let Err(e) = INFER_DATE_FLAGS.set(infer_date_flags) {
-                return fail_format!("Cannot init date inference flags: {e:?}");
-            };
+            Ok(infer_date_flags)
         }
-    // we're not inferring dates, set INFER_DATE_FLAGS to all false
-    } else if let Err(e) = INFER_DATE_FLAGS.set(vec![false; headers.len()]) {
-        return fail_format!("Cannot init empty date inference flags: {e:?}");
+    } else {
+        // we're not inferring dates, set all the date inference flags to false
+        Ok(vec![false; headers.len()])
     }
-    Ok(())
 }
 
+#[inline]
+fn init_boolean_inference(
+    infer_boolean: bool,
+    truthy: &str,
+    falsy: &str,
+) -> Result<Option<BooleanSets>, String> {
+    if !infer_boolean {
+        return Ok(None);
+    }
+    let to_set = |list: &str| {
+        list.split(',')
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect_vec()
+    };
+    let (truthy_set, falsy_set) = (to_set(truthy), to_set(falsy));
+    if let Some(common) = truthy_set.iter().find(|t| falsy_set.contains(t)) {
+        return fail_format!("\"{common}\" cannot be both a truthy and a falsy value.");
+    }
+    log::info!("inferring booleans with truthy: {truthy_set:?} falsy: {falsy_set:?}");
+    Ok(Some(BooleanSets {
+        truthy: truthy_set,
+        falsy:  falsy_set,
+    }))
+}
+
+/// the lowercased truthy/falsy values of boolean inference
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct BooleanSets {
+    truthy: Vec<String>,
+    falsy:  Vec<String>,
+}
+
+impl BooleanSets {
+    /// check if a sample is one of the truthy/falsy values, case-insensitive
+    #[inline]
+    fn contains(&self, sample: &[u8]) -> bool {
+        // we skip utf8 validation since we say we only work with utf8
+        let lower = unsafe { str::from_utf8_unchecked(sample) }.to_lowercase();
+        self.truthy.contains(&lower) || self.falsy.contains(&lower)
+    }
+}
+
+/// a currency amount, with a currency symbol or ISO 4217 code before or after it,
+/// an optional sign, thousands separators & up to 4 decimals,
+/// e.g. $1,234.56, -€10, USD 100 or 42.50 EUR.
+/// The currency subtype of stats and the currency format of `validate` both use it,
+/// so that the inferred formats validate the data they were inferred from.
+pub static CURRENCY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?:(?:[-+]?(?:\p{Sc}|[A-Z]{3}) ?|(?:\p{Sc}|[A-Z]{3}) ?[-+])(?:\d{1,3}(?:,\d{3})+|\d+)(?:\.\d{1,4})?|[-+]?(?:\d{1,3}(?:,\d{3})+|\d+)(?:\.\d{1,4})? ?(?:\p{Sc}|[A-Z]{3}))$",
+    )
+    .unwrap()
+});
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct WhichStats {
     include_nulls: bool,
@@ -484,6 +612,8 @@ struct WhichStats {
     quartiles:     bool,
     mode:          bool,
     typesonly:     bool,
+    boolean:       Option<BooleanSets>,
+    subtypes:      bool,
 }
 
 impl Commute for WhichStats {
@@ -504,10 +634,14 @@ pub struct Stats {
     median:    Option<Unsorted<f64>>,
     mad:       Option<Unsorted<f64>>,
     quartiles: Option<Unsorted<f64>>,
+    // still true if all the non-null samples so far are truthy/falsy values
+    boolean:   bool,
+    // bitmask of the semantic subtypes all the non-null samples so far match
+    subtypes:  u8,
     which:     WhichStats,
 }
 
-fn timestamp_ms_to_rfc3339(timestamp: i64, typ: FieldType) -> String {
+pub fn timestamp_ms_to_rfc3339(timestamp: i64, typ: FieldType) -> String {
     use chrono::prelude::*;
 
     let date_val = DateTime::<Utc>::from_utc(
@@ -558,15 +692,57 @@ impl Stats {
             median,
             mad,
             quartiles,
+            boolean: which.boolean.is_some(),
+            subtypes: if which.subtypes { SubType::ALL } else { 0 },
             which,
         }
     }
 
+    /// the type reported by stats. This is the inferred type, unless
+    /// boolean inference is on and all the non-null samples are truthy/falsy.
+    #[inline]
+    fn reported_type(&self) -> FieldType {
+        if self.boolean && self.typ != TNull {
+            TBoolean
+        } else {
+            self.typ
+        }
+    }
+
+    /// the type inferred from the samples, regardless of boolean inference
+    #[inline]
+    pub fn sample_type(&self) -> FieldType {
+        self.typ
+    }
+
+    /// the semantic subtype of a String column, if any
+    #[inline]
+    fn reported_subtype(&self) -> Option<SubType> {
+        if self.typ == TString {
+            SubType::first(self.subtypes)
+        } else {
+            None
+        }
+    }
+
     #[inline]
     fn add(&mut self, sample: &[u8], infer_dates: bool) {
         let (sample_type, timestamp_val) = FieldType::from_sample(infer_dates, sample, self.typ);
         self.typ.merge(sample_type);
 
+        if sample_type != TNull {
+            if self.boolean {
+                self.boolean = self
+                    .which
+                    .boolean
+                    .as_ref()
+                    .map_or(false, |sets| sets.contains(sample));
+            }
+            if self.subtypes != 0 {
+                self.subtypes = SubType::matches(self.subtypes, sample);
+            }
+        }
+
         // we're inferring typesonly, don't add samples to compute statistics
         if self.which.typesonly {
             return;
@@ -649,8 +825,9 @@ impl Stats {
                     }
                 }
             }
-            // do nothing for String type
-            TString => {}
+            // do nothing for String type. Boolean is only a reported type,
+            // and is never the type used to compute statistics
+            TString | TBoolean => {}
         }
     }
 
@@ -658,17 +835,34 @@ impl Stats {
     pub fn to_record(&mut self, round_places: u32) -> csv::StringRecord {
         // we're doing typesonly
         if self.which.typesonly {
-            return csv::StringRecord::from(vec![self.typ.to_string()]);
+            let mut pieces = vec![self.reported_type().to_string()];
+            if self.which.subtypes {
+                pieces.push(
+                    self.reported_subtype()
+                        .map(|st| st.to_string())
+                        .unwrap_or_default(),
+                );
+            }
+            return csv::StringRecord::from(pieces);
         }
 
         let typ = self.typ;
         // prealloc memory for performance
-        // we have 30 columns at most with --everything
-        let mut pieces = Vec::with_capacity(30);
+        // we have 31 columns at most with --everything
+        let mut pieces = Vec::with_capacity(31);
         let empty = String::new;
 
         // type
-        pieces.push(typ.to_string());
+        pieces.push(self.reported_type().to_string());
+
+        // subtype
+        if self.which.subtypes {
+            pieces.push(
+                self.reported_subtype()
+                    .map(|st| st.to_string())
+                    .unwrap_or_default(),
+            );
+        }
 
         // sum
         if let Some(sum) = self.sum.as_ref().and_then(|sum| sum.show(typ)) {
@@ -963,6 +1157,8 @@ impl Commute for Stats {
         self.modes.merge(other.modes);
         self.median.merge(other.median);
         self.quartiles.merge(other.quartiles);
+        self.boolean = self.boolean && other.boolean;
+        self.subtypes &= other.subtypes;
         self.which.merge(other.which);
     }
 }
@@ -980,12 +1176,17 @@ pub enum FieldType {
     TInteger,
     TDate,
     TDateTime,
+    // only reported when --infer-boolean is enabled, and all the
+    // non-null values are in the truthy/falsy sets. Never returned by
+    // from_sample, so statistics are computed on the underlying type.
+    TBoolean,
 }
 
 impl FieldType {
     // infer data type
     // infer_dates signals if date inference should be attempted
     // from a given sample & current type inference
+    // Boolean is not inferred here, see Stats::reported_type
     #[inline]
     pub fn from_sample(
         infer_dates: bool,
@@ -1059,6 +1260,7 @@ impl Commute for FieldType {
             // date data types
             (TDate, TDate) => TDate,
             (TDateTime | TDate, TDateTime) | (TDateTime, TDate) => TDateTime,
+            (TBoolean, TBoolean) => TBoolean,
             // anything else is a String
             (_, _) => TString,
         };
@@ -1074,6 +1276,7 @@ impl fmt::Display for FieldType {
             TInteger => write!(f, "Integer"),
             TDate => write!(f, "Date"),
             TDateTime => write!(f, "DateTime"),
+            TBoolean => write!(f, "Boolean"),
         }
     }
 }
@@ -1087,10 +1290,127 @@ impl fmt::Debug for FieldType {
             TInteger => write!(f, "Integer"),
             TDate => write!(f, "Date"),
             TDateTime => write!(f, "DateTime"),
+            TBoolean => write!(f, "Boolean"),
+        }
+    }
+}
+
+/// `SubType` is the semantic subtype of a String column, inferred with --infer-subtypes.
+/// Subtypes are tracked as a bitmask of the subtypes all the non-null samples match.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SubType {
+    Email,
+    Url,
+    Uuid,
+    Ipv4,
+    Ipv6,
+    Currency,
+    Percentage,
+    PostalCode,
+}
+
+impl SubType {
+    // in order of precedence, if the samples match more than one subtype
+    const VARIANTS: [SubType; 8] = [
+        SubType::Email,
+        SubType::Url,
+        SubType::Uuid,
+        SubType::Ipv4,
+        SubType::Ipv6,
+        SubType::Currency,
+        SubType::Percentage,
+        SubType::PostalCode,
+    ];
+    const ALL: u8 = u8::MAX;
+
+    #[inline]
+    const fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+
+    fn first(mask: u8) -> Option<SubType> {
+        SubType::VARIANTS
+            .into_iter()
+            .find(|subtype| mask & subtype.bit() != 0)
+    }
+
+    /// returns the subset of the `mask` subtypes that the sample matches
+    #[inline]
+    fn matches(mask: u8, sample: &[u8]) -> u8 {
+        // we skip utf8 validation since we say we only work with utf8
+        let string = unsafe { str::from_utf8_unchecked(sample) };
+        let mut matched = mask;
+        for subtype in SubType::VARIANTS {
+            if matched & subtype.bit() != 0 && !subtype.is_match(string) {
+                matched &= !subtype.bit();
+            }
+        }
+        matched
+    }
+
+    fn is_match(self, string: &str) -> bool {
+        match self {
+            SubType::Email => {
+                regex_once_cell!(r"^[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}$").is_match(string)
+            }
+            SubType::Url => regex_once_cell!(r"^(?i:https?|ftp)://[^\s/$.?#][^\s]*$").is_match(string),
+            SubType::Uuid => regex_once_cell!(
+                r"^(?i:[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})$"
+            )
+            .is_match(string),
+            SubType::Ipv4 => string.parse::<Ipv4Addr>().is_ok(),
+            SubType::Ipv6 => string.parse::<Ipv6Addr>().is_ok(),
+            SubType::Currency => CURRENCY_REGEX.is_match(string),
+            SubType::Percentage => regex_once_cell!(r"^[-+]?\d+(?:\.\d+)?\s?%$").is_match(string),
+            // US ZIP/ZIP+4, Canadian & UK postal codes
+            SubType::PostalCode => regex_once_cell!(
+                r"^(?:\d{5}(?:-\d{4})?|(?i:[A-Z]\d[A-Z] ?\d[A-Z]\d)|(?i:[A-Z]{1,2}\d[A-Z\d]? ?\d[A-Z]{2}))$"
+            )
+            .is_match(string),
+        }
+    }
+
+    /// the JSON Schema `format` for the subtype
+    pub const fn json_schema_format(self) -> &'static str {
+        match self {
+            SubType::Email => "email",
+            SubType::Url => "uri",
+            SubType::Uuid => "uuid",
+            SubType::Ipv4 => "ipv4",
+            SubType::Ipv6 => "ipv6",
+            SubType::Currency => "currency",
+            SubType::Percentage => "percentage",
+            SubType::PostalCode => "postal-code",
         }
     }
 }
 
+impl fmt::Display for SubType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SubType::Email => write!(f, "email"),
+            SubType::Url => write!(f, "url"),
+            SubType::Uuid => write!(f, "uuid"),
+            SubType::Ipv4 => write!(f, "ipv4"),
+            SubType::Ipv6 => write!(f, "ipv6"),
+            SubType::Currency => write!(f, "currency"),
+            SubType::Percentage => write!(f, "percentage"),
+            SubType::PostalCode => write!(f, "postalcode"),
+        }
+    }
+}
+
+impl FromStr for SubType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SubType::VARIANTS
+            .into_iter()
+            .find(|subtype| subtype.to_string() == s)
+            .ok_or_else(|| format!("Unknown subtype: {s}"))
+    }
+}
+
 /// `TypedSum` keeps a rolling sum of the data seen.
 /// It sums integers until it sees a float, at which point it sums floats.
 #[derive(Clone, Default)]
@@ -1132,7 +1452,7 @@ impl TypedSum {
 
     fn show(&self, typ: FieldType) -> Option<String> {
         match typ {
-            TNull | TString | TDate | TDateTime => None,
+            TNull | TString | TDate | TDateTime | TBoolean => None,
             TInteger => {
                 match self.integer {
                     // with saturating_add, if this is equal to i64::MAX or i64::MIN
@@ -1188,7 +1508,7 @@ impl TypedMinMax {
         // we can use unwrap_unchecked with confidence
         // below since we know the data type domains of the sample
         match typ {
-            TString | TNull => {}
+            TString | TNull | TBoolean => {}
             TFloat => {
                 let n = unsafe {
                     str::from_utf8_unchecked(sample)
@@ -1234,7 +1554,7 @@ impl TypedMinMax {
 
     fn show(&self, typ: FieldType, round_places: u32) -> Option<(String, String, String)> {
         match typ {
-            TNull => None,
+            TNull | TBoolean => None,
             TString => {
                 if let (Some(min), Some(max)) = (self.strings.min(), self.strings.max()) {
                     let min = String::from_utf8_lossy(min).to_string();
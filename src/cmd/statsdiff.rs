@@ -0,0 +1,481 @@
+static USAGE: &str = r#"
+Compare the summary statistics of two CSV files to detect data drift.
+
+Columns are aligned by name. For each column found in both files, statsdiff reports
+changes in data type, null rate, min/max, mean, stddev & cardinality. Columns
+only found in the new file are reported as "added", and columns only found in the
+old file as "removed".
+
+For low cardinality columns (see --max-categories), categorical drift is also
+measured by comparing the frequency tables of both files using the
+Population Stability Index (psi) & the Jensen-Shannon divergence (jsd, base 2).
+
+The drift report is formatted as CSV data:
+
+    field,metric,old,new,diff,drift
+
+For mean, stddev & cardinality, diff is the relative change (e.g. 0.1 is a 10% increase).
+For null_rate, diff is the absolute change. The drift column is "true" if the change
+exceeds its threshold.
+
+statsdiff exits with a non-zero exit code if any of the thresholds are exceeded,
+making it suitable for data pipeline checks.
+
+For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_statsdiff.rs.
+
+Usage:
+    qsv statsdiff [options] <old> <new>
+    qsv statsdiff --help
+
+statsdiff options:
+    --null-threshold <arg>         The maximum absolute change in null rate.
+                                   [default: 0.05]
+    --mean-threshold <arg>         The maximum relative change in mean.
+                                   [default: 0.1]
+    --stddev-threshold <arg>       The maximum relative change in stddev.
+                                   [default: 0.1]
+    --cardinality-threshold <arg>  The maximum relative change in cardinality.
+                                   [default: 0.1]
+    --psi-threshold <arg>          The maximum Population Stability Index.
+                                   [default: 0.2]
+    --jsd-threshold <arg>          The maximum Jensen-Shannon divergence.
+                                   [default: 0.1]
+    --max-categories <arg>         Columns with a cardinality of up to this value in
+                                   both files are considered categorical, and have
+                                   their psi & jsd computed. Set to 0 to disable.
+                                   [default: 100]
+    --ignore-schema-changes        Don't count type changes, and added/removed
+                                   columns as drift.
+    --infer-dates                  Infer date/datetime datatypes.
+                                   See 'qsv stats --help' for more info.
+    --dates-whitelist <list>       The case-insensitive patterns to look for when
+                                   shortlisting fields for date inferencing.
+                                   [default: date,time,due,open,close,created]
+    --round <decimal_places>       Round statistics to <decimal_places>.
+                                   [default: 4]
+    -j, --jobs <arg>               The number of jobs to run in parallel.
+                                   This works only when the given CSVs have an index.
+                                   When not set, the number of jobs is set to the
+                                   number of CPUs detected.
+
+Common options:
+    -h, --help                     Display this message
+    -o, --output <file>            Write output to <file> instead of stdout.
+    -d, --delimiter <arg>          The field delimiter for reading CSV data.
+                                   Must be a single character. (default: ,)
+"#;
+
+use ahash::AHashMap;
+use itertools::Itertools;
+use serde::Deserialize;
+use stats::Frequencies;
+
+use crate::{
+    cmd::{
+        frequency,
+        stats::{DEFAULT_FALSY, DEFAULT_TRUTHY},
+    },
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util, CliResult,
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_old:                    String,
+    arg_new:                    String,
+    flag_null_threshold:        f64,
+    flag_mean_threshold:        f64,
+    flag_stddev_threshold:      f64,
+    flag_cardinality_threshold: f64,
+    flag_psi_threshold:         f64,
+    flag_jsd_threshold:         f64,
+    flag_max_categories:        u64,
+    flag_ignore_schema_changes: bool,
+    flag_infer_dates:           bool,
+    flag_dates_whitelist:       String,
+    flag_round:                 u32,
+    flag_jobs:                  Option<usize>,
+    flag_output:                Option<String>,
+    flag_delimiter:             Option<Delimiter>,
+}
+
+// smoothing factor for categories missing in one of the frequency tables,
+// so the psi doesn't become infinite
+const PSI_EPSILON: f64 = 0.0001;
+
+/// the stats & categorical frequency tables of a CSV file
+struct FileProfile {
+    rows:        u64,
+    headers:     Vec<String>,
+    stats:       AHashMap<String, csv::StringRecord>,
+    stats_index: AHashMap<String, usize>,
+    ftables:     AHashMap<String, Frequencies<Vec<u8>>>,
+}
+
+impl FileProfile {
+    fn get(&self, field: &str, stat: &str) -> &str {
+        self.stats
+            .get(field)
+            .and_then(|record| record.get(self.stats_index[stat]))
+            .unwrap_or_default()
+    }
+
+    fn get_f64(&self, field: &str, stat: &str) -> Option<f64> {
+        self.get(field, stat).parse::<f64>().ok()
+    }
+
+    fn null_rate(&self, field: &str) -> Option<f64> {
+        if self.rows == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let rows = self.rows as f64;
+        self.get_f64(field, "nullcount")
+            .map(|nullcount| nullcount / rows)
+    }
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let mut old = profile(&args, &args.arg_old)?;
+    let mut new = profile(&args, &args.arg_new)?;
+
+    // only compute frequency tables for columns that are categorical in both files
+    let categorical_columns = old
+        .headers
+        .iter()
+        .filter(|field| {
+            new.stats.contains_key(*field)
+                && is_categorical(&old, field, args.flag_max_categories)
+                && is_categorical(&new, field, args.flag_max_categories)
+        })
+        .cloned()
+        .collect_vec();
+    old.ftables = frequency_tables(&args, &args.arg_old, &old.headers, &categorical_columns)?;
+    new.ftables = frequency_tables(&args, &args.arg_new, &new.headers, &categorical_columns)?;
+
+    let mut wtr = Config::new(&args.flag_output).writer()?;
+    wtr.write_record(["field", "metric", "old", "new", "diff", "drift"])?;
+
+    let round = |val: f64| util::round_num(val, args.flag_round);
+    let mut drift_count = 0_u64;
+    let mut write_metric = |field: &str,
+                            metric: &str,
+                            old_val: &str,
+                            new_val: &str,
+                            diff: &str,
+                            drift: bool|
+     -> CliResult<()> {
+        if drift {
+            drift_count += 1;
+        }
+        wtr.write_record([
+            field,
+            metric,
+            old_val,
+            new_val,
+            diff,
+            if drift { "true" } else { "false" },
+        ])?;
+        Ok(())
+    };
+
+    for field in &old.headers {
+        if !new.stats.contains_key(field) {
+            write_metric(
+                field,
+                "column",
+                "present",
+                "missing",
+                "removed",
+                !args.flag_ignore_schema_changes,
+            )?;
+            continue;
+        }
+
+        // type
+        let (old_type, new_type) = (old.get(field, "type"), new.get(field, "type"));
+        write_metric(
+            field,
+            "type",
+            old_type,
+            new_type,
+            if old_type == new_type { "" } else { "changed" },
+            old_type != new_type && !args.flag_ignore_schema_changes,
+        )?;
+
+        // null rate
+        let (old_rate, new_rate) = (old.null_rate(field), new.null_rate(field));
+        let rate_diff = old_rate.zip(new_rate).map(|(o, n)| n - o);
+        write_metric(
+            field,
+            "null_rate",
+            &old_rate.map(round).unwrap_or_default(),
+            &new_rate.map(round).unwrap_or_default(),
+            &rate_diff.map(round).unwrap_or_default(),
+            rate_diff.map_or(false, |diff| diff.abs() > args.flag_null_threshold),
+        )?;
+
+        // min/max are only informational, as there's no meaningful threshold
+        // for them across all the data types
+        for stat in ["min", "max"] {
+            let diff = old
+                .get_f64(field, stat)
+                .zip(new.get_f64(field, stat))
+                .map(|(o, n)| round(n - o));
+            write_metric(
+                field,
+                stat,
+                old.get(field, stat),
+                new.get(field, stat),
+                &diff.unwrap_or_default(),
+                false,
+            )?;
+        }
+
+        for (stat, threshold) in [
+            ("mean", args.flag_mean_threshold),
+            ("stddev", args.flag_stddev_threshold),
+            ("cardinality", args.flag_cardinality_threshold),
+        ] {
+            let (old_val, new_val) = (old.get(field, stat), new.get(field, stat));
+            let (diff, drift) = match (old_val.parse::<f64>(), new_val.parse::<f64>()) {
+                (Ok(o), Ok(n)) => match relative_change(o, n) {
+                    Some(change) => (round(change), change.abs() > threshold),
+                    // the relative change from zero is undefined
+                    None => (String::new(), (n - o).abs() > f64::EPSILON),
+                },
+                _ => (String::new(), false),
+            };
+            write_metric(field, stat, old_val, new_val, &diff, drift)?;
+        }
+
+        // categorical drift
+        if let (Some(old_ftable), Some(new_ftable)) =
+            (old.ftables.get(field), new.ftables.get(field))
+        {
+            if let Some((psi, jsd)) = distribution_drift(old_ftable, new_ftable) {
+                write_metric(
+                    field,
+                    "psi",
+                    "",
+                    "",
+                    &round(psi),
+                    psi > args.flag_psi_threshold,
+                )?;
+                write_metric(
+                    field,
+                    "jsd",
+                    "",
+                    "",
+                    &round(jsd),
+                    jsd > args.flag_jsd_threshold,
+                )?;
+            }
+        }
+    }
+
+    for field in new.headers.iter().filter(|h| !old.stats.contains_key(*h)) {
+        write_metric(
+            field,
+            "column",
+            "missing",
+            "present",
+            "added",
+            !args.flag_ignore_schema_changes,
+        )?;
+    }
+    wtr.flush()?;
+
+    if drift_count > 0 {
+        return fail_clierror!("{drift_count} drift threshold(s) exceeded.");
+    }
+    Ok(())
+}
+
+/// compute the stats of a CSV file, keyed by column name
+fn profile(args: &Args, path: &str) -> CliResult<FileProfile> {
+    let stats_args = crate::cmd::stats::Args {
+        arg_input:            Some(path.to_string()),
+        flag_select:          SelectColumns::parse("").unwrap(),
+        flag_everything:      false,
+        flag_typesonly:       false,
+        flag_mode:            false,
+        flag_cardinality:     true,
+        flag_median:          false,
+        flag_quartiles:       false,
+        flag_mad:             false,
+        flag_nulls:           false,
+        flag_round:           args.flag_round,
+        flag_infer_dates:     args.flag_infer_dates,
+        flag_dates_whitelist: args.flag_dates_whitelist.to_string(),
+        flag_prefer_dmy:      false,
+        flag_infer_boolean:   false,
+        flag_truthy:          DEFAULT_TRUTHY.to_string(),
+        flag_falsy:           DEFAULT_FALSY.to_string(),
+        flag_infer_subtypes:  false,
+        flag_jobs:            Some(util::njobs(args.flag_jobs)),
+        flag_output:          None,
+        flag_no_headers:      false,
+        flag_delimiter:       args.flag_delimiter,
+    };
+
+    let rconfig = stats_args.rconfig();
+    let (rows, (headers, stats)) = match rconfig.indexed()? {
+        Some(idx) => (
+            idx.count(),
+            stats_args.parallel_stats(&stats_args.flag_dates_whitelist, &idx)?,
+        ),
+        None => {
+            // we don't use util::count_rows as it caches the count of the first file
+            let mut rdr = rconfig.reader()?;
+            let mut record = csv::ByteRecord::new();
+            let mut count = 0_u64;
+            while rdr.read_byte_record(&mut record)? {
+                count += 1;
+            }
+            (
+                count,
+                stats_args.sequential_stats(&stats_args.flag_dates_whitelist)?,
+            )
+        }
+    };
+
+    let headers = headers
+        .iter()
+        .map(|h| String::from_utf8_lossy(h).to_string())
+        .collect_vec();
+    let stats = headers
+        .iter()
+        .cloned()
+        .zip(stats_args.stats_to_records(stats))
+        .collect();
+
+    let mut stats_index = AHashMap::new();
+    for (i, col) in stats_args.stat_headers().iter().enumerate() {
+        if col != "field" {
+            // need offset by 1 due to extra "field" column in headers that's not in stats records
+            stats_index.insert(col.to_owned(), i - 1);
+        }
+    }
+
+    Ok(FileProfile {
+        rows,
+        headers,
+        stats,
+        stats_index,
+        ftables: AHashMap::new(),
+    })
+}
+
+#[inline]
+fn is_categorical(profile: &FileProfile, field: &str, max_categories: u64) -> bool {
+    profile
+        .get(field, "cardinality")
+        .parse::<u64>()
+        .map_or(false, |cardinality| cardinality <= max_categories)
+}
+
+/// compute the frequency tables of the given columns, keyed by column name
+fn frequency_tables(
+    args: &Args,
+    path: &str,
+    headers: &[String],
+    columns: &[String],
+) -> CliResult<AHashMap<String, Frequencies<Vec<u8>>>> {
+    if columns.is_empty() {
+        return Ok(AHashMap::new());
+    }
+
+    // select columns by their sorted 1-based index, as the frequency tables
+    // are returned in the file's column order
+    let column_select_arg = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| columns.contains(h))
+        .map(|(i, _)| (i + 1).to_string())
+        .join(",");
+
+    let freq_args = frequency::Args {
+        arg_input:            Some(path.to_string()),
+        flag_select:          SelectColumns::parse(&column_select_arg)?,
+        flag_limit:           0,
+        flag_asc:             false,
+        flag_no_nulls:        false,
+        flag_weight:          None,
+        flag_rank:            false,
+        flag_pct:             false,
+        flag_other:           false,
+        flag_other_text:      String::new(),
+        flag_json:            false,
+        flag_approx:          false,
+        flag_approx_capacity: 0,
+        flag_jobs:            Some(util::njobs(args.flag_jobs)),
+        flag_output:          None,
+        flag_no_headers:      false,
+        flag_delimiter:       args.flag_delimiter,
+    };
+
+    let (freq_headers, ftables) = match freq_args.rconfig().indexed()? {
+        Some(ref mut idx) => freq_args.parallel_ftables(idx),
+        _ => freq_args.sequential_ftables(),
+    }?;
+
+    Ok(freq_headers
+        .iter()
+        .map(|h| String::from_utf8_lossy(h).to_string())
+        .zip(ftables)
+        .collect())
+}
+
+/// relative change from `old` to `new`. None if `old` is zero
+#[inline]
+fn relative_change(old: f64, new: f64) -> Option<f64> {
+    if old.abs() < f64::EPSILON {
+        if new.abs() < f64::EPSILON {
+            Some(0.0)
+        } else {
+            None
+        }
+    } else {
+        Some((new - old) / old.abs())
+    }
+}
+
+/// returns the Population Stability Index & the Jensen-Shannon divergence (base 2)
+/// between two frequency tables. None if either table is empty.
+fn distribution_drift(
+    old: &Frequencies<Vec<u8>>,
+    new: &Frequencies<Vec<u8>>,
+) -> Option<(f64, f64)> {
+    let old_counts: AHashMap<&Vec<u8>, u64> = old.most_frequent().into_iter().collect();
+    let new_counts: AHashMap<&Vec<u8>, u64> = new.most_frequent().into_iter().collect();
+    let old_total: u64 = old_counts.values().sum();
+    let new_total: u64 = new_counts.values().sum();
+    if old_total == 0 || new_total == 0 {
+        return None;
+    }
+
+    let mut psi = 0.0_f64;
+    let mut jsd = 0.0_f64;
+    for category in old_counts.keys().chain(new_counts.keys()).unique() {
+        #[allow(clippy::cast_precision_loss)]
+        let p = *old_counts.get(category).unwrap_or(&0) as f64 / old_total as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let q = *new_counts.get(category).unwrap_or(&0) as f64 / new_total as f64;
+
+        let (p_smoothed, q_smoothed) = (p.max(PSI_EPSILON), q.max(PSI_EPSILON));
+        psi += (q_smoothed - p_smoothed) * (q_smoothed / p_smoothed).ln();
+
+        let m = (p + q) / 2.0;
+        if p > 0.0 {
+            jsd += 0.5 * p * (p / m).log2();
+        }
+        if q > 0.0 {
+            jsd += 0.5 * q * (q / m).log2();
+        }
+    }
+    Some((psi, jsd))
+}
@@ -5,9 +5,9 @@ Smartly converts CSV to a newline-delimited JSON (JSONL/NDJSON).
 By scanning the CSV first, it "smartly" infers the appropriate JSON data type
 for each column.
 
-It will infer a column as boolean if it only has a domain of two values,
-and the first character of the values are one of the following case-insensitive
-combinations: t/f; t/null; 1/0; 1/null; y/n & y/null are treated as true/false.
+It will infer a column as boolean if it only has a domain of two values, and its
+non-empty values are one of the following case-insensitive values: true/false,
+t/f, yes/no, y/n & 1/0. Empty values in a boolean column are treated as false.
 
 For examples, see https://github.com/jqnatividad/qsv/blob/master/tests/test_tojsonl.rs.
 
@@ -27,15 +27,14 @@ Common options:
     -o, --output <file>    Write output to <file> instead of stdout.
 "#;
 
-use std::{env::temp_dir, fmt::Write, fs::File, path::Path, str::FromStr};
+use std::{env::temp_dir, fmt::Write, fs::File};
 
 use serde::Deserialize;
-use serde_json::{Map, Value};
-use strum_macros::EnumString;
+use serde_json::Value;
 use uuid::Uuid;
 
-use super::schema::infer_schema_from_stats;
 use crate::{
+    cmd::stats::{FieldType, DEFAULT_FALSY, DEFAULT_TRUTHY},
     config::{Config, Delimiter},
     util, CliError, CliResult,
 };
@@ -54,8 +53,7 @@ impl From<std::fmt::Error> for CliError {
     }
 }
 
-#[derive(PartialEq, EnumString)]
-#[strum(ascii_case_insensitive)]
+#[derive(PartialEq)]
 enum JsonlType {
     Boolean,
     String,
@@ -75,145 +73,79 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     // if using stdin, we create a stdin.csv file as stdin is not seekable and we need to
     // open the file multiple times to compile stats/unique values, etc.
-    let input_filename = if preargs.arg_input.is_none() {
+    if preargs.arg_input.is_none() {
         let mut stdin_file = File::create(stdin_fpath.clone())?;
         let stdin = std::io::stdin();
         let mut stdin_handle = stdin.lock();
         std::io::copy(&mut stdin_handle, &mut stdin_file)?;
-        args.arg_input = Some(stdin_fpath.clone());
+        args.arg_input = Some(stdin_fpath);
         is_stdin = true;
-        stdin_fpath
-    } else {
-        let filename = Path::new(args.arg_input.as_ref().unwrap())
-            .file_name()
-            .unwrap()
-            .to_string_lossy()
-            .to_string();
-        filename
-    };
-    // we're calling the schema command to infer data types and enums
-    let schema_args = crate::cmd::schema::Args {
-        // we only do three, as we're only inferring boolean based on enum
-        // i.e. we only inspect a field if its boolean if its domain
-        // is just two values. if its more than 2, that's all we need know
-        // for boolean inferencing
-        flag_enum_threshold:  3,
-        flag_strict_dates:    false,
-        flag_pattern_columns: crate::select::SelectColumns::parse("")?,
+    }
+
+    // we're calling the stats command to infer data types, booleans & cardinality
+    let stats_args = crate::cmd::stats::Args {
+        arg_input:            args.arg_input.clone(),
+        flag_select:          crate::select::SelectColumns::parse("")?,
+        flag_everything:      false,
+        flag_typesonly:       false,
+        flag_mode:            false,
+        flag_cardinality:     true,
+        flag_median:          false,
+        flag_mad:             false,
+        flag_quartiles:       false,
+        flag_round:           4,
+        flag_nulls:           false,
         // json doesn't have a date type, so don't infer dates
+        flag_infer_dates:     false,
         flag_dates_whitelist: "none".to_string(),
         flag_prefer_dmy:      false,
-        flag_stdout:          false,
+        flag_infer_boolean:   true,
+        flag_truthy:          DEFAULT_TRUTHY.to_string(),
+        flag_falsy:           DEFAULT_FALSY.to_string(),
+        flag_infer_subtypes:  false,
         flag_jobs:            Some(util::njobs(args.flag_jobs)),
+        flag_output:          None,
         flag_no_headers:      false,
         flag_delimiter:       args.flag_delimiter,
-        arg_input:            args.arg_input.clone(),
     };
-    // build schema for each field by their inferred type, min/max value/length, and unique values
-    let properties_map: Map<String, Value> =
-        match infer_schema_from_stats(&schema_args, &input_filename) {
-            Ok(map) => map,
-            Err(e) => {
-                return fail_clierror!("Failed to infer field types: {e}");
-            }
-        };
-
-    let mut rdr = if is_stdin {
-        Config::new(&Some(stdin_temp))
-            .delimiter(args.flag_delimiter)
-            .reader()?
-    } else {
-        conf.reader()?
+    let csv_stats = match stats_args.rconfig().indexed()? {
+        Some(idx) => stats_args.parallel_stats(&stats_args.flag_dates_whitelist, &idx),
+        None => stats_args.sequential_stats(&stats_args.flag_dates_whitelist),
+    };
+    let csv_stats = match csv_stats {
+        Ok((_, csv_stats)) => csv_stats,
+        Err(e) => {
+            return fail_clierror!("Failed to infer field types: {e}");
+        }
     };
 
-    // TODO: instead of abusing csv writer to write jsonl file
-    // just use a normal buffered writer
-    let mut wtr = Config::new(&args.flag_output)
-        .flexible(true)
-        .no_headers(true)
-        .quote_style(csv::QuoteStyle::Never)
-        .writer()?;
-
-    let headers = rdr.headers()?.clone();
+    // stats records don't have the "field" column of the stats headers
+    let stat_headers = stats_args.stat_headers();
+    let stat_idx = |name: &str| stat_headers.iter().position(|h| h == name).unwrap() - 1;
+    let (type_idx, cardinality_idx) = (stat_idx("type"), stat_idx("cardinality"));
 
     // create a vec lookup about inferred field data types
-    let mut field_type_vec: Vec<JsonlType> = Vec::with_capacity(headers.len());
-    for (_field_name, field_def) in properties_map.iter() {
-        let Some(field_map) = field_def.as_object() else { return fail!("Cannot create field map") };
-        let prelim_type = field_map.get("type").unwrap();
-        let field_values_enum = field_map.get("enum");
-
-        // log::debug!("prelim_type: {prelim_type} field_values_enum: {field_values_enum:?}");
-
-        // check if a field has a boolean data type
-        // by checking its enum constraint
-        if let Some(domain) = field_values_enum {
-            if let Some(vals) = domain.as_array() {
-                // if this field only has a domain of two values
-                if vals.len() == 2 {
-                    let val1 = if vals[0].is_null() {
-                        '_'
-                    } else {
-                        // check the first domain value, if its a string
-                        // get the first character of val1 lowercase
-                        if let Some(str_val) = vals[0].as_str() {
-                            first_lower_char(str_val)
-                        } else if let Some(int_val) = vals[0].as_u64() {
-                            // else, its an integer (as we only do enum constraints
-                            // for string and integers), and see if its 1 or 0
-                            match int_val {
-                                1 => '1',
-                                0 => '0',
-                                _ => '*', // its something else
-                            }
-                        } else {
-                            '*'
-                        }
-                    };
-                    // same as above, but for the 2nd domain value
-                    let val2 = if vals[1].is_null() {
-                        '_'
-                    } else if let Some(str_val) = vals[1].as_str() {
-                        first_lower_char(str_val)
-                    } else if let Some(int_val) = vals[1].as_u64() {
-                        match int_val {
-                            1 => '1',
-                            0 => '0',
-                            _ => '*',
-                        }
-                    } else {
-                        '*'
-                    };
-                    // log::debug!("val1: {val1} val2: {val2}");
-
-                    // check if the domain of two values is truthy or falsy
-                    // i.e. if first character, case-insensitive is "t", "1" or "y" - truthy
-                    // "f", "0", "n" or null - falsy
-                    // if it is, infer a boolean field
-                    if let ('t', 'f' | '_')
-                    | ('f' | '_', 't')
-                    | ('1', '0' | '_')
-                    | ('0' | '_', '1')
-                    | ('y', 'n' | '_')
-                    | ('n' | '_', 'y') = (val1, val2)
-                    {
-                        field_type_vec.push(JsonlType::Boolean);
-                        continue;
-                    }
-                }
-            }
+    let mut field_type_vec: Vec<JsonlType> = Vec::with_capacity(csv_stats.len());
+    for mut field_stats in csv_stats {
+        let sample_type = field_stats.sample_type();
+        let stats_record = field_stats.to_record(4);
+
+        // only infer a boolean field if its truthy/falsy values have a domain of
+        // two values, counting empty values, so that a column of only 1s or
+        // only "y" codes is not a boolean
+        if stats_record.get(type_idx) == Some("Boolean")
+            && stats_record.get(cardinality_idx) == Some("2")
+        {
+            field_type_vec.push(JsonlType::Boolean);
+            continue;
         }
 
-        // ok to use index access and unwrap here as we know
-        // we have at least one element in the prelim_type as_array
-        field_type_vec.push(
-            JsonlType::from_str(
-                prelim_type.as_array().unwrap()[0]
-                    .as_str()
-                    .unwrap_or("null"),
-            )
-            .unwrap_or(JsonlType::String),
-        );
+        field_type_vec.push(match sample_type {
+            FieldType::TNull => JsonlType::Null,
+            FieldType::TInteger => JsonlType::Integer,
+            FieldType::TFloat => JsonlType::Number,
+            _ => JsonlType::String,
+        });
     }
 
     // amortize allocs
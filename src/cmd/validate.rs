@@ -7,8 +7,63 @@ Example output files from `mydata.csv`. If piped from stdin, then filename is `s
 * mydata.csv.valid
 * mydata.csv.invalid
 * mydata.csv.validation-errors.tsv
-
-JSON Schema can be a local file or a URL.
+* mydata.csv.validation-summary.tsv (with --summary)
+
+The validation errors can also be reported as JSON or JSONL with --report-format,
+in mydata.csv.validation-errors.json or .jsonl, with the row number, column,
+keyword, instance value, schema path & message of each error. The summary is then
+written as JSON too, in mydata.csv.validation-summary.json.
+
+JSON Schema can be a local file or a URL. JSON Schema booleans are only true or false,
+unless the field lists its values in "x-trueValues" & "x-falseValues", as the `schema`
+command does for the boolean columns it infers.
+
+Instead of a JSON Schema, the schema can be a Frictionless Table Schema
+(https://specs.frictionlessdata.io/table-schema/), or a Data Package
+(https://specs.frictionlessdata.io/data-package/) with the Table Schemas of its
+resources. The Table Schema covers:
+  * field types & formats, with dates in ISO 8601, any dates recognized by qsv's date
+    parser with the "any" format, or a strftime pattern;
+  * the trueValues & falseValues of booleans, which default to the lowercase, capitalized
+    & uppercase variants of true,t,yes,y,1 & false,f,no,n,0;
+  * the required, unique, minimum, maximum, minLength, maxLength, pattern & enum
+    constraints;
+  * missingValues, which are validated as empty values;
+  * primaryKey, which must be unique & required;
+  * foreignKeys, to the CSV itself or to other resources of the Data Package,
+    whose paths are relative to the Data Package.
+The unique, primaryKey & foreignKeys constraints skip rows with an empty value in
+their columns.
+
+Uniqueness and referential integrity across the rows can also be checked with a
+JSON Schema, with these qsv-specific top-level extensions:
+  * "primaryKey": "<column>" or ["<column>", ...] - the key must be unique;
+  * "uniqueKeys": ["<column>", ["<column>", ...], ...] - each key must be unique;
+  * "foreignKeys": [{"fields": ..., "reference": {"path": "<lookup.csv>",
+    "fields": ...}}] - the key must be a key of the local lookup CSV, whose path is
+    relative to the schema. Without a path, the key references the CSV itself.
+The keys are kept in memory as 128-bit hashes, i.e. 16 bytes per distinct key whatever
+its width, so memory still grows with the number of distinct keys. Only a unique key
+checked with --sorted-keys uses constant memory. As the keys themselves are not kept,
+two different keys with the same hash would be reported as a duplicate, or as a valid
+foreign key. With 128-bit hashes, the odds are about 1 in 10^20 for a billion keys.
+Rows that break these constraints are invalid, with a primaryKey, uniqueKeys,
+unique or foreignKey error.
+
+Besides the standard JSON Schema keywords and formats, the schema can use these
+qsv-specific extensions:
+  * "format": "currency" - a currency amount, with a currency symbol or ISO 4217
+    code, an optional sign, thousands separators & up to 4 decimals,
+    e.g. $1,234.56, -€10, USD 100 or 42.50 EUR.
+  * "dateformat": "<pattern>" - a date matching a strftime pattern,
+    e.g. "%Y-%m-%d" or "%d/%m/%Y %H:%M". With "dateformat": true, any date
+    recognized by qsv's date parser is valid.
+  * "dynamicEnum": "<lookup.csv>" - a value of the first column of a local lookup
+    CSV, loaded when the schema is compiled. Its path is relative to the schema,
+    like the paths of foreignKeys. Use
+    "dynamicEnum": {"file": "<lookup.csv>", "column": "<name or index>"} to use
+    another column, by name or by index starting at 1.
+Empty values are not checked by dateformat & dynamicEnum.
 
 Returns exitcode 0 when the CSV file is valid, exitcode > 0 otherwise.
 If all records are valid, no output files are produced.
@@ -32,6 +87,29 @@ Validate options:
     -b, --batch <size>         The number of rows per batch to load into memory,
                                before running in parallel.
                                [default: 50000]
+    --report-format <arg>      The format of the validation error report, and of
+                               the summary: tsv, json or jsonl. [default: tsv]
+    --max-errors-per-column <n>  Only report the first <n> errors of each column.
+                               The other errors are still counted in the summary,
+                               and their rows are still invalid.
+    --summary                  Also write a summary of the validation errors,
+                               grouped by column and keyword, with their count
+                               and sample row numbers.
+    --summary-samples <n>      The maximum number of sample row numbers for each
+                               column and keyword in the summary. [default: 5]
+    --prefer-dmy               Prefer to parse dates in dmy format for
+                               "dateformat": true. Otherwise, use mdy format.
+    --unique <keys>            Check that the values of these columns are unique
+                               across all the rows, e.g. "id" or "first,last".
+                               Separate several unique keys with semicolons,
+                               e.g. "id;email". Rows with an empty value in the
+                               columns of a key are not checked.
+    --sorted-keys              The CSV is sorted on its unique key, so each row is
+                               only compared to the previous one, in constant
+                               memory. Fails if the CSV is not sorted.
+    --resource <name>          The resource of a Data Package to validate the CSV
+                               with. By default, the resource whose path has the
+                               same file name as the CSV, or the only resource.
 
 Common options:
     -h, --help                 Display this message
@@ -45,25 +123,38 @@ Common options:
 "#;
 
 use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
     env,
     fs::File,
     io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
     str,
 };
 
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, NaiveDate, NaiveDateTime, NaiveTime,
+};
 use csv::ByteRecord;
 #[cfg(any(feature = "full", feature = "lite"))]
 use indicatif::{ProgressBar, ProgressDrawTarget};
 use itertools::Itertools;
-use jsonschema::{output::BasicOutput, paths::PathChunk, JSONSchema};
+use jsonschema::{
+    output::BasicOutput,
+    paths::{JSONPointer, JsonPointerNode, PathChunk},
+    ErrorIterator, JSONSchema, Keyword, ValidationError,
+};
 use log::info;
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
+use qsv_dateparser::parse_with_preference;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, value::Number, Map, Value};
 use thousands::Separable;
 
 use crate::{
+    cmd::stats::{boolean_values, CURRENCY_REGEX, DEFAULT_FALSY, DEFAULT_TRUTHY},
     config::{Config, Delimiter, DEFAULT_WTR_BUFFER_CAPACITY},
     util, CliResult,
 };
@@ -74,18 +165,61 @@ static NULL_TYPE: once_cell::sync::OnceCell<Value> = OnceCell::new();
 #[derive(Deserialize)]
 #[allow(dead_code)]
 struct Args {
-    flag_fail_fast:   bool,
-    flag_valid:       Option<String>,
-    flag_invalid:     Option<String>,
-    flag_json:        bool,
-    flag_pretty_json: bool,
-    flag_jobs:        Option<usize>,
-    flag_batch:       u32,
-    flag_no_headers:  bool,
-    flag_delimiter:   Option<Delimiter>,
-    flag_progressbar: bool,
-    arg_input:        Option<String>,
-    arg_json_schema:  Option<String>,
+    flag_fail_fast:             bool,
+    flag_valid:                 Option<String>,
+    flag_invalid:               Option<String>,
+    flag_json:                  bool,
+    flag_pretty_json:           bool,
+    flag_jobs:                  Option<usize>,
+    flag_batch:                 u32,
+    flag_no_headers:            bool,
+    flag_delimiter:             Option<Delimiter>,
+    flag_progressbar:           bool,
+    flag_report_format:         ReportFormat,
+    flag_max_errors_per_column: Option<u64>,
+    flag_summary:               bool,
+    flag_summary_samples:       usize,
+    flag_prefer_dmy:            bool,
+    flag_resource:              Option<String>,
+    flag_unique:                Option<String>,
+    flag_sorted_keys:           bool,
+    arg_input:                  Option<String>,
+    arg_json_schema:            Option<String>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+enum ReportFormat {
+    Tsv,
+    Json,
+    Jsonl,
+}
+
+/// a validation error of a row
+#[derive(Serialize, Debug)]
+struct RowError {
+    row_number:  u64,
+    column:      String,
+    keyword:     String,
+    instance:    Value,
+    schema_path: String,
+    message:     String,
+}
+
+#[derive(Default)]
+struct ErrorSummary {
+    count:       u64,
+    sample_rows: Vec<u64>,
+}
+
+/// the validation errors to report, at most max_errors_per_column for each column,
+/// and the summary of all the errors by column and keyword
+struct ErrorReport {
+    errors:                Vec<RowError>,
+    max_errors_per_column: Option<u64>,
+    column_counts:         HashMap<String, u64>,
+    summary:               BTreeMap<(String, String), ErrorSummary>,
+    summary_samples:       usize,
+    unreported:            u64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -248,33 +382,61 @@ Use `qsv input` to fix formatting and to transcode to utf8 if required."#
     let headers = rdr.byte_headers()?.clone();
     let headers_len = headers.len();
 
-    // parse and compile supplied JSON Schema
-    let (schema_json, schema_compiled): (Value, JSONSchema) =
-        match load_json(&args.arg_json_schema.unwrap()) {
-            Ok(s) => {
-                // parse JSON string
-                match serde_json::from_str(&s) {
-                    Ok(json) => {
-                        // compile JSON Schema
-                        match JSONSchema::options().compile(&json) {
-                            Ok(schema) => (json, schema),
-                            Err(e) => {
-                                return fail_clierror!("Cannot compile schema json. error: {e}");
-                            }
-                        }
+    let prefer_dmy = args.flag_prefer_dmy || env::var("QSV_PREFER_DMY").is_ok();
+
+    let schema_uri = args.arg_json_schema.unwrap();
+
+    // parse and compile supplied JSON Schema, or Table Schema translated into a JSON Schema
+    let mut table_schema: Option<TableSchema> = None;
+    let (schema_json, schema_compiled): (Value, JSONSchema) = match load_json(&schema_uri) {
+        Ok(s) => {
+            // parse JSON string
+            match serde_json::from_str::<Value>(&s) {
+                Ok(mut json) => {
+                    if is_table_schema(&json) {
+                        let loaded = TableSchema::load(
+                            &json,
+                            &schema_uri,
+                            args.arg_input.as_deref(),
+                            args.flag_resource.as_deref(),
+                        )?;
+                        json = loaded.json_schema.clone();
+                        table_schema = Some(loaded);
                     }
-                    Err(e) => {
-                        return fail_clierror!("Unable to parse schema json. error: {e}");
+                    // compile JSON Schema
+                    match compile_schema(&json, prefer_dmy, &schema_dir(&schema_uri)) {
+                        Ok(schema) => (json, schema),
+                        Err(e) => {
+                            return fail_clierror!("Cannot compile schema json. error: {e}");
+                        }
                     }
                 }
+                Err(e) => {
+                    return fail_clierror!("Unable to parse schema json. error: {e}");
+                }
             }
-            Err(e) => {
-                return fail_clierror!("Unable to retrieve json. error: {e}");
-            }
-        };
+        }
+        Err(e) => {
+            return fail_clierror!("Unable to retrieve json. error: {e}");
+        }
+    };
 
     // debug!("compiled schema: {:?}", &schema_compiled);
 
+    // the Table Schema values read as empty values, and the constraints across the rows
+    let (missing_values, mut constraints) = match table_schema {
+        Some(table_schema) => (table_schema.missing_values, table_schema.constraints),
+        None => (
+            Vec::new(),
+            CrossRowConstraints::from_json_schema(&schema_json, &schema_uri)?,
+        ),
+    };
+    if let Some(ref unique_keys) = args.flag_unique {
+        constraints.add_unique_keys(unique_keys)?;
+    }
+    let mut row_constraints =
+        constraints.row_constraints(&headers, args.arg_input.as_deref(), args.flag_sorted_keys)?;
+
     // how many rows read and processed as batches
     let mut row_number: u32 = 0;
     // how many invalid rows found
@@ -287,7 +449,14 @@ Use `qsv input` to fix formatting and to transcode to utf8 if required."#
     let mut batch = Vec::with_capacity(batch_size);
     let mut validation_results = Vec::with_capacity(batch_size);
     let mut valid_flags: Vec<bool> = Vec::with_capacity(batch_size);
-    let mut validation_error_messages: Vec<String> = Vec::with_capacity(50);
+    let mut error_report = ErrorReport {
+        errors:                Vec::with_capacity(50),
+        max_errors_per_column: args.flag_max_errors_per_column,
+        column_counts:         HashMap::new(),
+        summary:               BTreeMap::new(),
+        summary_samples:       args.flag_summary_samples,
+        unreported:            0,
+    };
 
     // set RAYON_NUM_THREADS
     util::njobs(args.flag_jobs);
@@ -304,12 +473,24 @@ Use `qsv input` to fix formatting and to transcode to utf8 if required."#
                 Ok(has_data) => {
                     if has_data {
                         row_number += 1;
-                        let mut buffer = itoa::Buffer::new();
-                        record.push_field(buffer.format(row_number).as_bytes());
 
                         // non-allocating trimming in place is much faster on the record level
                         // with our csv fork than doing per field std::str::trim which is allocating
                         record.trim();
+                        if !missing_values.is_empty() {
+                            record = record
+                                .iter()
+                                .map(|field| {
+                                    if missing_values.iter().any(|missing| missing == field) {
+                                        &b""[..]
+                                    } else {
+                                        field
+                                    }
+                                })
+                                .collect();
+                        }
+                        let mut buffer = itoa::Buffer::new();
+                        record.push_field(buffer.format(row_number).as_bytes());
                         batch.push(record.clone());
                     } else {
                         // nothing else to add to batch
@@ -342,15 +523,32 @@ Use `qsv input` to fix formatting and to transcode to utf8 if required."#
             })
             .collect_into_vec(&mut validation_results);
 
+        // the constraints across the rows are checked sequentially, in the order of the rows
+        if !row_constraints.is_empty() {
+            for (record, result) in batch.iter().zip(validation_results.iter_mut()) {
+                let row_number: u64 = str::from_utf8(&record[headers_len])
+                    .unwrap_or_default()
+                    .parse()
+                    .unwrap_or_default();
+                for row_constraint in &mut row_constraints {
+                    if let Some(row_error) = row_constraint.check(record, row_number)? {
+                        result.get_or_insert_with(Vec::new).push(row_error);
+                    }
+                }
+            }
+        }
+
         // write to validation error report, but keep Vec<bool> to gen valid/invalid files later
         // because Rayon collect() guaranteeds original order, can sequentially append results to
         // vector with each batch
-        for result in &validation_results {
-            if let Some(validation_error_msg) = result {
+        for result in validation_results.drain(..) {
+            if let Some(row_errors) = result {
                 invalid_count += 1;
                 valid_flags.push(false);
 
-                validation_error_messages.push(validation_error_msg.to_string());
+                for row_error in row_errors {
+                    error_report.add(row_error);
+                }
             } else {
                 valid_flags.push(true);
             }
@@ -388,7 +586,16 @@ Use `qsv input` to fix formatting and to transcode to utf8 if required."#
             .clone()
             .unwrap_or_else(|| "stdin.csv".to_string());
 
-        write_error_report(&input_path, validation_error_messages)?;
+        write_error_report(&input_path, &error_report.errors, args.flag_report_format)?;
+        if args.flag_summary {
+            write_error_summary(&input_path, &error_report.summary, args.flag_report_format)?;
+        }
+        if error_report.unreported > 0 {
+            winfo!(
+                "{} errors not reported, over --max-errors-per-column.",
+                error_report.unreported.separate_with_commas()
+            );
+        }
 
         let valid_suffix = args.flag_valid.unwrap_or_else(|| "valid".to_string());
         let invalid_suffix = args.flag_invalid.unwrap_or_else(|| "invalid".to_string());
@@ -470,23 +677,81 @@ fn split_invalid_records(
     Ok(())
 }
 
-fn write_error_report(input_path: &str, validation_error_messages: Vec<String>) -> CliResult<()> {
+impl ErrorReport {
+    fn add(&mut self, error: RowError) {
+        let summary = self
+            .summary
+            .entry((error.column.clone(), error.keyword.clone()))
+            .or_default();
+        summary.count += 1;
+        // a row can have several errors for the same column and keyword
+        if summary.sample_rows.len() < self.summary_samples
+            && summary.sample_rows.last() != Some(&error.row_number)
+        {
+            summary.sample_rows.push(error.row_number);
+        }
+
+        let column_count = self.column_counts.entry(error.column.clone()).or_default();
+        *column_count += 1;
+        if self
+            .max_errors_per_column
+            .map_or(true, |max| *column_count <= max)
+        {
+            self.errors.push(error);
+        } else {
+            self.unreported += 1;
+        }
+    }
+}
+
+fn report_writer(path: &str) -> CliResult<BufWriter<File>> {
     let wtr_capacitys = env::var("QSV_WTR_BUFFER_CAPACITY")
         .unwrap_or_else(|_| DEFAULT_WTR_BUFFER_CAPACITY.to_string());
     let wtr_buffer_size: usize = wtr_capacitys.parse().unwrap_or(DEFAULT_WTR_BUFFER_CAPACITY);
 
-    let output_file = File::create(input_path.to_owned() + ".validation-errors.tsv")?;
-
-    let mut output_writer = BufWriter::with_capacity(wtr_buffer_size, output_file);
-
-    output_writer.write_all(b"row_number\tfield\terror\n")?;
+    let output_file = File::create(path)?;
+    Ok(BufWriter::with_capacity(wtr_buffer_size, output_file))
+}
 
-    // write out error report
-    for error_msg in validation_error_messages {
-        output_writer.write_all(error_msg.as_bytes())?;
-        // since writer is buffered, it's more efficient to do additional write than append Newline
-        // to message
-        output_writer.write_all(&[b'\n'])?;
+fn write_error_report(
+    input_path: &str,
+    errors: &[RowError],
+    report_format: ReportFormat,
+) -> CliResult<()> {
+    let extension = match report_format {
+        ReportFormat::Tsv => "tsv",
+        ReportFormat::Json => "json",
+        ReportFormat::Jsonl => "jsonl",
+    };
+    let mut output_writer = report_writer(&format!("{input_path}.validation-errors.{extension}"))?;
+
+    match report_format {
+        ReportFormat::Tsv => {
+            output_writer.write_all(b"row_number\tfield\terror\n")?;
+
+            // write out error report
+            for error in errors {
+                writeln!(
+                    output_writer,
+                    "{}\t{}\t{}",
+                    error.row_number, error.column, error.message
+                )?;
+            }
+        }
+        ReportFormat::Json => {
+            output_writer.write_all(b"[")?;
+            for (i, error) in errors.iter().enumerate() {
+                output_writer.write_all(if i == 0 { &b"\n"[..] } else { b",\n" })?;
+                serde_json::to_writer(&mut output_writer, error)?;
+            }
+            output_writer.write_all(b"\n]\n")?;
+        }
+        ReportFormat::Jsonl => {
+            for error in errors {
+                serde_json::to_writer(&mut output_writer, error)?;
+                output_writer.write_all(&[b'\n'])?;
+            }
+        }
     }
 
     // flush error report; file gets closed automagically when out-of-scope
@@ -495,39 +760,75 @@ fn write_error_report(input_path: &str, validation_error_messages: Vec<String>)
     Ok(())
 }
 
-/// if given record is valid, return None, otherwise, error file entry string
+fn write_error_summary(
+    input_path: &str,
+    summary: &BTreeMap<(String, String), ErrorSummary>,
+    report_format: ReportFormat,
+) -> CliResult<()> {
+    if report_format == ReportFormat::Tsv {
+        let mut output_writer = report_writer(&format!("{input_path}.validation-summary.tsv"))?;
+        output_writer.write_all(b"column\tkeyword\tcount\tsample_rows\n")?;
+        for ((column, keyword), column_summary) in summary {
+            writeln!(
+                output_writer,
+                "{column}\t{keyword}\t{}\t{}",
+                column_summary.count,
+                column_summary.sample_rows.iter().join(",")
+            )?;
+        }
+        output_writer.flush()?;
+    } else {
+        let summary_json: Vec<Value> = summary
+            .iter()
+            .map(|((column, keyword), column_summary)| {
+                json!({
+                    "column": column,
+                    "keyword": keyword,
+                    "count": column_summary.count,
+                    "sample_rows": column_summary.sample_rows,
+                })
+            })
+            .collect();
+        let mut output_writer = report_writer(&format!("{input_path}.validation-summary.json"))?;
+        serde_json::to_writer_pretty(&mut output_writer, &summary_json)?;
+        output_writer.write_all(&[b'\n'])?;
+        output_writer.flush()?;
+    }
+    Ok(())
+}
+
+/// if given record is valid, return None, otherwise, its validation errors
 fn do_json_validation(
     headers: &ByteRecord,
     headers_len: usize,
     record: &ByteRecord,
     schema_json: &Value,
     schema_compiled: &JSONSchema,
-) -> Option<String> {
+) -> Option<Vec<RowError>> {
     // row number was added as last column. We use unsafe from_utf8_unchecked to
     // skip UTF8 validation since we know its safe as we added it earlier
     let row_number_string = unsafe { str::from_utf8_unchecked(record.get(headers_len).unwrap()) };
+    let row_number: u64 = row_number_string.parse().unwrap_or_default();
 
     // debug!("instance[{row_number}]: {instance:?}");
-    validate_json_instance(
-        &(match to_json_instance(headers, headers_len, record, schema_json) {
-            Ok(obj) => obj,
-            Err(e) => {
-                return Some(format!("{row_number_string}\t<RECORD>\t{e}"));
-            }
-        }),
-        schema_compiled,
-    )
-    .map(|validation_errors| {
-        // squash multiple errors into one long String with linebreaks
-        let combined_errors: String = validation_errors
-            .iter()
-            .map(|tuple| {
-                // validation error file format: row_number, field, error
-                format!("{row_number_string}\t{}\t{}", tuple.0, tuple.1)
-            })
-            .join("\n");
-
-        combined_errors
+    let instance = match to_json_instance(headers, headers_len, record, schema_json) {
+        Ok(obj) => obj,
+        Err(e) => {
+            return Some(vec![RowError {
+                row_number,
+                column: "<RECORD>".to_string(),
+                keyword: "type".to_string(),
+                instance: Value::Null,
+                schema_path: String::new(),
+                message: e,
+            }]);
+        }
+    };
+    validate_json_instance(&instance, schema_compiled).map(|mut validation_errors| {
+        for validation_error in &mut validation_errors {
+            validation_error.row_number = row_number;
+        }
+        validation_errors
     })
 }
 
@@ -541,8 +842,8 @@ fn to_json_instance(
 ) -> Result<Value, String> {
     // make sure schema has expected structure
     let Some(schema_properties) = schema.get("properties") else {
-                     return fail!("JSON Schema missing 'properties' object");
-                 };
+        return fail!("JSON Schema missing 'properties' object");
+    };
 
     // map holds individual CSV fields converted as serde_json::Value
     // we use with_capacity to minimize allocs
@@ -632,8 +933,15 @@ fn to_json_instance(
                 }
             }
             b'b' => {
-                // boolean
-                if let Ok(boolean) = value_string.parse::<bool>() {
+                // boolean, with the listed true/false values of Table Schema
+                // fields & of the boolean columns inferred by the schema command
+                let boolean = match field_def.as_object() {
+                    Some(def) if def.contains_key(TRUE_VALUES) => {
+                        parse_listed_boolean(&value_string, def)
+                    }
+                    _ => value_string.parse::<bool>().ok(),
+                };
+                if let Some(boolean) = boolean {
                     json_object_map.insert(header_string, Value::Bool(boolean));
                 } else {
                     return fail_format!(
@@ -653,6 +961,31 @@ fn to_json_instance(
     Ok(Value::Object(json_object_map))
 }
 
+/// the keywords listing the true/false values of a boolean field, set by the schema
+/// command and for the fields of a Table Schema. Other booleans are only true/false.
+const TRUE_VALUES: &str = "x-trueValues";
+const FALSE_VALUES: &str = "x-falseValues";
+
+/// parse a boolean from the listed true/false values of a field
+#[inline]
+fn parse_listed_boolean(value: &str, field_def: &Map<String, Value>) -> Option<bool> {
+    let contains = |keyword: &str| {
+        field_def
+            .get(keyword)
+            .and_then(Value::as_array)
+            .map_or(false, |values| {
+                values.iter().any(|v| v.as_str() == Some(value))
+            })
+    };
+    if contains(TRUE_VALUES) {
+        Some(true)
+    } else if contains(FALSE_VALUES) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests_for_csv_to_json_conversion {
 
@@ -763,33 +1096,929 @@ mod tests_for_csv_to_json_conversion {
             error
         );
     }
+
+    #[test]
+    fn test_to_json_instance_boolean_only_true_false() {
+        let _ = NULL_TYPE.get_or_init(|| Value::String("null".to_string()));
+        let csv = "A,B,C,D,E,F,G,H
+        hello,3.1415,300000000,Yes,,,,";
+
+        let mut rdr = csv::Reader::from_reader(csv.as_bytes());
+        let headers = rdr.byte_headers().unwrap().clone();
+        let mut record = rdr.byte_records().next().unwrap().unwrap();
+        record.trim();
+
+        let result = to_json_instance(&headers, headers.len(), &record, &schema_json());
+        assert_eq!(
+            result.unwrap_err(),
+            "Can't cast into Boolean. header: D, value: Yes, json type: boolean"
+        );
+    }
+
+    #[test]
+    fn test_to_json_instance_table_schema_boolean() {
+        let _ = NULL_TYPE.get_or_init(|| Value::String("null".to_string()));
+        let fields = vec![
+            json!({"name": "A", "type": "boolean"}),
+            json!({"name": "B", "type": "boolean"}),
+            json!({"name": "C", "type": "boolean", "trueValues": ["oui"], "falseValues": ["non"]}),
+        ];
+        let (properties, _) = table_schema_properties(&fields, &[]).unwrap();
+        let schema = json!({ "properties": properties });
+
+        let csv = "A,B,C
+        Yes,0,non";
+        let mut rdr = csv::Reader::from_reader(csv.as_bytes());
+        let headers = rdr.byte_headers().unwrap().clone();
+        let mut record = rdr.byte_records().next().unwrap().unwrap();
+        record.trim();
+
+        let instance = to_json_instance(&headers, headers.len(), &record, &schema)
+            .expect("can't convert csv to json instance");
+        assert_eq!(instance, json!({"A": true, "B": false, "C": false}));
+
+        // the declared true/false values replace the defaults
+        let csv = "A,B,C
+        true,false,true";
+        let mut rdr = csv::Reader::from_reader(csv.as_bytes());
+        let headers = rdr.byte_headers().unwrap().clone();
+        let mut record = rdr.byte_records().next().unwrap().unwrap();
+        record.trim();
+        assert!(to_json_instance(&headers, headers.len(), &record, &schema).is_err());
+    }
+}
+
+/// Compile the JSON Schema, with the qsv-specific currency format,
+/// and the dateformat & dynamicEnum keywords. The dynamicEnum lookups
+/// are relative to `schema_dir`, like the foreignKeys paths.
+fn compile_schema(
+    json: &Value,
+    prefer_dmy: bool,
+    schema_dir: &Path,
+) -> Result<JSONSchema, ValidationError> {
+    let schema_dir = schema_dir.to_path_buf();
+    JSONSchema::options()
+        .with_format("currency", is_currency)
+        .with_keyword("dateformat", move |_parent, value, path| {
+            dateformat_factory(value, path, prefer_dmy)
+        })
+        .with_keyword("dynamicEnum", move |_parent, value, path| {
+            dynamic_enum_factory(value, path, &schema_dir)
+        })
+        .compile(json)
+}
+
+/// the currency format, matched like the currency subtype of stats
+fn is_currency(value: &str) -> bool {
+    CURRENCY_REGEX.is_match(value)
+}
+
+fn keyword_error<'a>(
+    instance: &'a Value,
+    instance_path: &JsonPointerNode,
+    message: String,
+) -> ErrorIterator<'a> {
+    Box::new(std::iter::once(ValidationError::custom(
+        JSONPointer::default(),
+        instance_path.into(),
+        instance,
+        message,
+    )))
+}
+
+/// the dateformat keyword: a strftime pattern, or None for any date qsv-dateparser recognizes
+struct DateFormatValidator {
+    pattern:    Option<String>,
+    prefer_dmy: bool,
+}
+
+impl Keyword for DateFormatValidator {
+    fn validate<'instance>(
+        &self,
+        instance: &'instance Value,
+        instance_path: &JsonPointerNode,
+    ) -> ErrorIterator<'instance> {
+        if self.is_valid(instance) {
+            return Box::new(std::iter::empty());
+        }
+        let message = match &self.pattern {
+            Some(pattern) => format!("{instance} does not match the date format {pattern:?}"),
+            None => format!("{instance} is not a valid date"),
+        };
+        keyword_error(instance, instance_path, message)
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        let Value::String(value) = instance else {
+            return true;
+        };
+        match &self.pattern {
+            Some(pattern) => {
+                DateTime::parse_from_str(value, pattern).is_ok()
+                    || NaiveDateTime::parse_from_str(value, pattern).is_ok()
+                    || NaiveDate::parse_from_str(value, pattern).is_ok()
+                    || NaiveTime::parse_from_str(value, pattern).is_ok()
+            }
+            None => parse_with_preference(value, self.prefer_dmy).is_ok(),
+        }
+    }
+}
+
+fn dateformat_factory(
+    value: &Value,
+    path: JSONPointer,
+    prefer_dmy: bool,
+) -> Result<Box<dyn Keyword>, ValidationError> {
+    let pattern = match value {
+        Value::Bool(true) => None,
+        Value::String(pattern) if !StrftimeItems::new(pattern).any(|item| item == Item::Error) => {
+            Some(pattern.clone())
+        }
+        _ => {
+            return Err(ValidationError::custom(
+                JSONPointer::default(),
+                path,
+                value,
+                "dateformat must be a valid strftime pattern, or true for any date".to_string(),
+            ))
+        }
+    };
+    Ok(Box::new(DateFormatValidator {
+        pattern,
+        prefer_dmy,
+    }))
+}
+
+/// the dynamicEnum keyword: the values of a column of a lookup CSV
+struct DynamicEnumValidator {
+    lookup: String,
+    values: HashSet<String>,
+}
+
+impl Keyword for DynamicEnumValidator {
+    fn validate<'instance>(
+        &self,
+        instance: &'instance Value,
+        instance_path: &JsonPointerNode,
+    ) -> ErrorIterator<'instance> {
+        if self.is_valid(instance) {
+            return Box::new(std::iter::empty());
+        }
+        let message = format!("{instance} is not a value of the lookup {}", self.lookup);
+        keyword_error(instance, instance_path, message)
+    }
+
+    fn is_valid(&self, instance: &Value) -> bool {
+        match instance {
+            Value::String(value) => self.values.contains(value),
+            Value::Number(value) => self.values.contains(&value.to_string()),
+            _ => true,
+        }
+    }
+}
+
+fn dynamic_enum_factory<'a>(
+    value: &'a Value,
+    path: JSONPointer,
+    schema_dir: &Path,
+) -> Result<Box<dyn Keyword>, ValidationError<'a>> {
+    let lookup = match value {
+        Value::String(file) => load_lookup_column(file, schema_dir, None),
+        Value::Object(obj) => match obj.get("file") {
+            Some(Value::String(file)) => load_lookup_column(file, schema_dir, obj.get("column")),
+            _ => fail!("dynamicEnum object must have a file"),
+        },
+        _ => fail!("dynamicEnum must be a lookup CSV file, or an object with a file and a column"),
+    };
+    match lookup {
+        Ok(dynamic_enum) => Ok(Box::new(dynamic_enum)),
+        Err(e) => Err(ValidationError::custom(
+            JSONPointer::default(),
+            path,
+            value,
+            e,
+        )),
+    }
+}
+
+/// load the values of a column of a lookup CSV, the first one by default
+fn load_lookup_column(
+    file: &str,
+    schema_dir: &Path,
+    column: Option<&Value>,
+) -> Result<DynamicEnumValidator, String> {
+    let mut rdr = match Config::new(&Some(resolve_path(schema_dir, file))).reader() {
+        Ok(rdr) => rdr,
+        Err(e) => return fail_format!("Cannot read dynamicEnum lookup {file}: {e}"),
+    };
+    let headers = match rdr.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => return fail_format!("Cannot read dynamicEnum lookup {file}: {e}"),
+    };
+
+    let (idx, lookup) = match column {
+        None => (0, file.to_string()),
+        Some(Value::String(name)) => match headers.iter().position(|h| h == name) {
+            Some(idx) => (idx, format!("{file} column {name}")),
+            None => return fail_format!("dynamicEnum column {name} not found in {file}"),
+        },
+        Some(Value::Number(n)) => match n.as_u64() {
+            Some(i) if i >= 1 && i as usize <= headers.len() => {
+                (i as usize - 1, format!("{file} column {i}"))
+            }
+            _ => return fail_format!("dynamicEnum column {n} not found in {file}"),
+        },
+        Some(_) => {
+            return fail!("dynamicEnum column must be a column name, or an index starting at 1")
+        }
+    };
+
+    let mut values = HashSet::new();
+    for row in rdr.records() {
+        match row {
+            Ok(row) => {
+                if let Some(value) = row.get(idx) {
+                    values.insert(value.trim().to_string());
+                }
+            }
+            Err(e) => return fail_format!("Cannot read dynamicEnum lookup {file}: {e}"),
+        }
+    }
+    Ok(DynamicEnumValidator { lookup, values })
+}
+
+/// whether the schema is a Frictionless Table Schema or Data Package, rather than a JSON Schema
+pub fn is_table_schema(schema: &Value) -> bool {
+    schema.get("fields").map_or(false, Value::is_array)
+        || schema.get("resources").map_or(false, Value::is_array)
+}
+
+/// a Frictionless Table Schema, translated into a JSON Schema for the validation of each row,
+/// and into the constraints across the rows
+struct TableSchema {
+    json_schema:    Value,
+    missing_values: Vec<Vec<u8>>,
+    constraints:    CrossRowConstraints,
+}
+
+/// the unique keys and foreign keys of a schema, checked across all the rows
+#[derive(Default)]
+struct CrossRowConstraints {
+    /// the columns of the unique constraints, and their keyword
+    unique:       Vec<(Vec<String>, &'static str)>,
+    foreign_keys: Vec<ForeignKey>,
+}
+
+struct ForeignKey {
+    fields:           Vec<String>,
+    /// the path of the referenced CSV, or None for the validated CSV itself
+    reference_path:   Option<String>,
+    reference_fields: Vec<String>,
+}
+
+impl TableSchema {
+    /// load a Table Schema, or the Table Schema of a resource of a Data Package
+    fn load(
+        descriptor: &Value,
+        descriptor_uri: &str,
+        input: Option<&str>,
+        resource_name: Option<&str>,
+    ) -> Result<Self, String> {
+        // the paths of a Data Package are relative to its directory
+        let base_dir = schema_dir(descriptor_uri);
+
+        let Some(resources) = descriptor.get("resources").and_then(Value::as_array) else {
+            return Self::from_schema(descriptor, &[], &base_dir);
+        };
+        let resource = select_resource(resources, input, resource_name)?;
+        let schema = match resource.get("schema") {
+            Some(Value::String(path)) => {
+                let path = resolve_path(&base_dir, path);
+                match serde_json::from_str(&load_json(&path)?) {
+                    Ok(schema) => schema,
+                    Err(e) => return fail_format!("Unable to parse Table Schema {path}: {e}"),
+                }
+            }
+            Some(schema @ Value::Object(_)) => schema.clone(),
+            _ => return fail!("The Data Package resource has no Table Schema"),
+        };
+        Self::from_schema(&schema, resources, &base_dir)
+    }
+
+    fn from_schema(schema: &Value, resources: &[Value], base_dir: &Path) -> Result<Self, String> {
+        let Some(fields) = schema.get("fields").and_then(Value::as_array) else {
+            return fail!("Table Schema missing 'fields' array");
+        };
+
+        let primary_key = field_names(schema.get("primaryKey"));
+        let (properties, required) = table_schema_properties(fields, &primary_key)?;
+        let mut unique: Vec<(Vec<String>, &'static str)> = fields
+            .iter()
+            .filter(|field| {
+                field.get("constraints").and_then(|c| c.get("unique")) == Some(&Value::Bool(true))
+            })
+            .map(|field| (field_names(field.get("name")), "unique"))
+            .collect();
+        if !primary_key.is_empty() {
+            unique.push((primary_key, "primaryKey"));
+        }
+
+        let missing_values = match schema.get("missingValues").and_then(Value::as_array) {
+            Some(missing_values) => missing_values
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|missing| !missing.is_empty())
+                .map(|missing| missing.as_bytes().to_vec())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let foreign_keys = parse_foreign_keys(schema, resources, base_dir)?;
+
+        Ok(TableSchema {
+            json_schema: json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            }),
+            missing_values,
+            constraints: CrossRowConstraints {
+                unique,
+                foreign_keys,
+            },
+        })
+    }
+}
+
+impl CrossRowConstraints {
+    /// the primaryKey, uniqueKeys and foreignKeys extensions of a JSON Schema
+    fn from_json_schema(schema: &Value, schema_uri: &str) -> Result<Self, String> {
+        let mut unique = Vec::new();
+        let primary_key = field_names(schema.get("primaryKey"));
+        if !primary_key.is_empty() {
+            unique.push((primary_key, "primaryKey"));
+        }
+        for unique_key in schema
+            .get("uniqueKeys")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            unique.push((field_names(Some(unique_key)), "uniqueKeys"));
+        }
+        if unique.iter().any(|(names, _)| names.is_empty()) {
+            return fail!("A unique key must have at least one column");
+        }
+
+        Ok(CrossRowConstraints {
+            unique,
+            foreign_keys: parse_foreign_keys(schema, &[], &schema_dir(schema_uri))?,
+        })
+    }
+
+    /// add the unique keys of the --unique option, separated by semicolons,
+    /// each with its columns separated by commas
+    fn add_unique_keys(&mut self, unique_keys: &str) -> Result<(), String> {
+        for unique_key in unique_keys.split(';') {
+            let names: Vec<String> = unique_key
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(ToString::to_string)
+                .collect();
+            if names.is_empty() {
+                return fail!("A unique key must have at least one column");
+            }
+            self.unique.push((names, "unique"));
+        }
+        Ok(())
+    }
+
+    /// the unique and foreign key constraints across the rows. With sorted, the CSV is
+    /// sorted on the unique keys, which are checked against the previous row only
+    fn row_constraints(
+        &self,
+        headers: &ByteRecord,
+        input: Option<&str>,
+        sorted: bool,
+    ) -> Result<Vec<RowConstraint>, String> {
+        let mut row_constraints = Vec::with_capacity(self.unique.len() + self.foreign_keys.len());
+        for (fields, keyword) in &self.unique {
+            row_constraints.push(RowConstraint {
+                columns: column_indices(headers, fields)?,
+                names:   fields.join(", "),
+                keyword: *keyword,
+                kind:    if sorted {
+                    RowConstraintKind::SortedUnique(None)
+                } else {
+                    RowConstraintKind::Unique(HashSet::new())
+                },
+            });
+        }
+        for foreign_key in &self.foreign_keys {
+            let reference_path = match (&foreign_key.reference_path, input) {
+                (Some(path), _) => path.as_str(),
+                (None, Some(input)) => input,
+                (None, None) => {
+                    return fail!("A foreignKey to the CSV itself cannot be checked on stdin")
+                }
+            };
+            row_constraints.push(RowConstraint {
+                columns: column_indices(headers, &foreign_key.fields)?,
+                names:   foreign_key.fields.join(", "),
+                keyword: "foreignKey",
+                kind:    RowConstraintKind::ForeignKey {
+                    reference: format!(
+                        "{reference_path} column {}",
+                        foreign_key.reference_fields.join(", ")
+                    ),
+                    values:    load_reference_keys(reference_path, &foreign_key.reference_fields)?,
+                },
+            });
+        }
+        Ok(row_constraints)
+    }
+}
+
+/// the foreignKeys of a schema, referencing the CSV itself, a resource of the
+/// Data Package, or a local CSV by its path
+fn parse_foreign_keys(
+    schema: &Value,
+    resources: &[Value],
+    base_dir: &Path,
+) -> Result<Vec<ForeignKey>, String> {
+    let mut foreign_keys = Vec::new();
+    for foreign_key in schema
+        .get("foreignKeys")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let reference = foreign_key.get("reference");
+        let fields = field_names(foreign_key.get("fields"));
+        let reference_fields = field_names(reference.and_then(|r| r.get("fields")));
+        if fields.is_empty() || fields.len() != reference_fields.len() {
+            return fail!(
+                "The fields of a foreignKey and of its reference must have the same length"
+            );
+        }
+        let resource = reference
+            .and_then(|r| r.get("resource"))
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let reference_path = if let Some(path) = reference
+            .and_then(|r| r.get("path"))
+            .and_then(Value::as_str)
+        {
+            Some(resolve_path(base_dir, path))
+        } else if resource.is_empty() {
+            None
+        } else {
+            let path = resources
+                .iter()
+                .find(|r| r.get("name").and_then(Value::as_str) == Some(resource))
+                .and_then(|r| r.get("path"))
+                .and_then(Value::as_str);
+            match path {
+                Some(path) => Some(resolve_path(base_dir, path)),
+                None => {
+                    return fail_format!(
+                        "The foreignKey resource {resource} is not a resource of the Data Package"
+                    )
+                }
+            }
+        };
+        foreign_keys.push(ForeignKey {
+            fields,
+            reference_path,
+            reference_fields,
+        });
+    }
+    Ok(foreign_keys)
+}
+
+/// the directory of a local schema, which its paths are relative to
+fn schema_dir(schema_uri: &str) -> PathBuf {
+    if schema_uri.starts_with("http") {
+        PathBuf::new()
+    } else {
+        Path::new(schema_uri)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+}
+
+/// the resource to validate the CSV with: the named resource, the resource with the
+/// same file name as the CSV, or the only resource
+fn select_resource<'a>(
+    resources: &'a [Value],
+    input: Option<&str>,
+    resource_name: Option<&str>,
+) -> Result<&'a Value, String> {
+    if let Some(name) = resource_name {
+        return match resources
+            .iter()
+            .find(|r| r.get("name").and_then(Value::as_str) == Some(name))
+        {
+            Some(resource) => Ok(resource),
+            None => fail_format!("The resource {name} is not a resource of the Data Package"),
+        };
+    }
+    if let Some(input_file) = input.and_then(|input| Path::new(input).file_name()) {
+        if let Some(resource) = resources.iter().find(|r| {
+            r.get("path")
+                .and_then(Value::as_str)
+                .and_then(|path| Path::new(path).file_name())
+                == Some(input_file)
+        }) {
+            return Ok(resource);
+        }
+    }
+    match resources {
+        [resource] => Ok(resource),
+        _ => fail!(
+            "Cannot tell which resource of the Data Package to validate the CSV with. Use \
+             --resource."
+        ),
+    }
+}
+
+fn resolve_path(base_dir: &Path, path: &str) -> String {
+    if path.starts_with("http") || Path::new(path).is_absolute() {
+        path.to_string()
+    } else {
+        base_dir.join(path).to_string_lossy().to_string()
+    }
+}
+
+/// the field names of a primaryKey or foreignKey, either a name or an array of names
+pub fn field_names(fields: Option<&Value>) -> Vec<String> {
+    match fields {
+        Some(Value::String(name)) => vec![name.clone()],
+        Some(Value::Array(names)) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// translate the fields of a Table Schema into JSON Schema properties,
+/// along with the names of the required fields
+pub fn table_schema_properties(
+    fields: &[Value],
+    primary_key: &[String],
+) -> Result<(Map<String, Value>, Vec<String>), String> {
+    let mut required = primary_key.to_vec();
+    for field in fields {
+        let Some(name) = field.get("name").and_then(Value::as_str) else {
+            return fail!("Table Schema field missing 'name'");
+        };
+        if field.get("constraints").and_then(|c| c.get("required")) == Some(&Value::Bool(true))
+            && !required.iter().any(|r| r == name)
+        {
+            required.push(name.to_string());
+        }
+    }
+
+    let mut properties = Map::with_capacity(fields.len());
+    for field in fields {
+        let name = field["name"].as_str().unwrap_or_default();
+        let is_required = required.iter().any(|r| r == name);
+        properties.insert(name.to_string(), table_field_property(field, is_required));
+    }
+    Ok((properties, required))
+}
+
+/// translate a Table Schema field into a JSON Schema property
+fn table_field_property(field: &Value, required: bool) -> Value {
+    let field_type = field
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("string");
+    let format = field
+        .get("format")
+        .and_then(Value::as_str)
+        .unwrap_or("default");
+    let json_type = match field_type {
+        "integer" | "year" => "integer",
+        "number" => "number",
+        "boolean" => "boolean",
+        _ => "string",
+    };
+
+    let mut property = Map::new();
+    property.insert(
+        "type".to_string(),
+        if required {
+            json!(json_type)
+        } else {
+            json!([json_type, "null"])
+        },
+    );
+    if json_type == "boolean" {
+        // the true/false values are case-sensitive. Without trueValues/falseValues,
+        // the case variants of the truthy/falsy values stats infers booleans from
+        // are accepted
+        for (keyword, table_keyword, defaults) in [
+            (TRUE_VALUES, "trueValues", DEFAULT_TRUTHY),
+            (FALSE_VALUES, "falseValues", DEFAULT_FALSY),
+        ] {
+            let values = match field.get(table_keyword) {
+                Some(Value::Array(values)) => values.clone(),
+                _ => boolean_values(defaults)
+                    .into_iter()
+                    .map(Value::String)
+                    .collect(),
+            };
+            property.insert(keyword.to_string(), Value::Array(values));
+        }
+    }
+    match (field_type, format) {
+        ("string", "email" | "uri" | "uuid") => {
+            property.insert("format".to_string(), json!(format));
+        }
+        ("date" | "datetime" | "time", "any") => {
+            property.insert("dateformat".to_string(), json!(true));
+        }
+        ("date" | "datetime" | "time", pattern) => {
+            let pattern = match (field_type, pattern) {
+                ("date", "default") => "%Y-%m-%d",
+                ("datetime", "default") => "%Y-%m-%dT%H:%M:%SZ",
+                ("time", "default") => "%H:%M:%S",
+                (_, pattern) => pattern,
+            };
+            property.insert("dateformat".to_string(), json!(pattern));
+        }
+        ("yearmonth", _) => {
+            property.insert("pattern".to_string(), json!(r"^\d{4}-(0[1-9]|1[0-2])$"));
+        }
+        _ => {}
+    }
+
+    if let Some(Value::Object(constraints)) = field.get("constraints") {
+        for (key, value) in constraints {
+            match key.as_str() {
+                "minLength" | "maxLength" => {
+                    property.insert(key.clone(), value.clone());
+                }
+                "minimum" | "maximum" if json_type == "integer" || json_type == "number" => {
+                    let value = table_value(value, json_type, &property);
+                    property.insert(key.clone(), value);
+                }
+                "pattern" => {
+                    // a Table Schema pattern matches the whole value
+                    if let Some(pattern) = value.as_str() {
+                        property.insert(key.clone(), json!(format!("^(?:{pattern})$")));
+                    }
+                }
+                "enum" => {
+                    if let Some(values) = value.as_array() {
+                        let mut values: Vec<Value> = values
+                            .iter()
+                            .map(|v| table_value(v, json_type, &property))
+                            .collect();
+                        if !required {
+                            values.push(Value::Null);
+                        }
+                        property.insert(key.clone(), Value::Array(values));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Value::Object(property)
+}
+
+/// a Table Schema constraint value as the JSON type its field is validated as
+fn table_value(value: &Value, json_type: &str, property: &Map<String, Value>) -> Value {
+    match (value, json_type) {
+        (Value::String(s), "integer" | "number") => {
+            if let Ok(int) = s.parse::<i64>() {
+                Value::Number(Number::from(int))
+            } else {
+                s.parse::<f64>()
+                    .ok()
+                    .and_then(Number::from_f64)
+                    .map_or_else(|| value.clone(), Value::Number)
+            }
+        }
+        (Value::String(s), "boolean") => {
+            parse_listed_boolean(s, property).map_or_else(|| value.clone(), Value::Bool)
+        }
+        (Value::Number(n), "string") => Value::String(n.to_string()),
+        _ => value.clone(),
+    }
+}
+
+fn column_indices(headers: &ByteRecord, names: &[String]) -> Result<Vec<usize>, String> {
+    names
+        .iter()
+        .map(
+            |name| match headers.iter().position(|h| h == name.as_bytes()) {
+                Some(idx) => Ok(idx),
+                None => fail_format!("The column {name} is not a column of the CSV"),
+            },
+        )
+        .collect()
+}
+
+/// the hashes of the keys of the referenced columns of a CSV
+fn load_reference_keys(path: &str, fields: &[String]) -> Result<HashSet<u128>, String> {
+    let mut rdr = match Config::new(&Some(path.to_string())).reader() {
+        Ok(rdr) => rdr,
+        Err(e) => return fail_format!("Cannot read the foreignKey reference {path}: {e}"),
+    };
+    let headers = match rdr.byte_headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => return fail_format!("Cannot read the foreignKey reference {path}: {e}"),
+    };
+    let columns = match column_indices(&headers, fields) {
+        Ok(columns) => columns,
+        Err(e) => return fail_format!("Invalid foreignKey reference {path}: {e}"),
+    };
+
+    let mut keys = HashSet::new();
+    let mut record = ByteRecord::new();
+    loop {
+        match rdr.read_byte_record(&mut record) {
+            Ok(true) => {
+                record.trim();
+                if let Some(key) = row_key(&columns, &record) {
+                    keys.insert(key_hash(&key));
+                }
+            }
+            Ok(false) => break,
+            Err(e) => return fail_format!("Cannot read the foreignKey reference {path}: {e}"),
+        }
+    }
+    Ok(keys)
+}
+
+/// the values of the columns of a row as a key, or None if any of them is empty
+fn row_key(columns: &[usize], record: &ByteRecord) -> Option<Vec<u8>> {
+    let mut key = Vec::new();
+    for (i, &column) in columns.iter().enumerate() {
+        let value = record.get(column).unwrap_or_default();
+        if value.is_empty() {
+            return None;
+        }
+        if i > 0 {
+            key.push(b'\x1f');
+        }
+        key.extend_from_slice(value);
+    }
+    Some(key)
+}
+
+/// a constraint across all the rows: the values of its columns must be unique,
+/// or must be values of the referenced columns
+struct RowConstraint {
+    columns: Vec<usize>,
+    names:   String,
+    keyword: &'static str,
+    kind:    RowConstraintKind,
+}
+
+enum RowConstraintKind {
+    /// the hashes of the keys seen so far
+    Unique(HashSet<u128>),
+    /// the key of the previous row, when the rows are sorted on the key
+    SortedUnique(Option<Vec<u8>>),
+    ForeignKey {
+        reference: String,
+        /// the hashes of the referenced keys
+        values:    HashSet<u128>,
+    },
+}
+
+// two differently seeded hashers, so the keys are kept as 128-bit hashes, 16 bytes per
+// distinct key whatever their width. A collision isn't checked against the keys, which
+// are not kept, but its probability is negligible (see USAGE)
+static KEY_HASHERS: Lazy<(ahash::RandomState, ahash::RandomState)> = Lazy::new(|| {
+    (
+        ahash::RandomState::with_seeds(
+            0x243f_6a88_85a3_08d3,
+            0x1319_8a2e_0370_7344,
+            0xa409_3822_299f_31d0,
+            0x082e_fa98_ec4e_6c89,
+        ),
+        ahash::RandomState::with_seeds(
+            0x4528_21e6_38d0_1377,
+            0xbe54_66cf_34e9_0c6c,
+            0xc0ac_29b7_c97c_50dd,
+            0x3f84_d5b5_b547_0917,
+        ),
+    )
+});
+
+fn key_hash(key: &[u8]) -> u128 {
+    let (hasher1, hasher2) = &*KEY_HASHERS;
+    (u128::from(hasher1.hash_one(key)) << 64) | u128::from(hasher2.hash_one(key))
+}
+
+impl RowConstraint {
+    /// the error of the row, if any. Fails if the rows aren't sorted on a sorted unique key.
+    fn check(&mut self, record: &ByteRecord, row_number: u64) -> Result<Option<RowError>, String> {
+        let Some(key) = row_key(&self.columns, record) else {
+            return Ok(None);
+        };
+        let instance = String::from_utf8_lossy(&key).replace('\x1f', ", ");
+        let message = match &mut self.kind {
+            RowConstraintKind::Unique(seen) => {
+                if seen.insert(key_hash(&key)) {
+                    return Ok(None);
+                }
+                duplicate_message(self.keyword, &self.names, &instance)
+            }
+            RowConstraintKind::SortedUnique(previous) => {
+                match previous
+                    .as_deref()
+                    .map(|previous| key.as_slice().cmp(previous))
+                {
+                    Some(Ordering::Equal) => {
+                        duplicate_message(self.keyword, &self.names, &instance)
+                    }
+                    Some(Ordering::Less) => {
+                        return fail_format!(
+                            "The CSV is not sorted on {}, at row {row_number}. Sort it, or \
+                             validate it without --sorted-keys.",
+                            self.names
+                        )
+                    }
+                    _ => {
+                        *previous = Some(key);
+                        return Ok(None);
+                    }
+                }
+            }
+            RowConstraintKind::ForeignKey { reference, values } => {
+                if values.contains(&key_hash(&key)) {
+                    return Ok(None);
+                }
+                format!("{instance:?} is not a value of the foreignKey reference {reference}")
+            }
+        };
+        Ok(Some(RowError {
+            row_number,
+            column: self.names.clone(),
+            keyword: self.keyword.to_string(),
+            instance: Value::String(instance),
+            schema_path: String::new(),
+            message,
+        }))
+    }
+}
+
+fn duplicate_message(keyword: &str, names: &str, instance: &str) -> String {
+    if keyword == "primaryKey" {
+        format!("{instance:?} is a duplicate of the primary key {names}")
+    } else {
+        format!("{instance:?} is a duplicate of the unique {names}")
+    }
 }
 
 /// Validate JSON instance against compiled JSON schema
-/// If invalid, returns Some(Vec<(String,String)>) holding the error messages
+/// If invalid, returns Some(Vec<RowError>) holding the errors, without their row number
 #[inline]
-fn validate_json_instance(
-    instance: &Value,
-    schema_compiled: &JSONSchema,
-) -> Option<Vec<(String, String)>> {
+fn validate_json_instance(instance: &Value, schema_compiled: &JSONSchema) -> Option<Vec<RowError>> {
     let validation_output = schema_compiled.apply(instance);
 
     // If validation output is Invalid, then grab field names and errors
     if validation_output.flag() {
         None
     } else {
-        // get validation errors as String
-        let validation_errors: Vec<(String, String)> = match validation_output.basic() {
+        // get validation errors
+        let validation_errors: Vec<RowError> = match validation_output.basic() {
             BasicOutput::Invalid(errors) => errors
                 .iter()
                 .map(|e| {
-                    if let Some(PathChunk::Property(box_str)) = e.instance_location().last() {
-                        (box_str.to_string(), e.error_description().to_string())
-                    } else {
-                        (
-                            e.instance_location().to_string(),
-                            e.error_description().to_string(),
-                        )
+                    let (column, value) =
+                        if let Some(PathChunk::Property(box_str)) = e.instance_location().last() {
+                            (
+                                box_str.to_string(),
+                                instance.get(&**box_str).cloned().unwrap_or(Value::Null),
+                            )
+                        } else {
+                            (e.instance_location().to_string(), Value::Null)
+                        };
+                    let schema_path = e.keyword_location().to_string();
+                    // the keyword is the last segment of the schema path
+                    let keyword = schema_path
+                        .rsplit('/')
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
+                    RowError {
+                        row_number: 0,
+                        column,
+                        keyword,
+                        instance: value,
+                        schema_path,
+                        message: e.error_description().to_string(),
                     }
                 })
                 .collect(),
@@ -835,9 +2064,7 @@ mod tests_for_schema_validation {
     }
 
     fn compiled_schema() -> JSONSchema {
-        JSONSchema::options()
-            .compile(&schema_json())
-            .expect("Invalid schema")
+        compile_schema(&schema_json(), false, Path::new("")).expect("Invalid schema")
     }
 
     #[test]
@@ -875,13 +2102,20 @@ mod tests_for_schema_validation {
 
         assert!(result.is_some());
 
+        let errors = result.unwrap();
         assert_eq!(
             vec![(
                 "name".to_string(),
                 "\"X\" is shorter than 2 characters".to_string()
             )],
-            result.unwrap()
+            errors
+                .iter()
+                .map(|e| (e.column.clone(), e.message.clone()))
+                .collect::<Vec<_>>()
         );
+        assert_eq!(errors[0].keyword, "minLength");
+        assert_eq!(errors[0].schema_path, "/properties/name/minLength");
+        assert_eq!(errors[0].instance, Value::String("X".to_string()));
     }
 }
 
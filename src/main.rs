@@ -86,8 +86,11 @@ fn main() -> QsvExitCode {
 
     enabled_commands.push_str(
         "    behead      Drop header from CSV file
+    cast        Cast CSV values to their schema types
     cat         Concatenate by row or column
+    correlate   Compute pairwise correlations & covariances of numeric columns
     count       Count records
+    crosstab    Compute a contingency table of two sets of columns
     dedup       Remove redundant rows
     diff        Create the difference between two CSVs
     enum        Add a new column enumerating CSV lines
@@ -116,11 +119,15 @@ fn main() -> QsvExitCode {
 
     enabled_commands.push_str("    frequency   Show frequency tables\n");
 
+    #[cfg(all(feature = "from", not(feature = "lite")))]
+    enabled_commands.push_str("    from        Convert Parquet/Arrow IPC files to CSV\n");
+
     #[cfg(all(feature = "generate", not(feature = "lite")))]
     enabled_commands.push_str("    generate    Generate test data by profiling a CSV\n");
 
     enabled_commands.push_str(
         "    headers     Show header names
+    histogram   Compute histograms of numeric & date columns
     help        Show this usage message
     index       Create CSV index for faster access
     input       Read CSVs w/ special quoting, skipping, trimming & transcoding rules
@@ -133,6 +140,7 @@ fn main() -> QsvExitCode {
 
     enabled_commands.push_str(
         "    partition   Partition CSV data based on a column value
+    patch       Apply the changes of a diff to a CSV
     pseudo      Pseudonymise the values of a column\n",
     );
 
@@ -146,6 +154,7 @@ fn main() -> QsvExitCode {
     safenames   Modify a CSV's header names to db-safe names
     sample      Randomly sample CSV data
     schema      Generate JSON Schema from CSV data
+    schemadiff  Compare two schemas and flag breaking changes
     search      Search CSV data with a regex
     searchset   Search CSV data with a regex set
     select      Select, re-order, duplicate or drop columns
@@ -155,6 +164,7 @@ fn main() -> QsvExitCode {
     sortcheck   Check if a CSV is sorted
     split       Split CSV data into many files
     stats       Infer data types and compute summary statistics
+    statsdiff   Compare the summary statistics of two CSVs to detect data drift
     table       Align CSV data into columns
     tojsonl     Convert CSV to newline-delimited JSON\n",
     );
@@ -258,8 +268,11 @@ enum Command {
     #[cfg(all(feature = "apply", not(feature = "lite")))]
     Apply,
     Behead,
+    Cast,
     Cat,
+    Correlate,
     Count,
+    Crosstab,
     Dedup,
     Diff,
     Enum,
@@ -278,9 +291,12 @@ enum Command {
     #[cfg(all(feature = "foreach", target_family = "unix", not(feature = "lite")))]
     ForEach,
     Frequency,
+    #[cfg(all(feature = "from", not(feature = "lite")))]
+    From,
     #[cfg(all(feature = "generate", not(feature = "lite")))]
     Generate,
     Headers,
+    Histogram,
     Help,
     Index,
     Input,
@@ -289,6 +305,7 @@ enum Command {
     #[cfg(all(feature = "luau", not(feature = "lite")))]
     Luau,
     Partition,
+    Patch,
     Pseudo,
     #[cfg(all(feature = "python", not(feature = "lite")))]
     Py,
@@ -298,6 +315,7 @@ enum Command {
     Safenames,
     Sample,
     Schema,
+    SchemaDiff,
     Search,
     SearchSet,
     Select,
@@ -307,6 +325,7 @@ enum Command {
     SortCheck,
     Split,
     Stats,
+    StatsDiff,
     Table,
     Transpose,
     #[cfg(all(feature = "to", not(feature = "lite")))]
@@ -331,8 +350,11 @@ impl Command {
             Command::Behead => cmd::behead::run(argv),
             #[cfg(all(feature = "apply", not(feature = "lite")))]
             Command::Apply => cmd::apply::run(argv),
+            Command::Cast => cmd::cast::run(argv),
             Command::Cat => cmd::cat::run(argv),
+            Command::Correlate => cmd::correlate::run(argv),
             Command::Count => cmd::count::run(argv),
+            Command::Crosstab => cmd::crosstab::run(argv),
             Command::Dedup => cmd::dedup::run(argv),
             Command::Diff => cmd::diff::run(argv),
             Command::Enum => cmd::enumerate::run(argv),
@@ -351,9 +373,12 @@ impl Command {
             Command::Flatten => cmd::flatten::run(argv),
             Command::Fmt => cmd::fmt::run(argv),
             Command::Frequency => cmd::frequency::run(argv),
+            #[cfg(all(feature = "from", not(feature = "lite")))]
+            Command::From => cmd::from::run(argv),
             #[cfg(all(feature = "generate", not(feature = "lite")))]
             Command::Generate => cmd::generate::run(argv),
             Command::Headers => cmd::headers::run(argv),
+            Command::Histogram => cmd::histogram::run(argv),
             Command::Help => {
                 wout!("{USAGE}");
                 _ = util::qsv_check_for_update(true, false);
@@ -366,6 +391,7 @@ impl Command {
             #[cfg(all(feature = "luau", not(feature = "lite")))]
             Command::Luau => cmd::luau::run(argv),
             Command::Partition => cmd::partition::run(argv),
+            Command::Patch => cmd::patch::run(argv),
             Command::Pseudo => cmd::pseudo::run(argv),
             #[cfg(all(feature = "python", not(feature = "lite")))]
             Command::Py => cmd::python::run(argv),
@@ -375,6 +401,7 @@ impl Command {
             Command::Safenames => cmd::safenames::run(argv),
             Command::Sample => cmd::sample::run(argv),
             Command::Schema => cmd::schema::run(argv),
+            Command::SchemaDiff => cmd::schemadiff::run(argv),
             Command::Search => cmd::search::run(argv),
             Command::SearchSet => cmd::searchset::run(argv),
             Command::Select => cmd::select::run(argv),
@@ -384,6 +411,7 @@ impl Command {
             Command::SortCheck => cmd::sortcheck::run(argv),
             Command::Split => cmd::split::run(argv),
             Command::Stats => cmd::stats::run(argv),
+            Command::StatsDiff => cmd::statsdiff::run(argv),
             Command::Table => cmd::table::run(argv),
             Command::Transpose => cmd::transpose::run(argv),
             #[cfg(all(feature = "to", not(feature = "lite")))]
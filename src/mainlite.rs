@@ -14,8 +14,11 @@ macro_rules! command_list {
     () => {
         "
     behead      Drop header from CSV file
+    cast        Cast CSV values to their schema types
     cat         Concatenate by row or column
+    correlate   Compute pairwise correlations & covariances of numeric columns
     count       Count records
+    crosstab    Compute a contingency table of two sets of columns
     dedup       Remove redundant rows
     enum        Add a new column enumerating CSV lines
     excel       Exports an Excel sheet to a CSV
@@ -28,6 +31,7 @@ macro_rules! command_list {
     fmt         Format CSV output (change field delimiter)
     frequency   Show frequency tables
     headers     Show header names
+    histogram   Compute histograms of numeric & date columns
     help        Show this usage message
     index       Create CSV index for faster access
     input       Read CSVs w/ special quoting, skipping, trimming & transcoding rules
@@ -41,6 +45,7 @@ macro_rules! command_list {
     safenames   Modify a CSV's header names to db-safe names
     sample      Randomly sample CSV data
     schema      Generate JSON Schema from CSV data
+    schemadiff  Compare two schemas and flag breaking changes
     search      Search CSV data with a regex
     searchset   Search CSV data with a regex set
     select      Select, re-order, duplicate or drop columns
@@ -50,6 +55,7 @@ macro_rules! command_list {
     sortcheck   Check if a CSV is sorted
     split       Split CSV data into many files
     stats       Infer data types and compute summary statistics
+    statsdiff   Compare the summary statistics of two CSVs to detect data drift
     table       Align CSV data into columns
     tojsonl     Convert CSV to newline-delimited JSON
     transpose   Transpose rows/columns of CSV data
@@ -175,8 +181,11 @@ Please choose one of the following commands:",
 #[serde(rename_all = "lowercase")]
 enum Command {
     Behead,
+    Cast,
     Cat,
+    Correlate,
     Count,
+    Crosstab,
     Dedup,
     Enum,
     Excel,
@@ -189,6 +198,7 @@ enum Command {
     Fmt,
     Frequency,
     Headers,
+    Histogram,
     Help,
     Index,
     Input,
@@ -202,6 +212,7 @@ enum Command {
     Safenames,
     Sample,
     Schema,
+    SchemaDiff,
     Search,
     SearchSet,
     Select,
@@ -211,6 +222,7 @@ enum Command {
     SortCheck,
     Split,
     Stats,
+    StatsDiff,
     Table,
     Tojsonl,
     Transpose,
@@ -231,8 +243,11 @@ impl Command {
         }
         match self {
             Command::Behead => cmd::behead::run(argv),
+            Command::Cast => cmd::cast::run(argv),
             Command::Cat => cmd::cat::run(argv),
+            Command::Correlate => cmd::correlate::run(argv),
             Command::Count => cmd::count::run(argv),
+            Command::Crosstab => cmd::crosstab::run(argv),
             Command::Dedup => cmd::dedup::run(argv),
             Command::Enum => cmd::enumerate::run(argv),
             Command::Excel => cmd::excel::run(argv),
@@ -245,6 +260,7 @@ impl Command {
             Command::Fmt => cmd::fmt::run(argv),
             Command::Frequency => cmd::frequency::run(argv),
             Command::Headers => cmd::headers::run(argv),
+            Command::Histogram => cmd::histogram::run(argv),
             Command::Help => {
                 wout!("{USAGE}");
                 _ = util::qsv_check_for_update(true, false);
@@ -262,6 +278,7 @@ impl Command {
             Command::Safenames => cmd::safenames::run(argv),
             Command::Sample => cmd::sample::run(argv),
             Command::Schema => cmd::schema::run(argv),
+            Command::SchemaDiff => cmd::schemadiff::run(argv),
             Command::Search => cmd::search::run(argv),
             Command::SearchSet => cmd::searchset::run(argv),
             Command::Select => cmd::select::run(argv),
@@ -271,6 +288,7 @@ impl Command {
             Command::SortCheck => cmd::sortcheck::run(argv),
             Command::Split => cmd::split::run(argv),
             Command::Stats => cmd::stats::run(argv),
+            Command::StatsDiff => cmd::statsdiff::run(argv),
             Command::Table => cmd::table::run(argv),
             Command::Tojsonl => cmd::tojsonl::run(argv),
             Command::Transpose => cmd::transpose::run(argv),
@@ -86,6 +86,8 @@ pub fn version() -> String {
     enabled_features.push_str("fetch;");
     #[cfg(all(feature = "foreach", not(feature = "lite")))]
     enabled_features.push_str("foreach;");
+    #[cfg(all(feature = "from", not(feature = "lite")))]
+    enabled_features.push_str("from;");
     #[cfg(all(feature = "generate", not(feature = "lite")))]
     enabled_features.push_str("generate;");
 
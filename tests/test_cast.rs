@@ -0,0 +1,211 @@
+use crate::workdir::Workdir;
+
+const SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": {
+    "id": { "type": "integer" },
+    "amount": { "type": ["number", "null"] },
+    "active": { "type": "boolean" },
+    "created": { "type": "string", "format": "date" },
+    "updated": { "type": ["string", "null"], "format": "date-time" },
+    "note": { "type": "string" }
+  }
+}"#;
+
+fn setup(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create_from_string("schema.json", SCHEMA);
+    wrk
+}
+
+#[test]
+fn cast_json_schema() {
+    let wrk = setup("cast_json_schema");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "amount", "active", "created", "updated", "note"],
+            svec![
+                "1,234",
+                "1,234.50",
+                "Y",
+                "07/04/2023",
+                "2023-07-04 10:30:15",
+                "1,234"
+            ],
+            svec!["2", "", "no", "2023-01-15", "", ""],
+            svec!["-3", "2.5e3", "TRUE", "2023-02-01", "", "x"],
+        ],
+    );
+    let mut cmd = wrk.command("cast");
+    cmd.arg("schema.json").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "amount", "active", "created", "updated", "note"],
+        svec![
+            "1234",
+            "1234.5",
+            "true",
+            "2023-07-04",
+            "2023-07-04T10:30:15Z",
+            "1,234"
+        ],
+        svec!["2", "", "false", "2023-01-15", "", ""],
+        svec!["-3", "2500", "true", "2023-02-01", "", "x"],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn cast_decimal_separator() {
+    let wrk = setup("cast_decimal_separator");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "amount"],
+            svec!["1.234", "1.234,50"],
+            svec!["5", "0,25"],
+        ],
+    );
+    let mut cmd = wrk.command("cast");
+    cmd.args(["--decimal-separator", ","])
+        .arg("schema.json")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "amount"],
+        svec!["1234", "1234.5"],
+        svec!["5", "0.25"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn cast_rejects() {
+    let wrk = setup("cast_rejects");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "amount", "active"],
+            svec!["1", "1.5", "yes"],
+            svec!["1.5", "1.234,5", "maybe"],
+            svec!["3", "2", "n"],
+        ],
+    );
+    let mut cmd = wrk.command("cast");
+    cmd.args(["--rejects", "rejects.csv"])
+        .arg("schema.json")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "amount", "active"],
+        svec!["1", "1.5", "true"],
+        svec!["3", "2", "false"],
+    ];
+    assert_eq!(got, expected);
+
+    let got: String = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "1 row rejected.\n");
+    wrk.assert_err(&mut cmd);
+
+    let got: String = wrk.from_str(&wrk.path("rejects.csv"));
+    let expected = "id,amount,active,cast_errors\n1.5,\"1.234,5\",maybe,\"id: cannot cast \
+                    \"\"1.5\"\" to integer; amount: cannot cast \"\"1.234,5\"\" to number; \
+                    active: cannot cast \"\"maybe\"\" to boolean\"\n";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn cast_error_columns() {
+    let wrk = setup("cast_error_columns");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "other", "created"],
+            svec!["1", "a", "2023-01-15"],
+            svec!["x", "b", "not a date"],
+        ],
+    );
+    let mut cmd = wrk.command("cast");
+    cmd.arg("--error-columns").arg("schema.json").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "other", "created", "id_error", "created_error"],
+        svec!["1", "a", "2023-01-15", "", ""],
+        svec![
+            "x",
+            "b",
+            "not a date",
+            "cannot cast \"x\" to integer",
+            "cannot cast \"not a date\" to date"
+        ],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn cast_table_schema() {
+    let wrk = Workdir::new("cast_table_schema");
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+  "fields": [
+    { "name": "amount", "type": "number", "decimalChar": ",", "groupChar": "." },
+    { "name": "active", "type": "boolean", "trueValues": ["ja"], "falseValues": ["nein"] },
+    { "name": "created", "type": "date", "format": "%d.%m.%Y" }
+  ],
+  "missingValues": ["", "NA"]
+}"#,
+    );
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["amount", "active", "created"],
+            svec!["1.234,50", "ja", "04.07.2023"],
+            svec!["NA", "nein", "NA"],
+        ],
+    );
+    let mut cmd = wrk.command("cast");
+    cmd.arg("schema.json").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["amount", "active", "created"],
+        svec!["1234.5", "true", "2023-07-04"],
+        svec!["", "false", ""],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn cast_json_schema_listed_booleans() {
+    let wrk = Workdir::new("cast_json_schema_listed_booleans");
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+  "type": "object",
+  "properties": {
+    "active": { "type": "boolean", "x-trueValues": ["Y", "si"], "x-falseValues": ["N"] }
+  }
+}"#,
+    );
+    wrk.create(
+        "in.csv",
+        vec![svec!["active"], svec!["si"], svec!["N"], svec!["yes"]],
+    );
+    let mut cmd = wrk.command("cast");
+    cmd.arg("schema.json").arg("in.csv");
+
+    // only the listed values are booleans, as with validate
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["active"], svec!["true"], svec!["false"]];
+    assert_eq!(got, expected);
+    wrk.assert_err(&mut cmd);
+}
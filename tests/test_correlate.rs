@@ -0,0 +1,120 @@
+use crate::workdir::Workdir;
+
+fn data() -> Vec<Vec<String>> {
+    vec![
+        svec!["x", "y", "z", "name"],
+        svec!["1", "2", "5", "a"],
+        svec!["2", "4", "4", "b"],
+        svec!["3", "6", "3", "c"],
+        svec!["4", "8", "2", "d"],
+        svec!["5", "10", "1", "e"],
+    ]
+}
+
+#[test]
+fn correlate_long() {
+    let wrk = Workdir::new("correlate_long");
+    wrk.create("in.csv", data());
+
+    let mut cmd = wrk.command("correlate");
+    cmd.arg("in.csv");
+
+    // name is not numeric, so its not correlated
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field1", "field2", "n", "covariance", "pearson"],
+        svec!["x", "y", "5", "5", "1"],
+        svec!["x", "z", "5", "-2.5", "-1"],
+        svec!["y", "z", "5", "-5", "-1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn correlate_long_indexed() {
+    let wrk = Workdir::new("correlate_long_indexed");
+    wrk.create_indexed("in.csv", data());
+
+    let mut cmd = wrk.command("correlate");
+    cmd.args(["--jobs", "2"]).arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field1", "field2", "n", "covariance", "pearson"],
+        svec!["x", "y", "5", "5", "1"],
+        svec!["x", "z", "5", "-2.5", "-1"],
+        svec!["y", "z", "5", "-5", "-1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn correlate_matrix() {
+    let wrk = Workdir::new("correlate_matrix");
+    wrk.create("in.csv", data());
+
+    let mut cmd = wrk.command("correlate");
+    cmd.arg("--matrix").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "x", "y", "z"],
+        svec!["x", "1", "1", "-1"],
+        svec!["y", "1", "1", "-1"],
+        svec!["z", "-1", "-1", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn correlate_spearman() {
+    let wrk = Workdir::new("correlate_spearman");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["x", "w"],
+            svec!["1", "1"],
+            svec!["2", "4"],
+            svec!["3", "9"],
+            svec!["4", "16"],
+            svec!["5", "100"],
+        ],
+    );
+
+    let mut cmd = wrk.command("correlate");
+    cmd.args(["--matrix", "--stat", "spearman"]).arg("in.csv");
+
+    // the relationship is monotonic, but not linear
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "x", "w"],
+        svec!["x", "1", "1"],
+        svec!["w", "1", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn correlate_pairwise_nulls() {
+    let wrk = Workdir::new("correlate_pairwise_nulls");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["x", "y"],
+            svec!["1", "2"],
+            svec!["2", "4"],
+            svec!["3", ""],
+            svec!["", "8"],
+        ],
+    );
+
+    let mut cmd = wrk.command("correlate");
+    cmd.arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field1", "field2", "n", "covariance", "pearson"],
+        svec!["x", "y", "2", "1", "1"],
+    ];
+    assert_eq!(got, expected);
+}
@@ -0,0 +1,222 @@
+use crate::workdir::Workdir;
+
+fn setup(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["region", "product", "channel"],
+            svec!["east", "widget", "web"],
+            svec!["east", "widget", "store"],
+            svec!["east", "widget", "web"],
+            svec!["east", "gadget", "web"],
+            svec!["west", "widget", "store"],
+            svec!["west", "gadget", "store"],
+            svec!["west", "gadget", "web"],
+            svec!["west", "gadget", "store"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn crosstab_wide() {
+    let wrk = setup("crosstab_wide");
+    let mut cmd = wrk.command("crosstab");
+    cmd.arg("region").arg("product").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region", "gadget", "widget"],
+        svec!["east", "1", "3"],
+        svec!["west", "3", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_margins() {
+    let wrk = setup("crosstab_margins");
+    let mut cmd = wrk.command("crosstab");
+    cmd.arg("--margins")
+        .arg("region")
+        .arg("product")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region", "gadget", "widget", "Total"],
+        svec!["east", "1", "3", "4"],
+        svec!["west", "3", "1", "4"],
+        svec!["Total", "4", "4", "8"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_long() {
+    let wrk = setup("crosstab_long");
+    let mut cmd = wrk.command("crosstab");
+    cmd.arg("--long").arg("region").arg("product").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region", "product", "count"],
+        svec!["east", "gadget", "1"],
+        svec!["east", "widget", "3"],
+        svec!["west", "gadget", "3"],
+        svec!["west", "widget", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_long_margins_normalize_all() {
+    let wrk = setup("crosstab_long_margins_normalize_all");
+    let mut cmd = wrk.command("crosstab");
+    cmd.arg("--long")
+        .arg("--margins")
+        .args(["--normalize", "all"])
+        .arg("region")
+        .arg("product")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region", "product", "percentage"],
+        svec!["east", "gadget", "12.5"],
+        svec!["east", "widget", "37.5"],
+        svec!["east", "Total", "50"],
+        svec!["west", "gadget", "37.5"],
+        svec!["west", "widget", "12.5"],
+        svec!["west", "Total", "50"],
+        svec!["Total", "gadget", "50"],
+        svec!["Total", "widget", "50"],
+        svec!["Total", "Total", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_normalize_row() {
+    let wrk = setup("crosstab_normalize_row");
+    let mut cmd = wrk.command("crosstab");
+    cmd.arg("--margins")
+        .args(["--normalize", "row"])
+        .arg("region")
+        .arg("product")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region", "gadget", "widget", "Total"],
+        svec!["east", "25", "75", "100"],
+        svec!["west", "75", "25", "100"],
+        svec!["Total", "50", "50", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_normalize_column() {
+    let wrk = setup("crosstab_normalize_column");
+    let mut cmd = wrk.command("crosstab");
+    cmd.args(["--normalize", "column"])
+        .arg("channel")
+        .arg("region")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["channel", "east", "west"],
+        svec!["store", "25", "75"],
+        svec!["web", "75", "25"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_nway() {
+    let wrk = setup("crosstab_nway");
+    let mut cmd = wrk.command("crosstab");
+    cmd.arg("region").arg("product,channel").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "region",
+            "gadget|store",
+            "gadget|web",
+            "widget|store",
+            "widget|web"
+        ],
+        svec!["east", "0", "1", "1", "2"],
+        svec!["west", "2", "1", "1", "0"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_nway_long() {
+    let wrk = setup("crosstab_nway_long");
+    let mut cmd = wrk.command("crosstab");
+    cmd.arg("--long")
+        .arg("region,product")
+        .arg("channel")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region", "product", "channel", "count"],
+        svec!["east", "gadget", "web", "1"],
+        svec!["east", "widget", "store", "1"],
+        svec!["east", "widget", "web", "2"],
+        svec!["west", "gadget", "store", "2"],
+        svec!["west", "gadget", "web", "1"],
+        svec!["west", "widget", "store", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_chi2() {
+    let wrk = setup("crosstab_chi2");
+    let mut cmd = wrk.command("crosstab");
+    cmd.arg("--chi2").arg("region").arg("product").arg("in.csv");
+
+    let got = wrk.output_stderr(&mut cmd);
+    let expected = "chi-square: 2\ndegrees of freedom: 1\np-value: 0.1573\ncramer's v: 0.5\n";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn crosstab_invalid_normalize() {
+    let wrk = setup("crosstab_invalid_normalize");
+    let mut cmd = wrk.command("crosstab");
+    cmd.args(["--normalize", "index"])
+        .arg("region")
+        .arg("product")
+        .arg("in.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn crosstab_chi2_stats() {
+    let wrk = setup("crosstab_chi2_stats");
+    let mut cmd = wrk.command("crosstab");
+    cmd.args(["--stats", "stats.csv"])
+        .arg("region")
+        .arg("product")
+        .arg("in.csv");
+    wrk.assert_success(&mut cmd);
+
+    let got: String = wrk.from_str(&wrk.path("stats.csv"));
+    let expected = "statistic,value
+chi_square,2
+degrees_of_freedom,1
+p_value,0.1573
+cramers_v,0.5
+";
+    assert_eq!(got, expected);
+}
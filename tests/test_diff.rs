@@ -24,3 +24,228 @@ fn simple_diff() {
 
     assert_eq!(got2, expected2.replace("\r\n", "\n").trim_end());
 }
+
+fn setup_changes(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "left.csv",
+        vec![
+            svec!["id", "name", "amount", "updated"],
+            svec!["1", "alice", "10", "2023-01-01"],
+            svec!["2", "bob", "20", "2023-01-01"],
+            svec!["3", "carl", "30", "2023-01-01"],
+        ],
+    );
+    wrk.create(
+        "right.csv",
+        vec![
+            svec!["id", "name", "amount", "updated"],
+            svec!["1", "alice", "10", "2023-02-01"],
+            svec!["2", "bobby", "25", "2023-02-01"],
+            svec!["4", "dave", "40", "2023-02-01"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn diff_long() {
+    let wrk = setup_changes("diff_long");
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--key", "id"])
+        .args(["--ignore-columns", "updated"])
+        .args(["--format", "long"])
+        .args(["left.csv", "right.csv"]);
+
+    let mut got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    got[1..].sort_unstable();
+    let expected = vec![
+        svec!["id", "diffresult", "column", "old_value", "new_value"],
+        svec!["2", "modified", "amount", "20", "25"],
+        svec!["2", "modified", "name", "bob", "bobby"],
+        svec!["3", "removed", "", "", ""],
+        svec!["4", "added", "", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_summary() {
+    let wrk = setup_changes("diff_summary");
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--key", "id"])
+        .args(["--format", "summary"])
+        .args(["left.csv", "right.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "column", "count"],
+        svec!["added", "", "1"],
+        svec!["removed", "", "1"],
+        svec!["modified", "", "2"],
+        svec!["modified", "name", "1"],
+        svec!["modified", "amount", "1"],
+        svec!["modified", "updated", "2"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_json() {
+    let wrk = setup_changes("diff_json");
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--key", "name"])
+        .args(["--ignore-columns", "updated"])
+        .args(["--format", "json"])
+        .args(["left.csv", "right.csv"]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    let mut records: Vec<serde_json::Value> = serde_json::from_str(&got).unwrap();
+    records.sort_by_key(|record| record["key"]["name"].as_str().unwrap().to_string());
+    let expected: Vec<serde_json::Value> = serde_json::from_str(
+        r#"[
+        {"diffresult": "removed", "key": {"name": "bob"}, "row": {"id": "2", "name": "bob", "amount": "20"}},
+        {"diffresult": "added", "key": {"name": "bobby"}, "row": {"id": "2", "name": "bobby", "amount": "25"}},
+        {"diffresult": "removed", "key": {"name": "carl"}, "row": {"id": "3", "name": "carl", "amount": "30"}},
+        {"diffresult": "added", "key": {"name": "dave"}, "row": {"id": "4", "name": "dave", "amount": "40"}}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(records, expected);
+}
+
+#[test]
+fn diff_json_no_changes() {
+    let wrk = setup_changes("diff_json_no_changes");
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--format", "json"])
+        .args(["left.csv", "left.csv"]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(got, "[]");
+}
+
+#[test]
+fn diff_key_and_primary_key_idx() {
+    let wrk = setup_changes("diff_key_and_primary_key_idx");
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--key", "id"])
+        .args(["--primary-key-idx", "0"])
+        .args(["left.csv", "right.csv"]);
+    wrk.assert_err(&mut cmd);
+}
+
+fn setup_reexport(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "left.csv",
+        vec![
+            svec!["id", "price", "name", "updated"],
+            svec!["1", "1.0", "Alice", "2023-01-31T00:00:00Z"],
+            svec!["2", "2.50", "bob", "2023-02-01T00:00:00Z"],
+            svec!["3", "3", "carl", "2023-03-01T00:00:00Z"],
+        ],
+    );
+    wrk.create(
+        "right.csv",
+        vec![
+            svec!["id", "price", "name", "updated"],
+            svec!["1", "1", "alice ", "2023-01-31T01:00:00+01:00"],
+            svec!["2", "2.504", "Bob", "2023-02-02T00:00:00Z"],
+            svec!["3", "3.1", "carl", "2023-03-01T00:00:00Z"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn diff_compare_rules() {
+    let wrk = setup_reexport("diff_compare_rules");
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--key", "id"])
+        .args(["--compare", "price:abs=0.005;name:trim+casei;updated:date"])
+        .args(["--format", "long"])
+        .args(["left.csv", "right.csv"]);
+
+    let mut got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    got[1..].sort_unstable();
+    let expected = vec![
+        svec!["id", "diffresult", "column", "old_value", "new_value"],
+        svec![
+            "2",
+            "modified",
+            "updated",
+            "2023-02-01T00:00:00Z",
+            "2023-02-02T00:00:00Z"
+        ],
+        svec!["3", "modified", "price", "3", "3.1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_compare_relative_stdin() {
+    let wrk = setup_reexport("diff_compare_relative_stdin");
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--key", "id"])
+        .args(["--ignore-columns", "name,updated"])
+        .args(["--compare", "price:rel=0.01"])
+        .args(["left.csv", "-"])
+        .stdin(std::fs::File::open(wrk.path("right.csv")).unwrap());
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "id", "price"],
+        svec!["-", "3", "3"],
+        svec!["+", "3", "3.1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_gzip() {
+    use std::io::Write;
+
+    let wrk = setup_reexport("diff_gzip");
+    let mut encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(wrk.path("right.csv.gz")).unwrap(),
+        flate2::Compression::default(),
+    );
+    encoder
+        .write_all(&std::fs::read(wrk.path("right.csv")).unwrap())
+        .unwrap();
+    encoder.finish().unwrap();
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--format", "summary"])
+        .args(["left.csv", "right.csv.gz"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "column", "count"],
+        svec!["added", "", "0"],
+        svec!["removed", "", "0"],
+        svec!["modified", "", "3"],
+        svec!["modified", "price", "3"],
+        svec!["modified", "name", "2"],
+        svec!["modified", "updated", "2"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_both_stdin() {
+    let wrk = setup_reexport("diff_both_stdin");
+    let mut cmd = wrk.command("diff");
+    cmd.arg("-");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn diff_compare_invalid() {
+    let wrk = setup_reexport("diff_compare_invalid");
+    let mut cmd = wrk.command("diff");
+    cmd.args(["--compare", "price:fuzzy"])
+        .args(["left.csv", "right.csv"]);
+    wrk.assert_err(&mut cmd);
+}
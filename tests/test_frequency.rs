@@ -125,6 +125,239 @@ fn frequency_select() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn frequency_rank_pct() {
+    let (wrk, mut cmd) = setup("frequency_rank_pct");
+    cmd.args(["--limit", "0"])
+        .args(["--select", "h2"])
+        .arg("--rank")
+        .arg("--pct");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "field",
+            "value",
+            "count",
+            "rank",
+            "percentage",
+            "cumulative_percentage"
+        ],
+        svec!["h2", "z", "3", "1", "50", "50"],
+        svec!["h2", "y", "2", "2", "33.3333", "83.3333"],
+        svec!["h2", "x", "1", "3", "16.6667", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_other() {
+    let (wrk, mut cmd) = setup("frequency_other");
+    cmd.args(["--limit", "1"]).arg("--other").arg("--pct");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "field",
+            "value",
+            "count",
+            "percentage",
+            "cumulative_percentage"
+        ],
+        svec!["h1", "a", "3", "50", "50"],
+        svec!["h1", "Other", "3", "50", "100"],
+        svec!["h2", "z", "3", "50", "50"],
+        svec!["h2", "Other", "3", "50", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_other_text() {
+    let (wrk, mut cmd) = setup("frequency_other_text");
+    cmd.args(["--limit", "2"])
+        .args(["--select", "h2"])
+        .arg("--other")
+        .args(["--other-text", "(rest)"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count"],
+        svec!["h2", "z", "3"],
+        svec!["h2", "y", "2"],
+        svec!["h2", "(rest)", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+fn weighted_rows() -> Vec<Vec<String>> {
+    vec![
+        svec!["region", "revenue"],
+        svec!["east", "10"],
+        svec!["west", "5"],
+        svec!["east", "2.5"],
+        svec!["north", "n/a"],
+        svec!["west", ""],
+    ]
+}
+
+#[test]
+fn frequency_weight() {
+    let wrk = Workdir::new("frequency_weight");
+    wrk.create("in.csv", weighted_rows());
+
+    // the weight column is excluded from the frequency tables
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--weight", "revenue"]).arg("--pct").arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "field",
+            "value",
+            "count",
+            "percentage",
+            "cumulative_percentage"
+        ],
+        svec!["region", "east", "12.5", "71.4286", "71.4286"],
+        svec!["region", "west", "5", "28.5714", "100"],
+        svec!["region", "north", "0", "0", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_weight_indexed() {
+    let wrk = Workdir::new("frequency_weight_indexed");
+    wrk.create_indexed("in.csv", weighted_rows());
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--weight", "2"])
+        .args(["--jobs", "2"])
+        .arg("--rank")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count", "rank"],
+        svec!["region", "east", "12.5", "1"],
+        svec!["region", "west", "5", "2"],
+        svec!["region", "north", "0", "3"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_weight_invalid() {
+    let wrk = Workdir::new("frequency_weight_invalid");
+    wrk.create("in.csv", weighted_rows());
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--weight", "region,revenue"]).arg("in.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn frequency_json() {
+    let (wrk, mut cmd) = setup("frequency_json");
+    cmd.args(["--limit", "2"])
+        .args(["--select", "h2"])
+        .arg("--other")
+        .arg("--json");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let got: serde_json::Value = serde_json::from_str(&got).unwrap();
+    let expected = serde_json::json!([{
+        "field": "h2",
+        "total": 6,
+        "values": [
+            {"value": "z", "count": 3, "rank": 1, "percentage": 50.0, "cumulative_percentage": 50.0},
+            {"value": "y", "count": 2, "rank": 2, "percentage": 33.3333, "cumulative_percentage": 83.3333},
+            {"value": "Other", "count": 1, "rank": null, "percentage": 16.6667, "cumulative_percentage": 100.0},
+        ]
+    }]);
+    assert_eq!(got, expected);
+}
+
+fn heavy_hitter_rows() -> Vec<Vec<String>> {
+    let mut rows = vec![vec!["v".to_string()]];
+    rows.extend((0..50).map(|_| vec!["a".to_string()]));
+    rows.extend((0..30).map(|_| vec!["b".to_string()]));
+    rows.extend((0..20).map(|i| vec![format!("c{i}")]));
+    rows
+}
+
+#[test]
+fn frequency_approx() {
+    let wrk = Workdir::new("frequency_approx");
+    wrk.create("in.csv", heavy_hitter_rows());
+
+    let mut cmd = wrk.command("frequency");
+    cmd.arg("--approx")
+        .args(["--approx-capacity", "5"])
+        .args(["--limit", "2"])
+        .arg("--other")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count", "error"],
+        svec!["v", "a", "50", "0"],
+        svec!["v", "b", "30", "0"],
+        svec!["v", "Other", "20", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_approx_exact_when_capacity_suffices() {
+    let (wrk, mut cmd) = setup("frequency_approx_exact_when_capacity_suffices");
+    cmd.arg("--approx")
+        .args(["--limit", "2"])
+        .args(["--select", "h2"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "value", "count", "error"],
+        svec!["h2", "z", "3", "0"],
+        svec!["h2", "y", "2", "0"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_approx_indexed() {
+    let wrk = Workdir::new("frequency_approx_indexed");
+    wrk.create_indexed("in.csv", heavy_hitter_rows());
+
+    let mut cmd = wrk.command("frequency");
+    cmd.arg("--approx")
+        .args(["--approx-capacity", "5"])
+        .args(["--limit", "2"])
+        .args(["--jobs", "4"])
+        .arg("in.csv");
+
+    // merging the summaries of the chunks may overestimate the counts,
+    // but the true counts are always within their error bounds
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 3);
+    for (row, (value, true_count)) in got[1..].iter().zip([("a", 50), ("b", 30)]) {
+        assert_eq!(row[1], value);
+        let count: u64 = row[2].parse().unwrap();
+        let error: u64 = row[3].parse().unwrap();
+        assert!(count - error <= true_count && true_count <= count);
+    }
+}
+
+#[test]
+fn frequency_approx_asc() {
+    let (wrk, mut cmd) = setup("frequency_approx_asc");
+    cmd.arg("--approx").arg("--asc");
+
+    wrk.assert_err(&mut cmd);
+}
+
 // This tests that a frequency table computed by `qsv` is always the same
 // as the frequency table computed in memory.
 #[test]
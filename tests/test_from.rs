@@ -0,0 +1,242 @@
+use std::{fs::File, sync::Arc};
+
+use arrow::{
+    array::{ArrayRef, Int32Array, Int64Array, StringArray, UInt32Array},
+    datatypes::{DataType, Field, Schema},
+    ipc::writer::{FileWriter, StreamWriter},
+    record_batch::RecordBatch,
+};
+use parquet::{arrow::ArrowWriter, file::properties::WriterProperties};
+
+use crate::workdir::Workdir;
+
+fn sample_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("year", DataType::Int32, false),
+        Field::new("country", DataType::Utf8, true),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5, 6])),
+        Arc::new(Int32Array::from(vec![2019, 2020, 2021, 2022, 2023, 2024])),
+        Arc::new(StringArray::from(vec![
+            Some("US"),
+            Some("CA"),
+            Some("US"),
+            None,
+            Some("MX"),
+            Some("US"),
+        ])),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).unwrap()
+}
+
+// three row groups of two rows each
+fn create_parquet(wrk: &Workdir, name: &str) {
+    let batch = sample_batch();
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(2)
+        .build();
+    let file = File::create(wrk.path(name)).unwrap();
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props)).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+}
+
+#[test]
+fn from_parquet() {
+    let wrk = Workdir::new("from_parquet");
+    create_parquet(&wrk, "data.parquet");
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet").arg("data.parquet");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "year", "country"],
+        svec!["1", "2019", "US"],
+        svec!["2", "2020", "CA"],
+        svec!["3", "2021", "US"],
+        svec!["4", "2022", ""],
+        svec!["5", "2023", "MX"],
+        svec!["6", "2024", "US"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_parquet_select() {
+    let wrk = Workdir::new("from_parquet_select");
+    create_parquet(&wrk, "data.parquet");
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet")
+        .args(["--select", "country,id,country"])
+        .arg("data.parquet");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["country", "id", "country"],
+        svec!["US", "1", "US"],
+        svec!["CA", "2", "CA"],
+        svec!["US", "3", "US"],
+        svec!["", "4", ""],
+        svec!["MX", "5", "MX"],
+        svec!["US", "6", "US"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_parquet_filter() {
+    let wrk = Workdir::new("from_parquet_filter");
+    create_parquet(&wrk, "data.parquet");
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet")
+        .args(["--filter", "year>=2023"])
+        .arg("data.parquet");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "year", "country"],
+        svec!["5", "2023", "MX"],
+        svec!["6", "2024", "US"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_parquet_filter_row_groups_not_rows() {
+    let wrk = Workdir::new("from_parquet_filter_row_groups_not_rows");
+    create_parquet(&wrk, "data.parquet");
+
+    // the filter skips the row groups that cannot match,
+    // but the rows of the other row groups are all kept
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet")
+        .args(["--filter", "year=2020; country=CA"])
+        .args(["--select", "id"])
+        .arg("data.parquet");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["id"], svec!["1"], svec!["2"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_parquet_filter_unsigned() {
+    let wrk = Workdir::new("from_parquet_filter_unsigned");
+    let schema = Schema::new(vec![Field::new("n", DataType::UInt32, false)]);
+    let column: ArrayRef = Arc::new(UInt32Array::from(vec![1, 2, 3_000_000_000, 4_000_000_000]));
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![column]).unwrap();
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(2)
+        .build();
+    let file = File::create(wrk.path("data.parquet")).unwrap();
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props)).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+
+    // values of 2^31 and more are negative in the INT32 statistics
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet")
+        .args(["--filter", "n>=3000000000"])
+        .arg("data.parquet");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["n"], svec!["3000000000"], svec!["4000000000"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_parquet_filter_unknown_column() {
+    let wrk = Workdir::new("from_parquet_filter_unknown_column");
+    create_parquet(&wrk, "data.parquet");
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("parquet")
+        .args(["--filter", "month>1"])
+        .arg("data.parquet");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn from_arrow_file() {
+    let wrk = Workdir::new("from_arrow_file");
+    let batch = sample_batch();
+    let file = File::create(wrk.path("data.arrow")).unwrap();
+    let mut writer = FileWriter::try_new(file, &batch.schema()).unwrap();
+    writer.write(&batch).unwrap();
+    writer.finish().unwrap();
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("arrow")
+        .args(["--select", "year,country"])
+        .arg("data.arrow");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["year", "country"],
+        svec!["2019", "US"],
+        svec!["2020", "CA"],
+        svec!["2021", "US"],
+        svec!["2022", ""],
+        svec!["2023", "MX"],
+        svec!["2024", "US"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_arrow_stream() {
+    let wrk = Workdir::new("from_arrow_stream");
+    let batch = sample_batch();
+    let file = File::create(wrk.path("data.arrows")).unwrap();
+    let mut writer = StreamWriter::try_new(file, &batch.schema()).unwrap();
+    writer.write(&batch).unwrap();
+    writer.finish().unwrap();
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("arrow").args(["--select", "1"]).arg("data.arrows");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id"],
+        svec!["1"],
+        svec!["2"],
+        svec!["3"],
+        svec!["4"],
+        svec!["5"],
+        svec!["6"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn from_arrow_filter_not_supported() {
+    let wrk = Workdir::new("from_arrow_filter_not_supported");
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("arrow")
+        .args(["--filter", "year>2020"])
+        .arg("data.arrow");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn from_arrow_corrupt_file() {
+    let wrk = Workdir::new("from_arrow_corrupt_file");
+    // an IPC file magic, without a valid footer
+    wrk.create_from_string("data.arrow", "ARROW1\0\0not an arrow file");
+
+    let mut cmd = wrk.command("from");
+    cmd.arg("arrow").arg("data.arrow");
+
+    wrk.assert_err(&mut cmd);
+    let got = wrk.output_stderr(&mut cmd);
+    // the error is about the IPC file, rather than about reading it as a stream
+    assert!(got.contains("footer"), "{got}");
+}
@@ -0,0 +1,124 @@
+use crate::workdir::Workdir;
+
+fn numbers(name: &str, values: impl Iterator<Item = u32>) -> Vec<Vec<String>> {
+    let mut rows = vec![vec![name.to_string(), "name".to_string()]];
+    rows.extend(values.map(|v| vec![v.to_string(), format!("name{v}")]));
+    rows
+}
+
+#[test]
+fn histogram_fixed() {
+    let wrk = Workdir::new("histogram_fixed");
+    wrk.create("in.csv", numbers("n", 1..=10));
+
+    let mut cmd = wrk.command("histogram");
+    cmd.args(["--bins", "5"]).arg("in.csv");
+
+    // the name column is skipped, as its not numeric
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "lower", "upper", "count"],
+        svec!["n", "1", "2.8", "2"],
+        svec!["n", "2.8", "4.6", "2"],
+        svec!["n", "4.6", "6.4", "2"],
+        svec!["n", "6.4", "8.2", "2"],
+        svec!["n", "8.2", "10", "2"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn histogram_quantile() {
+    let wrk = Workdir::new("histogram_quantile");
+    wrk.create("in.csv", numbers("n", 1..=8));
+
+    let mut cmd = wrk.command("histogram");
+    cmd.args(["--method", "quantile"])
+        .args(["--bins", "4"])
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "lower", "upper", "count"],
+        svec!["n", "1", "3", "2"],
+        svec!["n", "3", "5", "2"],
+        svec!["n", "5", "6", "1"],
+        svec!["n", "6", "8", "3"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn histogram_fd() {
+    let wrk = Workdir::new("histogram_fd");
+    wrk.create("in.csv", numbers("n", 1..=100));
+
+    let mut cmd = wrk.command("histogram");
+    cmd.args(["--method", "fd"]).arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert!(got.len() > 2);
+    let total: u64 = got[1..]
+        .iter()
+        .map(|row| row[3].parse::<u64>().unwrap())
+        .sum();
+    assert_eq!(total, 100);
+}
+
+#[test]
+fn histogram_dates() {
+    let wrk = Workdir::new("histogram_dates");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["due_date"],
+            svec!["2023-01-01"],
+            svec!["2023-01-03"],
+            svec!["2023-01-07"],
+            svec!["2023-01-11"],
+        ],
+    );
+
+    let mut cmd = wrk.command("histogram");
+    cmd.arg("--infer-dates").args(["--bins", "2"]).arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "lower", "upper", "count"],
+        svec!["due_date", "2023-01-01", "2023-01-06", "2"],
+        svec!["due_date", "2023-01-06", "2023-01-11", "2"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn histogram_bar() {
+    let wrk = Workdir::new("histogram_bar");
+    wrk.create(
+        "in.csv",
+        vec![svec!["n"], svec!["1"], svec!["1"], svec!["1"], svec!["2"]],
+    );
+
+    let mut cmd = wrk.command("histogram");
+    cmd.arg("--bar")
+        .args(["--bins", "2"])
+        .args(["--bar-width", "6"])
+        .arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = "n
+    1 - 1.5 | ██████ 3
+  1.5 -   2 | ██ 1";
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn histogram_invalid_method() {
+    let wrk = Workdir::new("histogram_invalid_method");
+    wrk.create("in.csv", numbers("n", 1..=10));
+
+    let mut cmd = wrk.command("histogram");
+    cmd.args(["--method", "sturges"]).arg("in.csv");
+
+    wrk.assert_err(&mut cmd);
+}
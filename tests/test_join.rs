@@ -151,7 +151,11 @@ join_test!(join_left_semi, |wrk: Workdir,
                             headers: bool| {
     cmd.arg("--left-semi");
     let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
-    let expected = make_rows(headers, true, vec![svec!["Buffalo", "NY"]]);
+    let expected = make_rows(
+        headers,
+        true,
+        vec![svec!["Boston", "MA"], svec!["Buffalo", "NY"]],
+    );
     assert_eq!(got, expected);
 });
 
@@ -234,3 +238,913 @@ fn join_cross_no_headers() {
     ];
     assert_eq!(got, expected);
 }
+
+fn setup_sorted(name: &str) -> Workdir {
+    let cities = vec![
+        svec!["city", "state"],
+        svec!["Boston", "MA"],
+        svec!["Buffalo", "NY"],
+        svec!["New York", "NY"],
+        svec!["San Francisco", "CA"],
+    ];
+    let places = vec![
+        svec!["city", "place"],
+        svec!["Boston", "Logan Airport"],
+        svec!["Boston", "Boston Garden"],
+        svec!["Buffalo", "Ralph Wilson Stadium"],
+        svec!["Orlando", "Disney World"],
+    ];
+
+    let wrk = Workdir::new(name);
+    wrk.create("cities.csv", cities);
+    wrk.create("places.csv", places);
+    wrk
+}
+
+fn sorted_join(name: &str, join_arg: Option<&str>) -> Vec<Vec<String>> {
+    let wrk = setup_sorted(name);
+    let mut cmd = wrk.command("join");
+    cmd.arg("--check-sorted");
+    if let Some(join_arg) = join_arg {
+        cmd.arg(join_arg);
+    }
+    cmd.args(["city", "cities.csv", "city", "places.csv"]);
+    wrk.read_stdout(&mut cmd)
+}
+
+#[test]
+fn join_sorted_inner() {
+    let got = sorted_join("join_sorted_inner", None);
+    let expected = make_rows(
+        true,
+        false,
+        vec![
+            svec!["Boston", "MA", "Boston", "Logan Airport"],
+            svec!["Boston", "MA", "Boston", "Boston Garden"],
+            svec!["Buffalo", "NY", "Buffalo", "Ralph Wilson Stadium"],
+        ],
+    );
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_sorted_outer_left() {
+    let got = sorted_join("join_sorted_outer_left", Some("--left"));
+    let expected = make_rows(
+        true,
+        false,
+        vec![
+            svec!["Boston", "MA", "Boston", "Logan Airport"],
+            svec!["Boston", "MA", "Boston", "Boston Garden"],
+            svec!["Buffalo", "NY", "Buffalo", "Ralph Wilson Stadium"],
+            svec!["New York", "NY", "", ""],
+            svec!["San Francisco", "CA", "", ""],
+        ],
+    );
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_sorted_outer_right() {
+    let got = sorted_join("join_sorted_outer_right", Some("--right"));
+    let expected = make_rows(
+        true,
+        false,
+        vec![
+            svec!["Boston", "MA", "Boston", "Logan Airport"],
+            svec!["Boston", "MA", "Boston", "Boston Garden"],
+            svec!["Buffalo", "NY", "Buffalo", "Ralph Wilson Stadium"],
+            svec!["", "", "Orlando", "Disney World"],
+        ],
+    );
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_sorted_outer_full() {
+    let got = sorted_join("join_sorted_outer_full", Some("--full"));
+    let expected = make_rows(
+        true,
+        false,
+        vec![
+            svec!["Boston", "MA", "Boston", "Logan Airport"],
+            svec!["Boston", "MA", "Boston", "Boston Garden"],
+            svec!["Buffalo", "NY", "Buffalo", "Ralph Wilson Stadium"],
+            svec!["New York", "NY", "", ""],
+            svec!["", "", "Orlando", "Disney World"],
+            svec!["San Francisco", "CA", "", ""],
+        ],
+    );
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_sorted_left_semi() {
+    let got = sorted_join("join_sorted_left_semi", Some("--left-semi"));
+    let expected = make_rows(
+        true,
+        true,
+        vec![svec!["Boston", "MA"], svec!["Buffalo", "NY"]],
+    );
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_sorted_same_as_hash_join() {
+    let wrk = setup_sorted("join_sorted_same_as_hash_join");
+    // full joins are left out, as sorted full joins interleave the unmatched rows
+    for join_arg in ["--left", "--left-anti", "--left-semi", "--right"] {
+        let mut joined = vec![];
+        for sorted in [false, true] {
+            let mut cmd = wrk.command("join");
+            if sorted {
+                cmd.arg("--sorted");
+            }
+            cmd.arg(join_arg)
+                .args(["city", "cities.csv", "city", "places.csv"]);
+            let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+            joined.push(got);
+        }
+        assert_eq!(joined[0], joined[1], "{join_arg}");
+    }
+}
+
+#[test]
+fn join_sorted_left_anti() {
+    let got = sorted_join("join_sorted_left_anti", Some("--left-anti"));
+    let expected = make_rows(
+        true,
+        true,
+        vec![svec!["New York", "NY"], svec!["San Francisco", "CA"]],
+    );
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_sorted_nulls() {
+    let wrk = Workdir::new("join_sorted_nulls");
+    wrk.create(
+        "a.csv",
+        vec![svec!["k", "a"], svec!["", "1"], svec!["x", "2"]],
+    );
+    wrk.create(
+        "b.csv",
+        vec![svec!["k", "b"], svec!["", "3"], svec!["x", "4"]],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--sorted").args(["k", "a.csv", "k", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["k", "a", "k", "b"], svec!["x", "2", "x", "4"]];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--sorted")
+        .arg("--nulls")
+        .args(["k", "a.csv", "k", "b.csv"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["k", "a", "k", "b"],
+        svec!["", "1", "", "3"],
+        svec!["x", "2", "x", "4"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_sorted_out_of_order() {
+    // the cities of the join_test! tests are not sorted
+    let wrk = setup("join_sorted_out_of_order", true);
+    let mut cmd = wrk.command("join");
+    cmd.arg("--check-sorted")
+        .args(["city", "cities.csv", "city", "places.csv"]);
+
+    wrk.assert_err(&mut cmd);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("<input1> is not sorted on the join columns: row 4 with key (Buffalo)"));
+}
+
+#[test]
+fn join_sorted_ignore_case_raw_order() {
+    // sorted on the raw keys, but not on the lowercased keys
+    let wrk = Workdir::new("join_sorted_ignore_case_raw_order");
+    wrk.create(
+        "a.csv",
+        vec![svec!["k", "v"], svec!["B", "1"], svec!["a", "2"]],
+    );
+    wrk.create(
+        "b.csv",
+        vec![svec!["k", "w"], svec!["A", "3"], svec!["b", "4"]],
+    );
+    let mut cmd = wrk.command("join");
+    cmd.arg("--sorted")
+        .arg("--ignore-case")
+        .args(["k", "a.csv", "k", "b.csv"]);
+
+    wrk.assert_err(&mut cmd);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("<input1> is not sorted on the join columns: row 2 with key (a)"));
+}
+
+#[test]
+fn join_sorted_cross() {
+    let wrk = setup_sorted("join_sorted_cross");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--sorted")
+        .arg("--cross")
+        .args(["", "cities.csv", "", "places.csv"]);
+
+    wrk.assert_err(&mut cmd);
+}
+
+fn setup_star(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "sales.csv",
+        vec![
+            svec!["sale", "product_id", "store_id"],
+            svec!["1", "p1", "s1"],
+            svec!["2", "p2", "s2"],
+            svec!["3", "p3", "s1"],
+            svec!["4", "p1", "s3"],
+        ],
+    );
+    wrk.create(
+        "products.csv",
+        vec![
+            svec!["id", "product"],
+            svec!["p1", "apple"],
+            svec!["p2", "pear"],
+        ],
+    );
+    wrk.create(
+        "stores.csv",
+        vec![
+            svec!["store_id", "city"],
+            svec!["s1", "Boston"],
+            svec!["s2", "Buffalo"],
+            svec!["s2", "Albany"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn join_multi_inner() {
+    let wrk = setup_star("join_multi_inner");
+    let mut cmd = wrk.command("join");
+    cmd.args([
+        "product_id",
+        "sales.csv",
+        "id",
+        "products.csv",
+        "store_id",
+        "stores.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "sale",
+            "product_id",
+            "store_id",
+            "id",
+            "product",
+            "store_id",
+            "city"
+        ],
+        svec!["1", "p1", "s1", "p1", "apple", "s1", "Boston"],
+        svec!["2", "p2", "s2", "p2", "pear", "s2", "Buffalo"],
+        svec!["2", "p2", "s2", "p2", "pear", "s2", "Albany"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_multi_joins() {
+    let wrk = setup_star("join_multi_joins");
+    let mut cmd = wrk.command("join");
+    cmd.args(["--joins", "left,inner"]).args([
+        "product_id",
+        "sales.csv",
+        "id",
+        "products.csv",
+        "store_id=store_id",
+        "stores.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "sale",
+            "product_id",
+            "store_id",
+            "id",
+            "product",
+            "store_id",
+            "city"
+        ],
+        svec!["1", "p1", "s1", "p1", "apple", "s1", "Boston"],
+        svec!["2", "p2", "s2", "p2", "pear", "s2", "Buffalo"],
+        svec!["2", "p2", "s2", "p2", "pear", "s2", "Albany"],
+        svec!["3", "p3", "s1", "", "", "s1", "Boston"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_multi_left() {
+    let wrk = setup_star("join_multi_left");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--left").args([
+        "product_id",
+        "sales.csv",
+        "id",
+        "products.csv",
+        "3=1",
+        "stores.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "sale",
+            "product_id",
+            "store_id",
+            "id",
+            "product",
+            "store_id",
+            "city"
+        ],
+        svec!["1", "p1", "s1", "p1", "apple", "s1", "Boston"],
+        svec!["2", "p2", "s2", "p2", "pear", "s2", "Buffalo"],
+        svec!["2", "p2", "s2", "p2", "pear", "s2", "Albany"],
+        svec!["3", "p3", "s1", "", "", "s1", "Boston"],
+        svec!["4", "p1", "s3", "p1", "apple", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_multi_invalid() {
+    let wrk = setup_star("join_multi_invalid");
+
+    // one join operation for two dimensions
+    let mut cmd = wrk.command("join");
+    cmd.args(["--joins", "left"]).args([
+        "product_id",
+        "sales.csv",
+        "id",
+        "products.csv",
+        "store_id",
+        "stores.csv",
+    ]);
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--full").args([
+        "product_id",
+        "sales.csv",
+        "id",
+        "products.csv",
+        "store_id",
+        "stores.csv",
+    ]);
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn join_normalize() {
+    let wrk = Workdir::new("join_normalize");
+    wrk.create(
+        "cities1.csv",
+        vec![
+            svec!["city", "state"],
+            svec!["Café-Zürich", "a"],
+            svec!["São  Paulo", "b"],
+            svec!["Boston", "c"],
+        ],
+    );
+    wrk.create(
+        "cities2.csv",
+        vec![
+            svec!["city", "population"],
+            svec!["cafe zurich", "1"],
+            svec!["sao paulo", "2"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--ignore-case")
+        .args(["--normalize", "nfkc,diacritics,punctuation,whitespace"])
+        .args(["city", "cities1.csv", "city", "cities2.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["city", "state", "city", "population"],
+        svec!["Café-Zürich", "a", "cafe zurich", "1"],
+        svec!["São  Paulo", "b", "sao paulo", "2"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_normalize_invalid() {
+    let wrk = setup("join_normalize_invalid", true);
+    let mut cmd = wrk.command("join");
+    cmd.args(["--normalize", "soundex"])
+        .args(["city", "cities.csv", "city", "places.csv"]);
+    wrk.assert_err(&mut cmd);
+}
+
+fn setup_fuzzy(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "people.csv",
+        vec![
+            svec!["name", "age"],
+            svec!["Jon Smith", "31"],
+            svec!["Mary Jones", "45"],
+            svec!["Bob Brown", "22"],
+        ],
+    );
+    wrk.create(
+        "customers.csv",
+        vec![
+            svec!["customer", "id"],
+            svec!["John Smith", "1"],
+            svec!["Mary Jones", "2"],
+            svec!["Alice Green", "3"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn join_fuzzy_inner() {
+    let wrk = setup_fuzzy("join_fuzzy_inner");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--fuzzy")
+        .args(["name", "people.csv", "customer", "customers.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "age", "customer", "id", "fuzzy_score"],
+        svec!["Jon Smith", "31", "John Smith", "1", "0.9733"],
+        svec!["Mary Jones", "45", "Mary Jones", "2", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_fuzzy_left_threshold() {
+    let wrk = setup_fuzzy("join_fuzzy_left_threshold");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--fuzzy")
+        .arg("--left")
+        .args(["--fuzzy-threshold", "0.98"])
+        .args(["name", "people.csv", "customer", "customers.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "age", "customer", "id", "fuzzy_score"],
+        svec!["Jon Smith", "31", "", "", ""],
+        svec!["Mary Jones", "45", "Mary Jones", "2", "1"],
+        svec!["Bob Brown", "22", "", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_fuzzy_damerau() {
+    let wrk = setup_fuzzy("join_fuzzy_damerau");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--fuzzy")
+        .args(["--fuzzy-metric", "damerau"])
+        .args(["--fuzzy-threshold", "0.8"])
+        .args(["name", "people.csv", "customer", "customers.csv"]);
+
+    // "Jon Smith" is one insertion away from "John Smith" - 1 - 1/10
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "age", "customer", "id", "fuzzy_score"],
+        svec!["Jon Smith", "31", "John Smith", "1", "0.9"],
+        svec!["Mary Jones", "45", "Mary Jones", "2", "1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_fuzzy_invalid() {
+    let wrk = setup_fuzzy("join_fuzzy_invalid");
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--fuzzy")
+        .arg("--full")
+        .args(["name", "people.csv", "customer", "customers.csv"]);
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--fuzzy").args(["--fuzzy-threshold", "1.5"]).args([
+        "name",
+        "people.csv",
+        "customer",
+        "customers.csv",
+    ]);
+    wrk.assert_err(&mut cmd);
+}
+
+fn setup_asof(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "transactions.csv",
+        vec![
+            svec!["id", "currency", "date", "amount"],
+            svec!["1", "USD", "2023-01-02", "100"],
+            svec!["2", "EUR", "2023-01-05", "200"],
+            svec!["3", "USD", "2023-01-10", "300"],
+            svec!["4", "USD", "2022-12-25", "50"],
+        ],
+    );
+    wrk.create(
+        "rates.csv",
+        vec![
+            svec!["currency", "date", "rate"],
+            svec!["USD", "2023-01-01", "1.0"],
+            svec!["EUR", "2023-01-01", "0.9"],
+            svec!["USD", "2023-01-05", "1.1"],
+            svec!["EUR", "2023-01-04", "0.95"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn join_asof_backward() {
+    let wrk = setup_asof("join_asof_backward");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--asof").args(["--by", "currency"]).args([
+        "date",
+        "transactions.csv",
+        "date",
+        "rates.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "currency", "date", "amount", "currency", "date", "rate"],
+        svec!["1", "USD", "2023-01-02", "100", "USD", "2023-01-01", "1.0"],
+        svec!["2", "EUR", "2023-01-05", "200", "EUR", "2023-01-04", "0.95"],
+        svec!["3", "USD", "2023-01-10", "300", "USD", "2023-01-05", "1.1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_asof_nearest_tolerance() {
+    let wrk = setup_asof("join_asof_nearest_tolerance");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--asof")
+        .arg("--left")
+        .args(["--direction", "nearest"])
+        .args(["--tolerance", "2"])
+        .args(["--by", "currency"])
+        .args(["date", "transactions.csv", "date", "rates.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "currency", "date", "amount", "currency", "date", "rate"],
+        svec!["1", "USD", "2023-01-02", "100", "USD", "2023-01-01", "1.0"],
+        svec!["2", "EUR", "2023-01-05", "200", "EUR", "2023-01-04", "0.95"],
+        svec!["3", "USD", "2023-01-10", "300", "", "", ""],
+        svec!["4", "USD", "2022-12-25", "50", "", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_asof_forward_numbers() {
+    let wrk = Workdir::new("join_asof_forward_numbers");
+    wrk.create(
+        "readings.csv",
+        vec![
+            svec!["t", "value"],
+            svec!["1.5", "a"],
+            svec!["3", "b"],
+            svec!["9", "c"],
+        ],
+    );
+    wrk.create(
+        "events.csv",
+        vec![
+            svec!["t", "event"],
+            svec!["3", "start"],
+            svec!["2", "ping"],
+            svec!["3", "restart"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--asof").args(["--direction", "forward"]).args([
+        "t",
+        "readings.csv",
+        "t",
+        "events.csv",
+    ]);
+
+    // ties go to the first row of the second data set
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["t", "value", "t", "event"],
+        svec!["1.5", "a", "2", "ping"],
+        svec!["3", "b", "3", "start"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_interval() {
+    let wrk = Workdir::new("join_interval");
+    wrk.create(
+        "orders.csv",
+        vec![
+            svec!["order", "amount"],
+            svec!["A", "5"],
+            svec!["B", "15"],
+            svec!["C", "25"],
+            svec!["D", "100"],
+            svec!["E", "10"],
+        ],
+    );
+    wrk.create(
+        "tariffs.csv",
+        vec![
+            svec!["band", "min", "max"],
+            svec!["low", "0", "10"],
+            svec!["mid", "10", "20"],
+            svec!["high", "20", "50"],
+            svec!["promo", "12", "30"],
+        ],
+    );
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--interval")
+        .arg("--left")
+        .args(["amount", "orders.csv", "min,max", "tariffs.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["order", "amount", "band", "min", "max"],
+        svec!["A", "5", "low", "0", "10"],
+        svec!["B", "15", "mid", "10", "20"],
+        svec!["B", "15", "promo", "12", "30"],
+        svec!["C", "25", "high", "20", "50"],
+        svec!["C", "25", "promo", "12", "30"],
+        svec!["D", "100", "", "", ""],
+        svec!["E", "10", "low", "0", "10"],
+        svec!["E", "10", "mid", "10", "20"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_asof_invalid() {
+    let wrk = setup_asof("join_asof_invalid");
+
+    // as-of joins are on a single column
+    let mut cmd = wrk.command("join");
+    cmd.arg("--asof").args([
+        "currency,date",
+        "transactions.csv",
+        "currency,date",
+        "rates.csv",
+    ]);
+    wrk.assert_err(&mut cmd);
+
+    // interval joins need the lower & upper bounds
+    let mut cmd = wrk.command("join");
+    cmd.arg("--interval")
+        .args(["date", "transactions.csv", "date", "rates.csv"]);
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--asof")
+        .arg("--right")
+        .args(["date", "transactions.csv", "date", "rates.csv"]);
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("join");
+    cmd.arg("--asof").args(["--direction", "sideways"]).args([
+        "date",
+        "transactions.csv",
+        "date",
+        "rates.csv",
+    ]);
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn join_asof_mixed_key_types() {
+    let wrk = Workdir::new("join_asof_mixed_key_types");
+    wrk.create(
+        "transactions.csv",
+        vec![svec!["date", "amount"], svec!["2023-01-02", "100"]],
+    );
+    wrk.create(
+        "rates.csv",
+        vec![
+            svec!["date", "rate"],
+            svec!["20230101", "1.0"],
+            svec!["2023-01-05", "1.1"],
+        ],
+    );
+
+    // the keys are numbers by the first key, so the date is an error
+    let mut cmd = wrk.command("join");
+    cmd.arg("--asof")
+        .args(["date", "transactions.csv", "date", "rates.csv"]);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("\"2023-01-05\" is not a number"), "{got}");
+    wrk.assert_err(&mut cmd);
+}
+
+fn setup_collisions(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "a.csv",
+        vec![svec!["id", "name"], svec!["1", "alice"], svec!["2", "bob"]],
+    );
+    wrk.create(
+        "b.csv",
+        vec![
+            svec!["id", "name", "score"],
+            svec!["2", "bobby", "10"],
+            svec!["3", "carl", "20"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn join_coalesce_keys() {
+    let wrk = setup_collisions("join_coalesce_keys");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--full")
+        .arg("--coalesce-keys")
+        .args(["--right-prefix", "r_"])
+        .args(["id", "a.csv", "id", "b.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "name", "r_name", "score"],
+        svec!["1", "alice", "", ""],
+        svec!["2", "bob", "bobby", "10"],
+        svec!["3", "", "carl", "20"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_right_select_suffixes() {
+    let wrk = setup_collisions("join_right_select_suffixes");
+    let mut cmd = wrk.command("join");
+    cmd.args(["--right-select", "id,score"])
+        .args(["--left-suffix", "_a"])
+        .args(["--right-suffix", "_b"])
+        .args(["id", "a.csv", "id", "b.csv"]);
+
+    // name isn't renamed, as the name column of b.csv isn't selected
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id_a", "name", "id_b", "score"],
+        svec!["2", "bob", "2", "10"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_right_select_no_headers() {
+    let wrk = setup_collisions("join_right_select_no_headers");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--no-headers")
+        .arg("--left")
+        .args(["--right-select", "3"])
+        .args(["1", "a.csv", "1", "b.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "name", "score"],
+        svec!["1", "alice", ""],
+        svec!["2", "bob", "10"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn join_coalesce_keys_multi() {
+    let wrk = setup_star("join_coalesce_keys_multi");
+    let mut cmd = wrk.command("join");
+    cmd.arg("--coalesce-keys").args([
+        "product_id",
+        "sales.csv",
+        "id",
+        "products.csv",
+        "store_id",
+        "stores.csv",
+    ]);
+    wrk.assert_err(&mut cmd);
+}
+
+fn setup_spill(name: &str) -> Workdir {
+    // the index of right.csv takes about 1.6MB, and the index of left.csv 2.1MB
+    let wrk = Workdir::new(name);
+    let mut left = vec![svec!["key", "left"]];
+    left.extend((0..20_000).map(|i| vec![format!("key{i:040}"), format!("l{i}")]));
+    let mut right = vec![svec!["key", "right"]];
+    right.extend(
+        (0..30_000)
+            .step_by(2)
+            .map(|i| vec![format!("key{i:040}"), format!("r{i}")]),
+    );
+    right.push(svec!["", "null"]);
+    wrk.create("left.csv", left);
+    wrk.create("right.csv", right);
+    wrk
+}
+
+/// joins with and without spilling to disk, and returns the rows of both,
+/// sorted as spilled joins are ordered by partition
+fn spilled_join(name: &str, join: &str) -> (Vec<Vec<String>>, Vec<Vec<String>>) {
+    let wrk = setup_spill(name);
+    let mut joined = vec![];
+    for memory_limit in [None, Some("1")] {
+        let mut cmd = wrk.command("join");
+        if !join.is_empty() {
+            cmd.arg(join);
+        }
+        if let Some(memory_limit) = memory_limit {
+            cmd.args(["--memory-limit", memory_limit]);
+        }
+        cmd.args(["key", "left.csv", "key", "right.csv"]);
+        let mut got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+        got[1..].sort_unstable();
+        joined.push(got);
+    }
+    let spilled = joined.pop().unwrap();
+    (joined.pop().unwrap(), spilled)
+}
+
+#[test]
+fn join_spill_inner() {
+    let (got, spilled) = spilled_join("join_spill_inner", "");
+    assert_eq!(got.len(), 10_001);
+    assert_eq!(got, spilled);
+}
+
+#[test]
+fn join_spill_left() {
+    let (got, spilled) = spilled_join("join_spill_left", "--left");
+    assert_eq!(got.len(), 20_001);
+    assert_eq!(got, spilled);
+}
+
+#[test]
+fn join_spill_right() {
+    let (got, spilled) = spilled_join("join_spill_right", "--right");
+    assert_eq!(got.len(), 15_002);
+    assert_eq!(got, spilled);
+}
+
+#[test]
+fn join_spill_full() {
+    let (got, spilled) = spilled_join("join_spill_full", "--full");
+    assert_eq!(got.len(), 25_002);
+    assert_eq!(got, spilled);
+}
+
+#[test]
+fn join_spill_left_anti() {
+    let (got, spilled) = spilled_join("join_spill_left_anti", "--left-anti");
+    assert_eq!(got.len(), 10_001);
+    assert_eq!(got, spilled);
+}
+
+#[test]
+fn join_spill_left_semi() {
+    let (got, spilled) = spilled_join("join_spill_left_semi", "--left-semi");
+    assert_eq!(got.len(), 10_001);
+    assert_eq!(got[0], svec!["key", "left"]);
+    assert!(got[1..]
+        .iter()
+        .all(|row| row[1][1..].parse::<u32>().unwrap() % 2 == 0));
+    assert_eq!(got, spilled);
+}
+
+#[test]
+fn join_spill_invalid() {
+    let wrk = setup("join_spill_invalid", true);
+    let mut cmd = wrk.command("join");
+    cmd.args(["--memory-limit", "0"])
+        .args(["city", "cities.csv", "city", "places.csv"]);
+    wrk.assert_err(&mut cmd);
+}
@@ -0,0 +1,157 @@
+use crate::workdir::Workdir;
+
+fn setup(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "base.csv",
+        vec![
+            svec!["id", "name", "amount"],
+            svec!["1", "alice", "10"],
+            svec!["2", "bob", "20"],
+            svec!["3", "carl", "30"],
+        ],
+    );
+    wrk.create(
+        "changes.csv",
+        vec![
+            svec!["diffresult", "id", "name", "amount"],
+            svec!["-", "2", "bob", "20"],
+            svec!["+", "2", "bobby", "25"],
+            svec!["-", "3", "carl", "30"],
+            svec!["+", "4", "dave", "40"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn patch_hash() {
+    let wrk = setup("patch_hash");
+    let mut cmd = wrk.command("patch");
+    cmd.args(["--key", "id"]).arg("base.csv").arg("changes.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "name", "amount"],
+        svec!["1", "alice", "10"],
+        svec!["2", "bobby", "25"],
+        svec!["4", "dave", "40"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn patch_sorted() {
+    let wrk = setup("patch_sorted");
+    wrk.create(
+        "changes.csv",
+        vec![
+            svec!["diffresult", "id", "name", "amount"],
+            svec!["+", "0", "zoe", "5"],
+            svec!["-", "2", "bob", "20"],
+            svec!["+", "2", "bobby", "25"],
+            svec!["-", "3", "carl", "30"],
+            svec!["+", "4", "dave", "40"],
+        ],
+    );
+    let mut cmd = wrk.command("patch");
+    cmd.arg("--sorted").arg("base.csv").arg("changes.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "name", "amount"],
+        svec!["0", "zoe", "5"],
+        svec!["1", "alice", "10"],
+        svec!["2", "bobby", "25"],
+        svec!["4", "dave", "40"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn patch_sorted_unsorted_changes() {
+    let wrk = setup("patch_sorted_unsorted_changes");
+    wrk.create(
+        "changes.csv",
+        vec![
+            svec!["diffresult", "id", "name", "amount"],
+            svec!["+", "4", "dave", "40"],
+            svec!["-", "3", "carl", "30"],
+        ],
+    );
+    let mut cmd = wrk.command("patch");
+    cmd.arg("--sorted").arg("base.csv").arg("changes.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn patch_conflict() {
+    let wrk = setup("patch_conflict");
+    wrk.create(
+        "changes.csv",
+        vec![
+            svec!["diffresult", "id", "name", "amount"],
+            svec!["-", "5", "eve", "50"],
+        ],
+    );
+    let mut cmd = wrk.command("patch");
+    cmd.arg("base.csv").arg("changes.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn patch_check() {
+    let wrk = setup("patch_check");
+    wrk.create(
+        "changes.csv",
+        vec![
+            svec!["diffresult", "id", "name", "amount"],
+            svec!["-", "2", "bob", "21"],
+            svec!["+", "2", "bobby", "25"],
+            svec!["-", "5", "eve", "50"],
+            svec!["+", "1", "alice", "11"],
+        ],
+    );
+    let mut cmd = wrk.command("patch");
+    cmd.arg("--check").arg("base.csv").arg("changes.csv");
+
+    let got: String = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "3 conflicts found.\n");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["id", "conflict", "column", "expected", "actual"],
+        svec!["1", "exists", "", "", ""],
+        svec!["2", "modified", "amount", "21", "20"],
+        svec!["5", "missing", "", "", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn patch_diff_roundtrip() {
+    let wrk = Workdir::new("patch_diff_roundtrip");
+    let base = wrk.load_test_file("boston311-100.csv");
+    let right = wrk.load_test_file("boston311-100-diff.csv");
+
+    let mut cmd = wrk.command("diff");
+    cmd.arg(&base).arg(&right);
+    let changes: String = wrk.stdout(&mut cmd);
+    wrk.create_from_string("changes.csv", &changes);
+
+    let mut cmd = wrk.command("patch");
+    cmd.arg(&base).arg("changes.csv");
+    wrk.create_from_string("patched.csv", &wrk.stdout::<String>(&mut cmd));
+
+    let mut cmd = wrk.command("sort");
+    cmd.arg("patched.csv");
+    let got: String = wrk.stdout(&mut cmd);
+
+    let mut cmd = wrk.command("sort");
+    cmd.arg(&right);
+    let expected: String = wrk.stdout(&mut cmd);
+
+    assert_eq!(got, expected);
+}
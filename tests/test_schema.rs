@@ -157,3 +157,355 @@ fn generate_schema_with_defaults_to_stdout() {
 
     assert_json_eq!(expected_schema_json, output_schema_json);
 }
+
+#[test]
+fn generate_schema_with_boolean_and_subtypes_and_validate_with_no_errors() {
+    let wrk = Workdir::new("schema_boolean_subtypes");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "active", "email", "homepage"],
+            svec!["1", "Yes", "jdoe@example.com", "https://example.com"],
+            svec!["2", "no", "mary@example.org", "https://example.org/mary"],
+            svec!["3", "", "bob@example.net", ""],
+        ],
+    );
+
+    // run schema command
+    let mut cmd = wrk.command("schema");
+    cmd.arg("--infer-boolean")
+        .arg("--infer-subtypes")
+        .arg("--stdout")
+        .arg("in.csv");
+    let stdout = wrk.stdout::<String>(&mut cmd);
+    let output_schema_json: Value = serde_json::from_str(&stdout).unwrap();
+
+    let properties = &output_schema_json["properties"];
+    assert_eq!(
+        properties["active"]["type"],
+        serde_json::json!(["boolean", "null"])
+    );
+    assert_eq!(properties["email"]["format"], "email");
+    assert_eq!(properties["homepage"]["format"], "uri");
+    assert!(properties["id"].get("format").is_none());
+
+    // the generated schema should validate the data it was inferred from
+    wrk.create_from_string("in.csv.schema.json", &stdout);
+    let mut cmd = wrk.command("validate");
+    cmd.arg("in.csv").arg("in.csv.schema.json");
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn generate_schema_truthy_falsy() {
+    let wrk = Workdir::new("generate_schema_truthy_falsy");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "active"],
+            svec!["1", "oui"],
+            svec!["2", "non"],
+            svec!["3", "Oui"],
+        ],
+    );
+
+    let mut cmd = wrk.command("schema");
+    cmd.arg("--infer-boolean")
+        .args(["--truthy", "oui"])
+        .args(["--falsy", "non"])
+        .arg("--stdout")
+        .arg("in.csv");
+    let stdout = wrk.stdout::<String>(&mut cmd);
+    let output_schema_json: Value = serde_json::from_str(&stdout).unwrap();
+
+    let active = &output_schema_json["properties"]["active"];
+    assert_eq!(active["type"], serde_json::json!(["boolean"]));
+    assert_eq!(
+        active["x-trueValues"],
+        serde_json::json!(["oui", "Oui", "OUI"])
+    );
+    assert_eq!(
+        active["x-falseValues"],
+        serde_json::json!(["non", "Non", "NON"])
+    );
+
+    // validate uses the configured values of the schema
+    wrk.create_from_string("in.csv.schema.json", &stdout);
+    let mut cmd = wrk.command("validate");
+    cmd.arg("in.csv").arg("in.csv.schema.json");
+    wrk.assert_success(&mut cmd);
+}
+
+fn create_formats_csv(wrk: &Workdir) {
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "name", "score", "created_date"],
+            svec!["1", "alice", "1.5", "2023-01-15"],
+            svec!["2", "bob", "", "2023-02-20"],
+            svec!["3", "carl", "2.25", "2023-03-05"],
+        ],
+    );
+}
+
+#[test]
+fn generate_schema_tableschema() {
+    let wrk = Workdir::new("schema_tableschema");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "tableschema"]).arg("in.csv");
+    wrk.assert_success(&mut cmd);
+
+    let output: Value = wrk.from_str(&wrk.path("in.csv.tableschema.json"));
+    let fields = output["fields"].as_array().unwrap();
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields[0]["name"], "id");
+    assert_eq!(fields[0]["type"], "integer");
+    assert_eq!(fields[0]["constraints"]["required"], true);
+    assert_eq!(
+        fields[0]["constraints"]["enum"],
+        serde_json::json!([1, 2, 3])
+    );
+    assert_eq!(fields[2]["type"], "number");
+    assert!(fields[2]["constraints"].get("required").is_none());
+    assert_eq!(fields[3]["type"], "date");
+    assert_eq!(fields[3]["format"], "any");
+
+    // the generated table schema should validate the data it was inferred from
+    let mut cmd = wrk.command("validate");
+    cmd.arg("in.csv").arg("in.csv.tableschema.json");
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn generate_schema_csvw() {
+    let wrk = Workdir::new("schema_csvw");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "csvw"]).arg("--stdout").arg("in.csv");
+    let output: Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+
+    assert_eq!(output["url"], "in.csv");
+    let columns = output["tableSchema"]["columns"].as_array().unwrap();
+    assert_eq!(columns[0]["titles"], "id");
+    assert_eq!(columns[0]["datatype"]["base"], "integer");
+    assert_eq!(columns[0]["required"], true);
+    assert_eq!(columns[2]["datatype"]["base"], "double");
+    assert_eq!(columns[2]["required"], false);
+    assert_eq!(columns[3]["datatype"], "date");
+}
+
+#[test]
+fn generate_schema_postgresql() {
+    let wrk = Workdir::new("schema_postgresql");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "postgresql"]).arg("in.csv");
+    wrk.assert_success(&mut cmd);
+
+    let got: String = wrk.from_str(&wrk.path("in.csv.sql"));
+    let expected = r#"CREATE TABLE "in" (
+    "id" INTEGER NOT NULL,
+    "name" TEXT NOT NULL,
+    "score" DOUBLE PRECISION,
+    "created_date" DATE NOT NULL
+);
+"#;
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn generate_schema_sqlite() {
+    let wrk = Workdir::new("schema_sqlite");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "sqlite"])
+        .arg("--stdout")
+        .arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"CREATE TABLE "in" (
+    "id" INTEGER NOT NULL,
+    "name" TEXT NOT NULL,
+    "score" REAL,
+    "created_date" TEXT NOT NULL
+);"#;
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn generate_schema_mysql() {
+    let wrk = Workdir::new("schema_mysql");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "mysql"])
+        .arg("--stdout")
+        .arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"CREATE TABLE `in` (
+    `id` INT NOT NULL,
+    `name` VARCHAR(5) NOT NULL,
+    `score` DOUBLE,
+    `created_date` DATE NOT NULL
+);"#;
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn generate_schema_avro() {
+    let wrk = Workdir::new("schema_avro");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "avro"]).arg("--stdout").arg("in.csv");
+    let output: Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+
+    assert_eq!(output["type"], "record");
+    assert_eq!(output["name"], "in");
+    let fields = output["fields"].as_array().unwrap();
+    assert_eq!(fields[0]["type"], "long");
+    assert_eq!(fields[2]["type"], serde_json::json!(["null", "double"]));
+    assert_eq!(fields[2]["default"], Value::Null);
+    assert_eq!(
+        fields[3]["type"],
+        serde_json::json!({"type": "int", "logicalType": "date"})
+    );
+}
+
+#[test]
+fn generate_schema_arrow() {
+    let wrk = Workdir::new("schema_arrow");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "arrow"])
+        .arg("--stdout")
+        .arg("in.csv");
+    let output: Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+
+    let fields = output["fields"].as_array().unwrap();
+    assert_eq!(fields[0]["name"], "id");
+    assert_eq!(fields[0]["nullable"], false);
+    assert_eq!(
+        fields[0]["type"],
+        serde_json::json!({"name": "int", "bitWidth": 64, "isSigned": true})
+    );
+    assert_eq!(fields[2]["nullable"], true);
+    assert_eq!(fields[3]["type"]["name"], "date");
+}
+
+#[test]
+fn generate_schema_invalid_format() {
+    let wrk = Workdir::new("schema_invalid_format");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "xml"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn generate_schema_with_slack() {
+    let wrk = Workdir::new("schema_slack");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--slack", "50"]).arg("--stdout").arg("in.csv");
+    let output: Value = serde_json::from_str(&wrk.stdout::<String>(&mut cmd)).unwrap();
+
+    let properties = &output["properties"];
+    assert_eq!(properties["id"]["minimum"], 0);
+    assert_eq!(properties["id"]["maximum"], 5);
+    assert_eq!(properties["name"]["minLength"], 1);
+    assert_eq!(properties["name"]["maxLength"], 8);
+    assert_eq!(properties["score"]["minimum"], 0.75);
+    assert_eq!(properties["score"]["maximum"], 3.375);
+}
+
+#[test]
+fn generate_schema_with_unique_hints_and_validate() {
+    let wrk = Workdir::new("schema_unique_hints");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.arg("--unique-hints").arg("in.csv");
+    wrk.assert_success(&mut cmd);
+
+    let output: Value = wrk.from_str(&wrk.path("in.csv.schema.json"));
+    assert_eq!(output["primaryKey"], "id");
+    assert_eq!(output["properties"]["id"]["x-unique"], true);
+    assert_eq!(output["properties"]["score"]["x-unique"], true);
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("in.csv").arg("in.csv.schema.json");
+    wrk.assert_success(&mut cmd);
+
+    // the primary key is enforced by validate
+    wrk.create(
+        "dupe.csv",
+        vec![
+            svec!["id", "name", "score", "created_date"],
+            svec!["1", "alice", "1.5", "2023-01-15"],
+            svec!["1", "bob", "", "2023-02-20"],
+        ],
+    );
+    let mut cmd = wrk.command("validate");
+    cmd.arg("dupe.csv").arg("in.csv.schema.json");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn generate_schema_mysql_with_unique_hints() {
+    let wrk = Workdir::new("schema_mysql_unique_hints");
+    let long_name = "x".repeat(800);
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["id", "code", "name"],
+            svec!["1", "a1", "alice"],
+            svec!["2", "b2", long_name],
+        ],
+    );
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "mysql"])
+        .arg("--unique-hints")
+        .arg("--stdout")
+        .arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"CREATE TABLE `in` (
+    `id` INT NOT NULL PRIMARY KEY,
+    `code` VARCHAR(2) NOT NULL UNIQUE,
+    -- too long for UNIQUE in MySQL, index a prefix instead
+    `name` TEXT NOT NULL
+);"#;
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn generate_schema_postgresql_with_unique_hints() {
+    let wrk = Workdir::new("schema_postgresql_unique_hints");
+    create_formats_csv(&wrk);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["--format", "postgresql"])
+        .arg("--unique-hints")
+        .arg("--stdout")
+        .arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"CREATE TABLE "in" (
+    "id" INTEGER NOT NULL PRIMARY KEY,
+    "name" TEXT NOT NULL UNIQUE,
+    "score" DOUBLE PRECISION UNIQUE,
+    "created_date" DATE NOT NULL UNIQUE
+);"#;
+    assert_eq!(got, expected);
+}
@@ -0,0 +1,304 @@
+use crate::workdir::Workdir;
+
+const OLD_SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": {
+    "id": { "type": "integer" },
+    "customer_name": { "type": "string", "maxLength": 10 },
+    "status": { "type": "string", "enum": ["open", "closed"] },
+    "amount": { "type": "integer", "minimum": 0 },
+    "notes": { "type": ["string", "null"] }
+  }
+}"#;
+
+#[test]
+fn schemadiff_same() {
+    let wrk = Workdir::new("schemadiff_same");
+    wrk.create_from_string("old.schema.json", OLD_SCHEMA);
+    wrk.create_from_string("new.schema.json", OLD_SCHEMA);
+
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("old.schema.json").arg("new.schema.json");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec![
+        "field",
+        "constraint",
+        "old",
+        "new",
+        "change",
+        "breaking"
+    ]];
+    assert_eq!(got, expected);
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn schemadiff_breaking() {
+    let wrk = Workdir::new("schemadiff_breaking");
+    wrk.create_from_string("old.schema.json", OLD_SCHEMA);
+    wrk.create_from_string(
+        "new.schema.json",
+        r#"{
+  "type": "object",
+  "properties": {
+    "id": { "type": "integer" },
+    "customer_nm": { "type": "string", "maxLength": 20 },
+    "status": { "type": "string", "enum": ["open", "closed", "pending"] },
+    "amount": { "type": "number", "minimum": 0 },
+    "region": { "type": "integer" }
+  }
+}"#,
+    );
+
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("old.schema.json").arg("new.schema.json");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "constraint", "old", "new", "change", "breaking"],
+        svec![
+            "customer_nm",
+            "name",
+            "customer_name",
+            "customer_nm",
+            "renamed",
+            "true"
+        ],
+        svec!["customer_nm", "maxLength", "10", "20", "widened", "true"],
+        svec!["status", "enum", "", "[\"pending\"]", "widened", "true"],
+        svec!["amount", "type", "integer", "number", "widened", "true"],
+        svec!["notes", "name", "notes", "", "removed", "true"],
+        svec!["region", "name", "", "region", "added", "false"],
+    ];
+    assert_eq!(got, expected);
+
+    let got: String = wrk.output_stderr(&mut cmd);
+    assert_eq!(got, "5 breaking changes found.\n");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn schemadiff_narrowed() {
+    let wrk = Workdir::new("schemadiff_narrowed");
+    wrk.create_from_string(
+        "old.schema.json",
+        r#"{
+  "properties": {
+    "amount": { "type": ["number", "null"] },
+    "status": { "type": "string", "enum": ["open", "closed", "pending"] }
+  }
+}"#,
+    );
+    wrk.create_from_string(
+        "new.schema.json",
+        r#"{
+  "properties": {
+    "amount": { "type": "integer", "minimum": 0 },
+    "status": { "type": "string", "enum": ["open", "closed"] }
+  }
+}"#,
+    );
+
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("old.schema.json").arg("new.schema.json");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "constraint", "old", "new", "change", "breaking"],
+        svec!["amount", "type", "number", "integer", "narrowed", "false"],
+        svec!["amount", "nullable", "true", "false", "narrowed", "false"],
+        svec!["amount", "minimum", "", "0", "narrowed", "false"],
+        svec!["status", "enum", "[\"pending\"]", "", "narrowed", "false"],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn schemadiff_ignore_constraints() {
+    let wrk = Workdir::new("schemadiff_ignore_constraints");
+    wrk.create_from_string("old.schema.json", OLD_SCHEMA);
+    wrk.create_from_string(
+        "new.schema.json",
+        r#"{
+  "properties": {
+    "id": { "type": "integer" },
+    "customer_name": { "type": "string", "maxLength": 20 },
+    "status": { "type": "string" },
+    "amount": { "type": "integer", "minimum": -10 },
+    "notes": { "type": ["string", "null"] }
+  }
+}"#,
+    );
+
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("--ignore-constraints")
+        .arg("old.schema.json")
+        .arg("new.schema.json");
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn schemadiff_table_schema() {
+    let wrk = Workdir::new("schemadiff_table_schema");
+    wrk.create_from_string(
+        "old.json",
+        r#"{
+  "fields": [
+    { "name": "id", "type": "integer", "constraints": { "required": true } },
+    { "name": "name", "type": "string" }
+  ]
+}"#,
+    );
+    wrk.create_from_string(
+        "new.json",
+        r#"{
+  "fields": [
+    { "name": "id", "type": "integer" },
+    { "name": "name", "type": "string" }
+  ]
+}"#,
+    );
+
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("old.json").arg("new.json");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "constraint", "old", "new", "change", "breaking"],
+        svec!["id", "nullable", "false", "true", "widened", "true"],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn schemadiff_csv() {
+    let wrk = Workdir::new("schemadiff_csv");
+    wrk.create(
+        "old.csv",
+        vec![svec!["id", "name"], svec!["1", "alice"], svec!["2", "bob"]],
+    );
+    let mut cmd = wrk.command("schema");
+    cmd.arg("old.csv");
+    wrk.assert_success(&mut cmd);
+
+    // an added column is backward compatible
+    wrk.create(
+        "new.csv",
+        vec![svec!["id", "name", "city"], svec!["3", "carl", "Boston"]],
+    );
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("--ignore-constraints")
+        .arg("old.csv.schema.json")
+        .arg("new.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "constraint", "old", "new", "change", "breaking"],
+        svec!["city", "name", "", "city", "added", "false"],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_success(&mut cmd);
+
+    // an integer column becoming a string is not
+    wrk.create("new.csv", vec![svec!["id", "name"], svec!["A3", "carl"]]);
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("--ignore-constraints")
+        .arg("old.csv.schema.json")
+        .arg("new.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "constraint", "old", "new", "change", "breaking"],
+        svec!["id", "type", "integer", "string", "changed", "true"],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn schemadiff_csv_infer_boolean() {
+    let wrk = Workdir::new("schemadiff_csv_infer_boolean");
+    wrk.create(
+        "old.csv",
+        vec![
+            svec!["id", "active"],
+            svec!["1", "true"],
+            svec!["2", "false"],
+        ],
+    );
+    wrk.create(
+        "new.csv",
+        vec![
+            svec!["id", "active"],
+            svec!["3", "yes"],
+            svec!["4", "unknown"],
+        ],
+    );
+
+    // both schemas are inferred in the same run
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("--infer-boolean")
+        .arg("--ignore-constraints")
+        .arg("old.csv")
+        .arg("new.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "constraint", "old", "new", "change", "breaking"],
+        svec!["active", "type", "boolean", "string", "changed", "true"],
+    ];
+    assert_eq!(got, expected);
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn schemadiff_csv_data_drift() {
+    let wrk = Workdir::new("schemadiff_csv_data_drift");
+    wrk.create(
+        "old.csv",
+        vec![
+            svec!["id", "name", "status"],
+            svec!["1", "alice", "open"],
+            svec!["2", "bob", "closed"],
+        ],
+    );
+    let mut cmd = wrk.command("schema");
+    cmd.arg("old.csv");
+    wrk.assert_success(&mut cmd);
+
+    // the ranges, lengths & enum values inferred from the new extract
+    // are not compared, as they vary with every extract
+    wrk.create(
+        "new.csv",
+        vec![
+            svec!["id", "name", "status"],
+            svec!["3", "christopher", "pending"],
+            svec!["400", "dan", "open"],
+        ],
+    );
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("old.csv.schema.json").arg("new.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec![
+        "field",
+        "constraint",
+        "old",
+        "new",
+        "change",
+        "breaking"
+    ]];
+    assert_eq!(got, expected);
+    wrk.assert_success(&mut cmd);
+
+    // but the schemas of the two extracts are
+    let mut cmd = wrk.command("schema");
+    cmd.arg("new.csv");
+    wrk.assert_success(&mut cmd);
+    let mut cmd = wrk.command("schemadiff");
+    cmd.arg("old.csv.schema.json").arg("new.csv.schema.json");
+    wrk.assert_err(&mut cmd);
+}
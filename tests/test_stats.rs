@@ -815,6 +815,162 @@ fn stats_leading_zero_handling() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn stats_infer_boolean() {
+    let wrk = Workdir::new("stats_infer_boolean");
+
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["col1", "col2", "col3", "col4"],
+            svec!["1", "Yes", "true", "1"],
+            svec!["0", "no", "", "2"],
+            svec!["1", "Y", "F", "0"],
+            svec!["0", "N", "False", "1"],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--typesonly")
+        .arg("--infer-boolean")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "type"],
+        svec!["col1", "Boolean"],
+        svec!["col2", "Boolean"],
+        svec!["col3", "Boolean"],
+        svec!["col4", "Integer"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn stats_infer_boolean_custom_sets() {
+    let wrk = Workdir::new("stats_infer_boolean_custom_sets");
+
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["col1", "col2"],
+            svec!["on", "yes"],
+            svec!["OFF", "no"],
+            svec!["On", "yes"],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--typesonly")
+        .arg("--infer-boolean")
+        .args(["--truthy", "on"])
+        .args(["--falsy", "off"])
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "type"],
+        svec!["col1", "Boolean"],
+        svec!["col2", "String"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn stats_infer_boolean_overlapping_sets() {
+    let wrk = Workdir::new("stats_infer_boolean_overlapping_sets");
+
+    wrk.create("data.csv", vec![svec!["col1"], svec!["1"]]);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--infer-boolean")
+        .args(["--truthy", "yes,1"])
+        .args(["--falsy", "no,1"])
+        .arg("data.csv");
+
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn stats_infer_subtypes() {
+    let wrk = Workdir::new("stats_infer_subtypes");
+
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["email", "url", "uuid", "ip", "price", "pct", "zip", "name"],
+            svec![
+                "jdoe@example.com",
+                "https://example.com/a?b=c",
+                "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "192.168.1.1",
+                "$1,234.50",
+                "12.5%",
+                "02134-1234",
+                "John"
+            ],
+            svec![
+                "",
+                "http://qsv.dathere.com",
+                "E7A8A6C4-6E0B-4C3A-9E2A-6F0C0B8D6E55",
+                "10.0.0.255",
+                "€ 12",
+                "-3 %",
+                "K1A 0B1",
+                "Jane"
+            ],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--typesonly")
+        .arg("--infer-subtypes")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "type", "subtype"],
+        svec!["email", "String", "email"],
+        svec!["url", "String", "url"],
+        svec!["uuid", "String", "uuid"],
+        svec!["ip", "String", "ipv4"],
+        svec!["price", "String", "currency"],
+        svec!["pct", "String", "percentage"],
+        svec!["zip", "String", "postalcode"],
+        svec!["name", "String", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn stats_infer_subtypes_currency_like_validate() {
+    let wrk = Workdir::new("stats_infer_subtypes_currency_like_validate");
+
+    // mixed thousands separators & more than 4 decimals are not valid currencies
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["grouping", "decimals", "amount"],
+            svec!["$1,234567", "$1.23456", "USD 1,234.56"],
+            svec!["$1,234", "$1.5", "42.50 EUR"],
+        ],
+    );
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--typesonly")
+        .arg("--infer-subtypes")
+        .arg("data.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["field", "type", "subtype"],
+        svec!["grouping", "String", ""],
+        svec!["decimals", "String", ""],
+        svec!["amount", "String", "currency"],
+    ];
+    assert_eq!(got, expected);
+}
+
 mod stats_infer_nothing {
     // Only test CSV data with headers.
     // Empty CSV data with no headers won't produce any statistical analysis.
@@ -0,0 +1,113 @@
+use crate::workdir::Workdir;
+
+fn drift_of(got: &[Vec<String>], field: &str, metric: &str) -> String {
+    got.iter()
+        .find(|row| row[0] == field && row[1] == metric)
+        .unwrap_or_else(|| panic!("no {metric} row for {field}"))[5]
+        .clone()
+}
+
+#[test]
+fn statsdiff_no_drift() {
+    let wrk = Workdir::new("statsdiff_no_drift");
+    let rows = vec![
+        svec!["id", "color", "amount"],
+        svec!["1", "red", "10.5"],
+        svec!["2", "blue", "20"],
+        svec!["3", "red", ""],
+        svec!["4", "green", "30.25"],
+    ];
+    wrk.create("old.csv", rows.clone());
+    wrk.create("new.csv", rows);
+
+    let mut cmd = wrk.command("statsdiff");
+    cmd.arg("old.csv").arg("new.csv");
+
+    wrk.assert_success(&mut cmd);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(
+        got[0],
+        svec!["field", "metric", "old", "new", "diff", "drift"]
+    );
+    assert!(got[1..].iter().all(|row| row[5] == "false"));
+    // color is categorical, so it has its psi & jsd computed
+    assert_eq!(drift_of(&got, "color", "psi"), "false");
+    assert_eq!(drift_of(&got, "color", "jsd"), "false");
+}
+
+#[test]
+fn statsdiff_drift() {
+    let wrk = Workdir::new("statsdiff_drift");
+    wrk.create(
+        "old.csv",
+        vec![
+            svec!["id", "color", "amount", "legacy"],
+            svec!["1", "red", "10", "x"],
+            svec!["2", "blue", "20", "y"],
+            svec!["3", "red", "30", "z"],
+            svec!["4", "blue", "40", "w"],
+        ],
+    );
+    wrk.create(
+        "new.csv",
+        vec![
+            svec!["id", "color", "amount", "region"],
+            svec!["one", "red", "100", "north"],
+            svec!["2", "red", "", "south"],
+            svec!["3", "red", "300", "east"],
+            svec!["4", "red", "400", "west"],
+        ],
+    );
+
+    let mut cmd = wrk.command("statsdiff");
+    cmd.arg("old.csv").arg("new.csv");
+
+    wrk.assert_err(&mut cmd);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(drift_of(&got, "id", "type"), "true");
+    assert_eq!(drift_of(&got, "color", "cardinality"), "true");
+    assert_eq!(drift_of(&got, "color", "psi"), "true");
+    assert_eq!(drift_of(&got, "color", "jsd"), "true");
+    assert_eq!(drift_of(&got, "amount", "null_rate"), "true");
+    assert_eq!(drift_of(&got, "amount", "mean"), "true");
+    assert_eq!(drift_of(&got, "legacy", "column"), "true");
+    assert_eq!(drift_of(&got, "region", "column"), "true");
+}
+
+#[test]
+fn statsdiff_thresholds() {
+    let wrk = Workdir::new("statsdiff_thresholds");
+    wrk.create(
+        "old.csv",
+        vec![
+            svec!["amount", "legacy"],
+            svec!["10", "a"],
+            svec!["20", "b"],
+        ],
+    );
+    wrk.create("new.csv", vec![svec!["amount"], svec!["11"], svec!["21"]]);
+
+    // mean moved by 6.67%, which is within the default 10% threshold,
+    // and schema changes are ignored. amount's values are all new categories,
+    // so we disable categorical drift
+    let mut cmd = wrk.command("statsdiff");
+    cmd.args(["--max-categories", "0"])
+        .arg("--ignore-schema-changes")
+        .arg("old.csv")
+        .arg("new.csv");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("statsdiff");
+    cmd.args(["--mean-threshold", "0.05"])
+        .args(["--max-categories", "0"])
+        .arg("--ignore-schema-changes")
+        .arg("old.csv")
+        .arg("new.csv");
+    wrk.assert_err(&mut cmd);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(drift_of(&got, "amount", "mean"), "true");
+    assert_eq!(drift_of(&got, "legacy", "column"), "false");
+}
@@ -115,6 +115,30 @@ fn tojsonl_boolean_1or0() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn tojsonl_not_boolean_single_value() {
+    let wrk = Workdir::new("tojsonl");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["col1", "col2"],
+            svec!["1", "y"],
+            svec!["1", "y"],
+            svec!["1", "y"],
+        ],
+    );
+
+    let mut cmd = wrk.command("tojsonl");
+    cmd.arg("in.csv");
+
+    // not treated as boolean since the domains only have one value
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"{"col1":1,"col2":"y"}
+{"col1":1,"col2":"y"}
+{"col1":1,"col2":"y"}"#;
+    assert_eq!(got, expected);
+}
+
 #[test]
 fn tojsonl_not_boolean_case_sensitive() {
     let wrk = Workdir::new("tojsonl");
@@ -188,6 +212,30 @@ fn tojsonl_boolean_yes() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn tojsonl_boolean_mixed_truthy_falsy() {
+    let wrk = Workdir::new("tojsonl");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["col1", "col2"],
+            svec!["Yes", "Mark"],
+            svec!["n", "John"],
+            svec!["n", "Bob"],
+        ],
+    );
+
+    let mut cmd = wrk.command("tojsonl");
+    cmd.arg("in.csv");
+
+    // Yes & n are both in the truthy/falsy sets, with a domain of two values
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"{"col1":true,"col2":"Mark"}
+{"col1":false,"col2":"John"}
+{"col1":false,"col2":"Bob"}"#;
+    assert_eq!(got, expected);
+}
+
 #[test]
 fn tojsonl_boolean_null() {
     let wrk = Workdir::new("tojsonl");
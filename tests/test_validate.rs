@@ -221,3 +221,468 @@ fn validate_adur_public_toilets_dataset_with_json_schema_url() {
     assert_eq!(adur_errors(), validation_error_output);
     wrk.assert_err(&mut cmd);
 }
+
+#[test]
+fn validate_custom_keywords_and_formats() {
+    let wrk = Workdir::new("validate_custom_keywords_and_formats");
+    wrk.create(
+        "codes.csv",
+        vec![
+            svec!["description", "code"],
+            svec!["Fruit", "F01"],
+            svec!["Vegetable", "V02"],
+        ],
+    );
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "price", "sold", "shipped", "code"],
+            svec![
+                "1",
+                "$1,234.56",
+                "2023-01-31",
+                "2023-01-31T10:30:00Z",
+                "F01"
+            ],
+            svec!["2", "12.5 EUR", "2023-02-30", "2023-02-01", "V02"],
+            svec!["3", "1.234,56", "2023-03-01", "not a date", "X99"],
+            svec!["4", "-€10", "", "", ""],
+        ],
+    );
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+    "type": "object",
+    "properties": {
+        "id": { "type": "integer" },
+        "price": { "type": "string", "format": "currency" },
+        "sold": { "type": ["string", "null"], "dateformat": "%Y-%m-%d" },
+        "shipped": { "type": ["string", "null"], "dateformat": true },
+        "code": { "type": ["string", "null"], "dynamicEnum": { "file": "codes.csv", "column": "code" } }
+    }
+}"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_error_output: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let expected = r#"row_number	field	error
+2	sold	"2023-02-30" does not match the date format "%Y-%m-%d"
+3	price	"1.234,56" is not a "currency"
+3	shipped	"not a date" is not a valid date
+3	code	"X99" is not a value of the lookup codes.csv column code
+"#;
+    assert_eq!(validation_error_output, expected);
+}
+
+#[test]
+fn validate_dynamic_enum_missing_lookup() {
+    let wrk = Workdir::new("validate_dynamic_enum_missing_lookup");
+    wrk.create("data.csv", vec![svec!["code"], svec!["F01"]]);
+    wrk.create_from_string(
+        "schema.json",
+        r#"{"type": "object", "properties": {"code": {"type": "string", "dynamicEnum": "missing.csv"}}}"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("schema.json");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn validate_dynamic_enum_relative_to_schema() {
+    let wrk = Workdir::new("validate_dynamic_enum_relative_to_schema");
+    wrk.create("data.csv", vec![svec!["code"], svec!["F01"], svec!["X99"]]);
+    std::fs::create_dir_all(wrk.path("schemas")).unwrap();
+    wrk.create("schemas/codes.csv", vec![svec!["code"], svec!["F01"]]);
+    wrk.create_from_string(
+        "schemas/schema.json",
+        r#"{"type": "object", "properties": {"code": {"type": "string", "dynamicEnum": "codes.csv"}}}"#,
+    );
+
+    // the lookup is next to the schema, not in the current directory
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("schemas/schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_error_output: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let expected = r#"row_number	field	error
+2	code	"X99" is not a value of the lookup codes.csv
+"#;
+    assert_eq!(validation_error_output, expected);
+}
+
+fn setup_report(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "name", "age"],
+            svec!["1", "X", "10"],
+            svec!["2", "Bob", "15"],
+            svec!["3", "Y", "30"],
+            svec!["4", "Zed", "12"],
+        ],
+    );
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+    "type": "object",
+    "properties": {
+        "id": { "type": "integer" },
+        "name": { "type": "string", "minLength": 2 },
+        "age": { "type": "integer", "minimum": 18 }
+    }
+}"#,
+    );
+    wrk
+}
+
+#[test]
+fn validate_report_jsonl() {
+    let wrk = setup_report("validate_report_jsonl");
+    let mut cmd = wrk.command("validate");
+    cmd.args(["--report-format", "jsonl"])
+        .arg("data.csv")
+        .arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let report: String = wrk.from_str(&wrk.path("data.csv.validation-errors.jsonl"));
+    let errors: Vec<serde_json::Value> = report
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(errors.len(), 5);
+    assert_eq!(
+        errors[0],
+        serde_json::json!({
+            "row_number": 1,
+            "column": "name",
+            "keyword": "minLength",
+            "instance": "X",
+            "schema_path": "/properties/name/minLength",
+            "message": "\"X\" is shorter than 2 characters"
+        })
+    );
+    assert_eq!(errors[1]["column"], "age");
+    assert_eq!(errors[1]["keyword"], "minimum");
+    assert_eq!(errors[1]["instance"], 10);
+}
+
+#[test]
+fn validate_report_json_summary() {
+    let wrk = setup_report("validate_report_json_summary");
+    let mut cmd = wrk.command("validate");
+    cmd.args(["--report-format", "json"])
+        .arg("--summary")
+        .args(["--summary-samples", "2"])
+        .arg("data.csv")
+        .arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let report: String = wrk.from_str(&wrk.path("data.csv.validation-errors.json"));
+    let errors: serde_json::Value = serde_json::from_str(&report).unwrap();
+    assert_eq!(errors.as_array().unwrap().len(), 5);
+
+    let summary: String = wrk.from_str(&wrk.path("data.csv.validation-summary.json"));
+    let summary: serde_json::Value = serde_json::from_str(&summary).unwrap();
+    assert_eq!(
+        summary,
+        serde_json::json!([
+            {"column": "age", "keyword": "minimum", "count": 3, "sample_rows": [1, 2]},
+            {"column": "name", "keyword": "minLength", "count": 2, "sample_rows": [1, 3]}
+        ])
+    );
+}
+
+#[test]
+fn validate_max_errors_per_column() {
+    let wrk = setup_report("validate_max_errors_per_column");
+    let mut cmd = wrk.command("validate");
+    cmd.args(["--max-errors-per-column", "1"])
+        .arg("--summary")
+        .arg("data.csv")
+        .arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let report: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let expected = r#"row_number	field	error
+1	name	"X" is shorter than 2 characters
+1	age	10 is less than the minimum of 18
+"#;
+    assert_eq!(report, expected);
+
+    let summary: String = wrk.from_str(&wrk.path("data.csv.validation-summary.tsv"));
+    let expected =
+        "column\tkeyword\tcount\tsample_rows\nage\tminimum\t3\t1,2,4\nname\tminLength\t2\t1,3\n";
+    assert_eq!(summary, expected);
+
+    // all the invalid rows are still split out
+    let invalid: String = wrk.from_str(&wrk.path("data.csv.invalid"));
+    assert_eq!(invalid, "id,name,age\n1,X,10\n2,Bob,15\n3,Y,30\n4,Zed,12\n");
+}
+
+#[test]
+fn validate_table_schema() {
+    let wrk = Workdir::new("validate_table_schema");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "name", "amount", "sold", "code"],
+            svec!["1", "Alice", "10", "2023-01-01", "A"],
+            svec!["2", "Bob", "NA", "2023-02-30", "B"],
+            svec!["2", "Carl", "-5", "2023-03-01", "C"],
+            svec!["NA", "Dan", "1", "2023-01-02", ""],
+        ],
+    );
+    wrk.create_from_string(
+        "tableschema.json",
+        r#"{
+    "fields": [
+        { "name": "id", "type": "integer" },
+        { "name": "name", "type": "string", "constraints": { "required": true, "pattern": "[A-Z][a-z]+" } },
+        { "name": "amount", "type": "integer", "constraints": { "minimum": 0 } },
+        { "name": "sold", "type": "date" },
+        { "name": "code", "type": "string", "constraints": { "enum": ["A", "B"] } }
+    ],
+    "missingValues": ["", "NA"],
+    "primaryKey": "id"
+}"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("tableschema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_error_output: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let expected = r#"row_number	field	error
+2	sold	"2023-02-30" does not match the date format "%Y-%m-%d"
+3	amount	-5 is less than the minimum of 0
+3	code	"C" is not one of ["A","B",null]
+3	id	"2" is a duplicate of the primary key id
+4	id	null is not of type "integer"
+"#;
+    assert_eq!(validation_error_output, expected);
+
+    let invalid_output: String = wrk.from_str(&wrk.path("data.csv.invalid"));
+    assert_eq!(
+        invalid_output,
+        "id,name,amount,sold,code\n2,Bob,NA,2023-02-30,B\n2,Carl,-5,2023-03-01,C\nNA,Dan,1,\
+         2023-01-02,\n"
+    );
+}
+
+#[test]
+fn validate_data_package_foreign_keys() {
+    let wrk = Workdir::new("validate_data_package_foreign_keys");
+    wrk.create(
+        "customers.csv",
+        vec![svec!["id", "name"], svec!["1", "Alice"], svec!["2", "Bob"]],
+    );
+    wrk.create(
+        "orders.csv",
+        vec![
+            svec!["order_id", "customer_id"],
+            svec!["100", "1"],
+            svec!["101", "9"],
+            svec!["102", "2"],
+            svec!["102", ""],
+        ],
+    );
+    wrk.create_from_string(
+        "datapackage.json",
+        r#"{
+    "name": "shop",
+    "resources": [
+        {
+            "name": "customers",
+            "path": "customers.csv",
+            "schema": {
+                "fields": [
+                    { "name": "id", "type": "integer" },
+                    { "name": "name", "type": "string" }
+                ],
+                "primaryKey": "id"
+            }
+        },
+        {
+            "name": "orders",
+            "path": "orders.csv",
+            "schema": {
+                "fields": [
+                    { "name": "order_id", "type": "integer", "constraints": { "unique": true } },
+                    { "name": "customer_id", "type": "integer" }
+                ],
+                "foreignKeys": [
+                    { "fields": "customer_id", "reference": { "resource": "customers", "fields": "id" } }
+                ]
+            }
+        }
+    ]
+}"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("orders.csv").arg("datapackage.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_error_output: String =
+        wrk.from_str(&wrk.path("orders.csv.validation-errors.tsv"));
+    let expected = r#"row_number	field	error
+2	customer_id	"9" is not a value of the foreignKey reference customers.csv column id
+4	order_id	"102" is a duplicate of the unique order_id
+"#;
+    assert_eq!(validation_error_output, expected);
+
+    // the customers resource is valid
+    let mut cmd = wrk.command("validate");
+    cmd.args(["--resource", "customers"])
+        .arg("customers.csv")
+        .arg("datapackage.json");
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn validate_json_schema_cross_row_constraints() {
+    let wrk = Workdir::new("validate_json_schema_cross_row_constraints");
+    wrk.create("codes.csv", vec![svec!["code"], svec!["A"], svec!["B"]]);
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "first", "last", "code"],
+            svec!["1", "Ann", "Lee", "A"],
+            svec!["2", "Bob", "Ray", "Z"],
+            svec!["1", "Cat", "Poe", "B"],
+            svec!["3", "Ann", "Lee", ""],
+        ],
+    );
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+    "type": "object",
+    "properties": {
+        "id": { "type": "integer" },
+        "first": { "type": "string" },
+        "last": { "type": "string" },
+        "code": { "type": ["string", "null"] }
+    },
+    "primaryKey": "id",
+    "uniqueKeys": [["first", "last"]],
+    "foreignKeys": [
+        { "fields": "code", "reference": { "path": "codes.csv", "fields": "code" } }
+    ]
+}"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_error_output: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let expected = r#"row_number	field	error
+2	code	"Z" is not a value of the foreignKey reference codes.csv column code
+3	id	"1" is a duplicate of the primary key id
+4	first, last	"Ann, Lee" is a duplicate of the unique first, last
+"#;
+    assert_eq!(validation_error_output, expected);
+
+    let invalid_output: String = wrk.from_str(&wrk.path("data.csv.invalid"));
+    assert_eq!(
+        invalid_output,
+        "id,first,last,code\n2,Bob,Ray,Z\n1,Cat,Poe,B\n3,Ann,Lee,\n"
+    );
+}
+
+#[test]
+fn validate_unique_option() {
+    let wrk = Workdir::new("validate_unique_option");
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "email"],
+            svec!["1", "a@example.com"],
+            svec!["2", "b@example.com"],
+            svec!["2", "a@example.com"],
+            svec!["3", ""],
+            svec!["4", ""],
+        ],
+    );
+    wrk.create_from_string(
+        "schema.json",
+        r#"{"type": "object", "properties": {"id": {"type": "integer"}}}"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.args(["--unique", "id;email"])
+        .arg("data.csv")
+        .arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_error_output: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let expected = r#"row_number	field	error
+3	id	"2" is a duplicate of the unique id
+3	email	"a@example.com" is a duplicate of the unique email
+"#;
+    assert_eq!(validation_error_output, expected);
+}
+
+#[test]
+fn validate_unique_sorted_keys() {
+    let wrk = Workdir::new("validate_unique_sorted_keys");
+    wrk.create(
+        "data.csv",
+        vec![svec!["id"], svec!["1"], svec!["2"], svec!["2"], svec!["3"]],
+    );
+    wrk.create("unsorted.csv", vec![svec!["id"], svec!["2"], svec!["1"]]);
+    wrk.create_from_string(
+        "schema.json",
+        r#"{"type": "object", "properties": {"id": {"type": "string"}}, "primaryKey": "id"}"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("--sorted-keys").arg("data.csv").arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_error_output: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    assert_eq!(
+        validation_error_output,
+        "row_number\tfield\terror\n3\tid\t\"2\" is a duplicate of the primary key id\n"
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("--sorted-keys")
+        .arg("unsorted.csv")
+        .arg("schema.json");
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.starts_with("The CSV is not sorted on id, at row 2."));
+}
+
+#[test]
+fn validate_json_schema_boolean_values() {
+    let wrk = Workdir::new("validate_json_schema_boolean_values");
+    wrk.create(
+        "data.csv",
+        vec![svec!["id", "active"], svec!["1", "true"], svec!["2", "yes"]],
+    );
+
+    // JSON Schema booleans are only true or false
+    wrk.create_from_string(
+        "schema.json",
+        r#"{ "type": "object", "properties": { "active": { "type": "boolean" } } }"#,
+    );
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    // unless the field lists its true & false values
+    wrk.create_from_string(
+        "schema.json",
+        r#"{ "type": "object", "properties": { "active": { "type": "boolean",
+            "x-trueValues": ["true", "yes"], "x-falseValues": ["false", "no"] } } }"#,
+    );
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("schema.json");
+    wrk.assert_success(&mut cmd);
+}
@@ -37,10 +37,16 @@ mod test_applydp;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_behead;
 #[cfg(any(feature = "full", feature = "lite"))]
+mod test_cast;
+#[cfg(any(feature = "full", feature = "lite"))]
 mod test_cat;
 mod test_combos;
 mod test_comments;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_correlate;
 mod test_count;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_crosstab;
 mod test_dedup;
 #[cfg(feature = "full")]
 mod test_diff;
@@ -65,7 +71,11 @@ mod test_fmt;
 #[cfg(all(feature = "foreach", target_family = "unix"))]
 mod test_foreach;
 mod test_frequency;
+#[cfg(feature = "from")]
+mod test_from;
 mod test_headers;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_histogram;
 mod test_index;
 mod test_input;
 #[cfg(any(feature = "full", feature = "lite"))]
@@ -76,6 +86,8 @@ mod test_jsonl;
 mod test_luau;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_partition;
+#[cfg(feature = "full")]
+mod test_patch;
 mod test_pseudo;
 #[cfg(feature = "python")]
 mod test_py;
@@ -87,6 +99,8 @@ mod test_safenames;
 mod test_sample;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_schema;
+#[cfg(any(feature = "full", feature = "lite"))]
+mod test_schemadiff;
 mod test_search;
 mod test_searchset;
 mod test_select;
@@ -97,6 +111,8 @@ mod test_sort;
 mod test_split;
 mod test_stats;
 #[cfg(any(feature = "full", feature = "lite"))]
+mod test_statsdiff;
+#[cfg(any(feature = "full", feature = "lite"))]
 mod test_table;
 #[cfg(any(feature = "full", feature = "lite"))]
 mod test_tojsonl;